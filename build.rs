@@ -0,0 +1,29 @@
+fn main() {
+    #[cfg(feature = "std")]
+    exception_shim();
+}
+
+/// Compiles the C++ shim of `exception::try_call`, under a symbol name unique to this version.
+#[cfg(feature = "std")]
+fn exception_shim() {
+    use std::env;
+
+    println!("cargo:rerun-if-changed=src/exception.cpp");
+    if env::var_os("CARGO_CFG_UNIX").is_none() && env::var_os("CARGO_CFG_WINDOWS").is_none() {
+        return;
+    }
+
+    let version = env::var("CARGO_PKG_VERSION").unwrap().replace(['.', '-', '+'], "_");
+    let symbol = format!("bridgeless_try_call_{version}");
+    println!("cargo:rustc-env=BRIDGELESS_TRY_CALL={symbol}");
+
+    let mut build = cc::Build::new();
+    build
+        .cpp(true)
+        .file("src/exception.cpp")
+        .define("BRIDGELESS_TRY_CALL", symbol.as_str());
+    if env::var("CARGO_CFG_TARGET_ENV").unwrap() == "msvc" {
+        build.flag("/EHsc");
+    }
+    build.compile("bridgeless_exception");
+}
@@ -0,0 +1,75 @@
+//! Compiles the C++ fixtures in `fixtures/` into a static library, and the ones in
+//! `fixtures/shared/` into a shared library, using the C++ compiler found by `cc`.
+//!
+//! The fixtures and their Rust declarations assume the Itanium C++ ABI, an LP64 data model and
+//! the layouts of libstdc++, so they are only built on Linux. Elsewhere the crate and its tests
+//! are empty, as the `bridgeless_fixtures` cfg is not set.
+
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+fn main() {
+    println!("cargo:rustc-check-cfg=cfg(bridgeless_fixtures)");
+    if env::var("CARGO_CFG_TARGET_OS").unwrap() != "linux" {
+        println!("cargo:warning=the C++ fixtures are only supported on Linux, skipping them");
+        return;
+    }
+    println!("cargo:rustc-cfg=bridgeless_fixtures");
+
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    let fixtures_dir = Path::new("fixtures");
+
+    // Also links the C++ runtime of the target
+    build(fixtures_dir)
+        .files(fixture_sources(fixtures_dir))
+        .compile("bridgeless_fixtures");
+
+    // Fixtures in `fixtures/shared` are linked dynamically, so that their symbols are exported
+    let shared_build = build(fixtures_dir)
+        .files(fixture_sources(&fixtures_dir.join("shared")))
+        .out_dir(out_dir.join("shared"))
+        .clone();
+    let shared_objects = shared_build.compile_intermediates();
+
+    let shared_lib = out_dir.join("libbridgeless_shared_fixtures.so");
+    let mut link = shared_build.get_compiler().to_command();
+    link.arg("-shared").args(&shared_objects).arg("-o").arg(&shared_lib);
+    let status = link.status().unwrap_or_else(|e| panic!("failed to run {link:?}: {e}"));
+    if !status.success() {
+        panic!("{link:?} exited with {status}");
+    }
+
+    println!("cargo:rustc-link-search=native={}", out_dir.display());
+    println!("cargo:rustc-link-lib=dylib=bridgeless_shared_fixtures");
+    println!("cargo:rustc-link-arg=-Wl,-rpath,{}", out_dir.display());
+}
+
+fn build(include_dir: &Path) -> cc::Build {
+    let mut build = cc::Build::new();
+    build
+        .cpp(true)
+        .std("c++17")
+        .opt_level(1)
+        .pic(true)
+        .warnings(true)
+        .include(include_dir);
+    build
+}
+
+fn fixture_sources(dir: &Path) -> Vec<PathBuf> {
+    println!("cargo:rerun-if-changed={}", dir.display());
+
+    let mut sources: Vec<_> = dir
+        .read_dir()
+        .expect("fixtures directory should exist")
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "cpp"))
+        .collect();
+    sources.sort();
+    for source in &sources {
+        println!("cargo:rerun-if-changed={}", source.display());
+    }
+    sources
+}
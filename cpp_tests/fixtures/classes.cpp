@@ -0,0 +1,119 @@
+// Fixtures for class declarations: virtual calls in both directions, single inheritance,
+// overrides and vtable gaps.
+//
+// Itanium C++ ABI compilers reuse the tail padding of a base class with a vtable for the fields
+// of derived classes, which `#[repr(C)]` Rust layouts cannot express. The data of every base
+// class is therefore kept a multiple of the pointer size.
+
+#include <cstddef>
+#include <cstdint>
+
+struct Shape {
+    int64_t id;
+
+    Shape(int64_t id) : id(id) {}
+
+    virtual int64_t area() const {
+        return 0;
+    }
+    virtual int64_t scale(int64_t factor) {
+        id *= factor;
+        return id;
+    }
+};
+
+struct Square : Shape {
+    int64_t side;
+
+    Square(int64_t id, int64_t side) : Shape(id), side(side) {}
+
+    int64_t area() const override {
+        return side * side;
+    }
+    int64_t scale(int64_t factor) override {
+        side *= factor;
+        return side;
+    }
+    virtual int64_t perimeter() const {
+        return 4 * side;
+    }
+};
+
+// Only `kind` and `weight` are bound on the Rust side, with `#[offset(3)]` on `weight`.
+struct Widget {
+    int64_t weight;
+
+    Widget(int64_t weight) : weight(weight) {}
+
+    virtual int64_t kind() const {
+        return 1;
+    }
+    virtual void unbound_1() {}
+    virtual void unbound_2() {}
+    virtual int64_t get_weight() const {
+        return weight;
+    }
+};
+
+static_assert(sizeof(Shape) == sizeof(void*) + 8, "unexpected Shape layout");
+static_assert(sizeof(Square) == sizeof(void*) + 16, "unexpected Square layout");
+
+// Virtual destructors would add vtable slots. Objects are only freed through their concrete type.
+#pragma GCC diagnostic ignored "-Wdelete-non-virtual-dtor"
+
+extern "C" {
+
+Shape* shape_new(int64_t id) {
+    return new Shape(id);
+}
+
+void shape_free(Shape* shape) {
+    delete shape;
+}
+
+Square* square_new(int64_t id, int64_t side) {
+    return new Square(id, side);
+}
+
+void square_free(Square* square) {
+    delete square;
+}
+
+Widget* widget_new(int64_t weight) {
+    return new Widget(weight);
+}
+
+void widget_free(Widget* widget) {
+    delete widget;
+}
+
+int64_t shape_area(const Shape* shape) {
+    return shape->area();
+}
+
+int64_t shape_scale(Shape* shape, int64_t factor) {
+    return shape->scale(factor);
+}
+
+int64_t square_perimeter(const Square* square) {
+    return square->perimeter();
+}
+
+int64_t widget_kind(const Widget* widget) {
+    return widget->kind();
+}
+
+int64_t widget_weight(const Widget* widget) {
+    return widget->get_weight();
+}
+
+// Sums the areas of an array of shapes, as a game would when iterating over its objects.
+int64_t shapes_total_area(const Shape* const* shapes, size_t count) {
+    int64_t total = 0;
+    for (size_t i = 0; i < count; i++) {
+        total += shapes[i]->area();
+    }
+    return total;
+}
+
+}
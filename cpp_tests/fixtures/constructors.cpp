@@ -0,0 +1,46 @@
+// Fixtures for C++ constructors called from Rust: a class whose constructor initializes private
+// fields and registers the instance.
+
+#include <cstdint>
+
+class Engine {
+    int64_t rpm;
+    int64_t serial;
+
+    static int64_t next_serial;
+
+public:
+    Engine(int64_t rpm);
+
+    virtual int64_t power() const {
+        return rpm * 2;
+    }
+
+    int64_t get_serial() const {
+        return serial;
+    }
+};
+
+int64_t Engine::next_serial = 0;
+
+Engine::Engine(int64_t rpm) : rpm(rpm), serial(++next_serial) {}
+
+extern "C" {
+
+Engine* engine_new(int64_t rpm) {
+    return new Engine(rpm);
+}
+
+void engine_free(Engine* engine) {
+    delete engine;
+}
+
+int64_t engine_power(const Engine* engine) {
+    return engine->power();
+}
+
+int64_t engine_serial(const Engine* engine) {
+    return engine->get_serial();
+}
+
+}
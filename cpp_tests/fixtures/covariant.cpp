@@ -0,0 +1,75 @@
+// Fixtures for covariant return types: `Dog::clone` returns a `Dog*` where the `Animal::clone`
+// it overrides returns an `Animal*`.
+
+#include <cstdint>
+
+struct Animal {
+    int64_t legs;
+
+    Animal(int64_t legs) : legs(legs) {}
+
+    virtual Animal* clone() const {
+        return new Animal(*this);
+    }
+    virtual int64_t sound() const {
+        return 0;
+    }
+};
+
+struct Dog : Animal {
+    int64_t tricks;
+
+    Dog(int64_t tricks) : Animal(4), tricks(tricks) {}
+
+    Dog* clone() const override {
+        return new Dog(*this);
+    }
+    int64_t sound() const override {
+        return 1;
+    }
+    virtual int64_t perform() const {
+        return tricks;
+    }
+};
+
+static_assert(sizeof(Dog) == sizeof(void*) + 16, "unexpected Dog layout");
+
+// Virtual destructors would add vtable slots. Objects are only freed through their concrete type.
+#pragma GCC diagnostic ignored "-Wdelete-non-virtual-dtor"
+
+extern "C" {
+
+Animal* animal_new(int64_t legs) {
+    return new Animal(legs);
+}
+
+void animal_free(Animal* animal) {
+    delete animal;
+}
+
+Dog* dog_new(int64_t tricks) {
+    return new Dog(tricks);
+}
+
+void dog_free(Dog* dog) {
+    delete dog;
+}
+
+Animal* animal_clone(const Animal* animal) {
+    return animal->clone();
+}
+
+// Relies on the override stored in the `Animal::clone` slot of a `Dog` returning a `Dog*`
+Dog* dog_clone(const Dog* dog) {
+    return dog->clone();
+}
+
+int64_t dog_perform(const Dog* dog) {
+    return dog->perform();
+}
+
+int64_t animal_sound(const Animal* animal) {
+    return animal->sound();
+}
+
+}
@@ -0,0 +1,63 @@
+// Fixtures for virtual destructors: a base class and a derived class counting destructor calls.
+
+#include <cstdint>
+#include <new>
+
+extern "C" {
+int64_t resource_destructions = 0;
+int64_t texture_destructions = 0;
+}
+
+struct Resource {
+    int64_t id;
+
+    Resource(int64_t id) : id(id) {}
+
+    virtual ~Resource() {
+        resource_destructions++;
+    }
+    virtual int64_t size() const {
+        return 0;
+    }
+};
+
+struct Texture : Resource {
+    int64_t width;
+    int64_t height;
+
+    Texture(int64_t id, int64_t width, int64_t height) : Resource(id), width(width), height(height) {}
+
+    ~Texture() override {
+        texture_destructions++;
+    }
+    int64_t size() const override {
+        return width * height;
+    }
+    virtual bool is_square() const {
+        return width == height;
+    }
+};
+
+extern "C" {
+
+Texture* texture_new(int64_t id, int64_t width, int64_t height) {
+    return new Texture(id, width, height);
+}
+
+void resource_delete(Resource* resource) {
+    delete resource;
+}
+
+void resource_destroy(Resource* resource) {
+    resource->~Resource();
+}
+
+int64_t resource_size(const Resource* resource) {
+    return resource->size();
+}
+
+void raw_delete(void* ptr) {
+    ::operator delete(ptr);
+}
+
+}
@@ -0,0 +1,34 @@
+// Fixtures for C++ exceptions thrown by virtual methods called from Rust.
+
+#include <cstdint>
+#include <stdexcept>
+
+struct Calculator {
+    int64_t computed;
+
+    Calculator() : computed(0) {}
+
+    // Throws `std::domain_error` when dividing by zero, and the bare divisor when it is negative
+    virtual int64_t divide(int64_t dividend, int64_t divisor) {
+        if (divisor == 0) {
+            throw std::domain_error("division by zero");
+        }
+        if (divisor < 0) {
+            throw divisor;
+        }
+        computed++;
+        return dividend / divisor;
+    }
+};
+
+extern "C" {
+
+Calculator* calculator_new() {
+    return new Calculator();
+}
+
+void calculator_free(Calculator* calculator) {
+    delete calculator;
+}
+
+}
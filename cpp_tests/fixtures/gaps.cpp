@@ -0,0 +1,55 @@
+// Fixtures for vtables with slots which are not declared in Rust: classes declare their methods
+// at byte offsets, leaving gaps.
+
+#include <cstdint>
+
+struct Widget {
+    int64_t width;
+    int64_t height;
+
+    Widget(int64_t width, int64_t height) : width(width), height(height) {}
+
+    virtual int64_t id() const {
+        return 1;
+    }
+
+    // Not declared in Rust
+    virtual int64_t secret() const {
+        return 42;
+    }
+    virtual int64_t version() const {
+        return 3;
+    }
+
+    virtual int64_t area() const {
+        return width * height;
+    }
+};
+
+struct Panel : Widget {
+    int64_t depth_;
+
+    Panel(int64_t width, int64_t height, int64_t depth)
+        : Widget(width, height), depth_(depth) {}
+
+    // Not declared in Rust
+    virtual int64_t layer() const {
+        return 7;
+    }
+
+    virtual int64_t depth() const {
+        return depth_;
+    }
+};
+
+extern "C" {
+
+Panel* panel_new(int64_t width, int64_t height, int64_t depth) {
+    return new Panel(width, height, depth);
+}
+
+void panel_free(Panel* panel) {
+    delete panel;
+}
+
+}
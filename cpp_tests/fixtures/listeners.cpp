@@ -0,0 +1,35 @@
+// Fixtures for in-place construction: a subsystem keeping pointers to registered listeners.
+
+#include <algorithm>
+#include <cstdint>
+#include <vector>
+
+struct Listener {
+    int64_t channel;
+
+    virtual int64_t notify(int64_t event) = 0;
+};
+
+static std::vector<Listener*> listeners;
+
+extern "C" {
+
+void listener_register(Listener* listener) {
+    listeners.push_back(listener);
+}
+
+void listener_unregister(Listener* listener) {
+    listeners.erase(std::remove(listeners.begin(), listeners.end(), listener), listeners.end());
+}
+
+int64_t listeners_broadcast(int64_t channel, int64_t event) {
+    int64_t total = 0;
+    for (Listener* listener : listeners) {
+        if (listener->channel == channel) {
+            total += listener->notify(event);
+        }
+    }
+    return total;
+}
+
+}
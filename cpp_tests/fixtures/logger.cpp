@@ -0,0 +1,53 @@
+// Fixtures for variadic methods: a printf-style logger interface.
+
+#include <cstdarg>
+#include <cstdint>
+#include <cstdio>
+#include <cstring>
+
+struct Logger {
+    int64_t count;
+    char last[128];
+
+    Logger() : count(0), last{} {}
+
+    virtual int32_t log(const char* fmt, ...) {
+        va_list args;
+        va_start(args, fmt);
+        int32_t len = vsnprintf(last, sizeof(last), fmt, args);
+        va_end(args);
+        count++;
+        return len;
+    }
+
+    virtual int64_t level() const {
+        return 1;
+    }
+
+    int32_t append(const char* fmt, ...);
+};
+
+int32_t Logger::append(const char* fmt, ...) {
+    size_t offset = strlen(last);
+    va_list args;
+    va_start(args, fmt);
+    int32_t len = vsnprintf(last + offset, sizeof(last) - offset, fmt, args);
+    va_end(args);
+    return len;
+}
+
+extern "C" {
+
+int64_t logger_level(const Logger* logger) {
+    return logger->level();
+}
+
+Logger* logger_new() {
+    return new Logger();
+}
+
+void logger_free(Logger* logger) {
+    delete logger;
+}
+
+}
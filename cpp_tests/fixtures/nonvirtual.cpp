@@ -0,0 +1,43 @@
+// Fixtures for non-virtual methods bound by RVA or address. Their symbols are referenced from
+// Rust only to compute the addresses the bindings are tested against.
+
+#include <cstdint>
+
+struct Gauge {
+    int64_t level;
+
+    Gauge(int64_t level) : level(level) {}
+
+    virtual int64_t capacity() const {
+        return 100;
+    }
+
+    int64_t remaining() const;
+    void fill(int64_t amount);
+};
+
+int64_t Gauge::remaining() const {
+    return capacity() - level;
+}
+
+void Gauge::fill(int64_t amount) {
+    level += amount;
+    if (level > capacity()) {
+        level = capacity();
+    }
+}
+
+// Virtual destructors would add vtable slots. Objects are only freed through their concrete type.
+#pragma GCC diagnostic ignored "-Wdelete-non-virtual-dtor"
+
+extern "C" {
+
+Gauge* gauge_new(int64_t level) {
+    return new Gauge(level);
+}
+
+void gauge_free(Gauge* gauge) {
+    delete gauge;
+}
+
+}
@@ -0,0 +1,43 @@
+// Fixtures for overloaded virtual methods, whose slot order depends on the ABI.
+
+#include <cstdint>
+
+struct Brush {
+    int64_t width;
+
+    Brush(int64_t width) : width(width) {}
+
+    virtual int64_t paint(int64_t length) const {
+        return width * length;
+    }
+    virtual int64_t erase() const {
+        return -width;
+    }
+    virtual int64_t paint(double length) const {
+        return (int64_t)(width * length * 10);
+    }
+};
+
+extern "C" {
+
+Brush* brush_new(int64_t width) {
+    return new Brush(width);
+}
+
+void brush_free(Brush* brush) {
+    delete brush;
+}
+
+int64_t brush_paint_int(const Brush* brush, int64_t length) {
+    return brush->paint(length);
+}
+
+int64_t brush_paint_double(const Brush* brush, double length) {
+    return brush->paint(length);
+}
+
+int64_t brush_erase(const Brush* brush) {
+    return brush->erase();
+}
+
+}
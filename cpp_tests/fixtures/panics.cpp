@@ -0,0 +1,57 @@
+// Fixtures for panics in Rust implementations of virtual methods called from C++.
+
+#include <cstdint>
+
+struct Handler {
+    int64_t handled;
+
+    virtual int64_t handle(int64_t event) = 0;
+};
+
+struct Validator {
+    int64_t checks;
+
+    virtual bool validate(int64_t value) const = 0;
+    virtual int64_t limit() const = 0;
+};
+
+extern "C" {
+
+int64_t handler_cleanups = 0;
+
+}
+
+namespace {
+
+struct Cleanup {
+    ~Cleanup() {
+        handler_cleanups++;
+    }
+};
+
+}
+
+extern "C" {
+
+int64_t handler_dispatch(Handler* handler, int64_t event) {
+    Cleanup cleanup;
+    handler->handled++;
+    return handler->handle(event);
+}
+
+int64_t validator_count_valid(Validator* validator, int64_t count) {
+    int64_t valid = 0;
+    for (int64_t i = 0; i < count; i++) {
+        validator->checks++;
+        if (validator->validate(i)) {
+            valid++;
+        }
+    }
+    return valid;
+}
+
+int64_t validator_limit(const Validator* validator) {
+    return validator->limit();
+}
+
+}
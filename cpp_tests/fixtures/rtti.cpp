@@ -0,0 +1,60 @@
+// Fixtures for vtable lookup by RTTI name: a namespaced class, and a struct deriving from it.
+
+#include <cstdint>
+
+namespace game {
+
+class Player {
+public:
+    int64_t health;
+
+    Player(int64_t health) : health(health) {}
+
+    virtual int64_t max_health() const {
+        return 100;
+    }
+    virtual void damage(int64_t amount) {
+        health -= amount;
+    }
+};
+
+struct Boss : Player {
+    int64_t phase;
+
+    Boss(int64_t health) : Player(health), phase(1) {}
+
+    int64_t max_health() const override {
+        return 1000;
+    }
+    void damage(int64_t amount) override {
+        Player::damage(amount / 2);
+    }
+    virtual int64_t enrage() {
+        return ++phase;
+    }
+};
+
+}
+
+// Virtual destructors would add vtable slots. Objects are only freed through their concrete type.
+#pragma GCC diagnostic ignored "-Wdelete-non-virtual-dtor"
+
+extern "C" {
+
+game::Player* player_new(int64_t health) {
+    return new game::Player(health);
+}
+
+void player_free(game::Player* player) {
+    delete player;
+}
+
+game::Boss* boss_new(int64_t health) {
+    return new game::Boss(health);
+}
+
+void boss_free(game::Boss* boss) {
+    delete boss;
+}
+
+}
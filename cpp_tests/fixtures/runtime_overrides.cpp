@@ -0,0 +1,86 @@
+// Fixtures for overriding the methods of C++ classes at runtime: enemies spawned by C++, with a
+// callback run on each new instance, and a vtable only known at runtime.
+
+#include <cstdint>
+#include <typeinfo>
+
+struct Enemy {
+    int64_t health;
+    int64_t strength;
+
+    Enemy(int64_t health, int64_t strength) : health(health), strength(strength) {}
+
+    virtual ~Enemy();
+
+    virtual int64_t attack() const {
+        return strength;
+    }
+
+    virtual int64_t take_damage(int64_t damage) {
+        health -= damage;
+        return health;
+    }
+};
+
+// Enemies with vtables of their own, each overridden by a single test so that `apply` does not
+// depend on the order of the tests
+struct Boss : Enemy {
+    using Enemy::Enemy;
+};
+
+struct Minion : Enemy {
+    using Enemy::Enemy;
+};
+
+extern "C" {
+
+int64_t enemies_destroyed = 0;
+
+void (*enemy_spawn_hook)(Enemy* enemy) = nullptr;
+
+}
+
+Enemy::~Enemy() {
+    enemies_destroyed++;
+}
+
+extern "C" {
+
+Enemy* enemy_spawn(int64_t health, int64_t strength) {
+    Enemy* enemy = new Enemy(health, strength);
+    if (enemy_spawn_hook) {
+        enemy_spawn_hook(enemy);
+    }
+    return enemy;
+}
+
+void enemy_free(Enemy* enemy) {
+    delete enemy;
+}
+
+int64_t enemy_attack(const Enemy* enemy) {
+    return enemy->attack();
+}
+
+int64_t enemy_take_damage(Enemy* enemy, int64_t damage) {
+    return enemy->take_damage(damage);
+}
+
+bool enemy_is_exact(const Enemy* enemy) {
+    return typeid(*enemy) == typeid(Enemy);
+}
+
+const void* enemy_vtable() {
+    Enemy enemy(0, 0);
+    return *reinterpret_cast<const void**>(&enemy);
+}
+
+Boss* boss_spawn(int64_t health, int64_t strength) {
+    return new Boss(health, strength);
+}
+
+Minion* minion_spawn(int64_t health, int64_t strength) {
+    return new Minion(health, strength);
+}
+
+}
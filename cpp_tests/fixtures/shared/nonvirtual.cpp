@@ -0,0 +1,42 @@
+// Fixtures for non-virtual methods bound by symbol name, compiled into a shared library so that
+// their symbols are exported.
+
+#include <cstdint>
+
+struct Counter {
+    int64_t count;
+
+    Counter(int64_t count) : count(count) {}
+
+    virtual int64_t step() const {
+        return 1;
+    }
+
+    int64_t get() const;
+    void advance(int64_t times);
+};
+
+int64_t Counter::get() const {
+    return count;
+}
+
+void Counter::advance(int64_t times) {
+    for (int64_t i = 0; i < times; i++) {
+        count += step();
+    }
+}
+
+// Virtual destructors would add vtable slots. Objects are only freed through their concrete type.
+#pragma GCC diagnostic ignored "-Wdelete-non-virtual-dtor"
+
+extern "C" {
+
+Counter* counter_new(int64_t count) {
+    return new Counter(count);
+}
+
+void counter_free(Counter* counter) {
+    delete counter;
+}
+
+}
@@ -0,0 +1,63 @@
+// Fixtures for virtual methods returning classes by value, which may be returned through a
+// hidden pointer depending on the ABI.
+
+#include <cstdint>
+
+struct Vector3 {
+    double x, y, z;
+};
+
+struct Extent {
+    int32_t width, height;
+};
+
+// Not trivially copyable, so always returned through a hidden pointer
+struct Handle {
+    int64_t id;
+
+    Handle(int64_t id) : id(id) {}
+    Handle(const Handle& other) : id(other.id) {}
+    ~Handle() {}
+};
+
+struct Transform {
+    int64_t scale;
+
+    Transform(int64_t scale) : scale(scale) {}
+
+    virtual Vector3 position() const {
+        return {1.0 * scale, 2.0 * scale, 3.0 * scale};
+    }
+    virtual Extent extent() const {
+        return {(int32_t)scale, (int32_t)scale * 2};
+    }
+    virtual Handle handle(int64_t offset) const {
+        return Handle(scale + offset);
+    }
+};
+
+extern "C" {
+
+Transform* transform_new(int64_t scale) {
+    return new Transform(scale);
+}
+
+void transform_free(Transform* transform) {
+    delete transform;
+}
+
+double transform_position_sum(const Transform* transform) {
+    Vector3 position = transform->position();
+    return position.x + position.y + position.z;
+}
+
+int64_t transform_area(const Transform* transform) {
+    Extent extent = transform->extent();
+    return (int64_t)extent.width * extent.height;
+}
+
+int64_t transform_handle_id(const Transform* transform, int64_t offset) {
+    return transform->handle(offset).id;
+}
+
+}
@@ -0,0 +1,120 @@
+// Fixtures for the `bridgeless::stl` views.
+
+#include <cstddef>
+#include <cstdint>
+#include <list>
+#include <map>
+#include <memory>
+#include <set>
+#include <string>
+#include <unordered_map>
+#include <unordered_set>
+#include <vector>
+
+struct StlFields {
+    std::string name;
+    std::u16string wide_name;
+    std::vector<int32_t> values;
+    std::unique_ptr<int32_t> boxed;
+    std::shared_ptr<int32_t> shared;
+    std::shared_ptr<int32_t> shared_copy;
+    std::unique_ptr<int32_t> empty_box;
+    std::vector<int64_t> empty_values;
+};
+
+extern "C" {
+
+StlFields* stl_fields_new(const char* name, size_t value_count) {
+    auto fields = new StlFields();
+    fields->name = name;
+    for (const char* c = name; *c; c++) {
+        fields->wide_name.push_back(static_cast<char16_t>(*c));
+    }
+    for (size_t i = 0; i < value_count; i++) {
+        fields->values.push_back(static_cast<int32_t>(i * 10));
+    }
+    fields->boxed = std::make_unique<int32_t>(42);
+    fields->shared = std::make_shared<int32_t>(1337);
+    fields->shared_copy = fields->shared;
+    return fields;
+}
+
+void stl_fields_free(StlFields* fields) {
+    delete fields;
+}
+
+const char* stl_fields_name(const StlFields* fields) {
+    return fields->name.c_str();
+}
+
+size_t stl_fields_name_len(const StlFields* fields) {
+    return fields->name.size();
+}
+
+int64_t stl_fields_values_sum(const StlFields* fields) {
+    int64_t sum = 0;
+    for (auto v : fields->values) {
+        sum += v;
+    }
+    return sum;
+}
+
+int32_t stl_fields_boxed(const StlFields* fields) {
+    return *fields->boxed;
+}
+
+size_t stl_fields_shared_use_count(const StlFields* fields) {
+    return fields->shared.use_count();
+}
+
+}
+
+struct Entity {
+    int32_t id;
+};
+
+struct Registry {
+    std::list<int32_t> list;
+    std::set<int32_t> set;
+    std::multiset<int32_t> multiset;
+    std::map<int32_t, Entity*> entities;
+    std::multimap<int32_t, int64_t> multimap;
+    std::unordered_map<int32_t, Entity*> entities_by_hash;
+    std::unordered_set<std::string> names;
+    std::vector<Entity*> entity_list;
+    std::list<std::string> empty_list;
+    std::map<int32_t, int32_t> empty_map;
+    std::unordered_map<int32_t, int32_t> empty_unordered_map;
+};
+
+extern "C" {
+
+// Builds a registry of `count` entities. Keys are inserted out of order to exercise tree
+// traversal. `count` should not be a multiple of 7.
+Registry* stl_registry_new(int32_t count) {
+    auto registry = new Registry();
+    for (int32_t i = 0; i < count; i++) {
+        int32_t key = (i * 7) % count;
+        auto entity = new Entity{key};
+
+        registry->list.push_back(key);
+        registry->set.insert(key);
+        registry->multiset.insert(key / 2);
+        registry->entities.emplace(key, entity);
+        registry->multimap.emplace(key / 2, static_cast<int64_t>(key) * 100);
+        registry->entities_by_hash.emplace(key, entity);
+        registry->names.insert("entity #" + std::to_string(key));
+        registry->entity_list.push_back(entity);
+        registry->entity_list.push_back(nullptr);
+    }
+    return registry;
+}
+
+void stl_registry_free(Registry* registry) {
+    for (auto& [_, entity] : registry->entities) {
+        delete entity;
+    }
+    delete registry;
+}
+
+}
@@ -0,0 +1,57 @@
+// Fixtures for generic classes: a class template instantiated for several element types, whose
+// virtual methods are overridden by a generic Rust class.
+
+#include <cstdint>
+
+template <typename T>
+struct Array {
+    int64_t len;
+    T items[4];
+
+    Array(int64_t len, T fill) : len(len) {
+        for (int64_t i = 0; i < 4; i++) {
+            items[i] = fill;
+        }
+    }
+
+    virtual T get(int64_t index) const {
+        return items[index];
+    }
+    virtual void set(int64_t index, T value) {
+        items[index] = value;
+    }
+    virtual T sum() const {
+        T total = 0;
+        for (int64_t i = 0; i < len; i++) {
+            total += get(i);
+        }
+        return total;
+    }
+};
+
+static_assert(sizeof(Array<int32_t>) == sizeof(void*) + 24, "unexpected Array<int32_t> layout");
+static_assert(sizeof(Array<double>) == sizeof(void*) + 40, "unexpected Array<double> layout");
+
+#pragma GCC diagnostic ignored "-Wdelete-non-virtual-dtor"
+
+#define ARRAY_FIXTURES(name, T)                                 \
+    Array<T>* array_##name##_new(int64_t len, T fill) {         \
+        return new Array<T>(len, fill);                         \
+    }                                                           \
+    void array_##name##_free(Array<T>* array) {                 \
+        delete array;                                           \
+    }                                                           \
+    T array_##name##_get(const Array<T>* array, int64_t i) {    \
+        return array->get(i);                                   \
+    }                                                           \
+    void array_##name##_set(Array<T>* array, int64_t i, T v) {  \
+        array->set(i, v);                                       \
+    }                                                           \
+    T array_##name##_sum(const Array<T>* array) {               \
+        return array->sum();                                    \
+    }
+
+extern "C" {
+ARRAY_FIXTURES(i32, int32_t)
+ARRAY_FIXTURES(f64, double)
+}
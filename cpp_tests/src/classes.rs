@@ -0,0 +1,154 @@
+//! Class declarations for the C++ classes in `fixtures/classes.cpp`, along with Rust classes
+//! deriving from them.
+//!
+//! The C++ classes have no Rust implementation of their virtual methods, so Rust classes deriving
+//! from them must override all of them.
+
+use bridgeless::{class, class_impl, CRef, CRefMut, Cls, DynCls, Impl};
+
+#[repr(C)]
+pub struct Shape {
+    pub id: i64,
+}
+
+#[class]
+pub trait Shape {
+    fn area(&self) -> i64;
+    fn scale(&mut self, factor: i64) -> i64;
+}
+
+#[repr(C)]
+pub struct Square {
+    pub side: i64,
+}
+
+#[class]
+pub trait Square: Shape_Meta {
+    fn perimeter(&self) -> i64;
+}
+
+// Required to derive from `Square` in Rust
+#[class_impl]
+impl Shape_Impl for Impl<Square> {}
+
+/// Widget, whose vtable has two unbound slots between `kind` and `weight`.
+#[repr(C)]
+pub struct Widget {
+    pub weight: i64,
+}
+
+#[class]
+pub trait Widget {
+    fn kind(&self) -> i64;
+    #[offset(3)]
+    fn weight(&self) -> i64;
+}
+
+extern "C" {
+    pub fn shape_new(id: i64) -> *mut Cls<Shape>;
+    pub fn shape_free(shape: *mut Cls<Shape>);
+    pub fn square_new(id: i64, side: i64) -> *mut Cls<Square>;
+    pub fn square_free(square: *mut Cls<Square>);
+    pub fn widget_new(weight: i64) -> *mut Cls<Widget>;
+    pub fn widget_free(widget: *mut Cls<Widget>);
+
+    pub fn shape_area(shape: CRef<'_, Shape>) -> i64;
+    pub fn shape_scale(shape: CRefMut<'_, Shape>, factor: i64) -> i64;
+    pub fn square_perimeter(square: CRef<'_, Square>) -> i64;
+    pub fn widget_kind(widget: CRef<'_, Widget>) -> i64;
+    pub fn widget_weight(widget: CRef<'_, Widget>) -> i64;
+    pub fn shapes_total_area(shapes: *const CRef<'_, Shape>, count: usize) -> i64;
+}
+
+/// Circle: Shape, implemented in Rust.
+#[repr(C)]
+pub struct Circle {
+    pub radius: i64,
+}
+
+#[class]
+pub trait Circle: Shape_Meta {}
+
+#[class_impl]
+impl Shape_Impl for Impl<Circle> {
+    fn area(&self) -> i64 {
+        3 * self.radius * self.radius
+    }
+
+    fn scale(&mut self, factor: i64) -> i64 {
+        self.radius *= factor;
+        self.radius
+    }
+}
+
+impl Circle {
+    pub fn new(id: i64, data: Circle) -> Cls<Circle> {
+        Cls::new(CircleLayout(ShapeLayout((), Shape { id }), data))
+    }
+}
+
+/// Tile: Square, implemented in Rust.
+#[repr(C)]
+pub struct Tile {
+    pub border: i64,
+}
+
+#[class]
+pub trait Tile: Square_Meta {}
+
+fn outer_side(tile: &DynCls<Tile>) -> i64 {
+    tile.upcast::<Square>().side + 2 * tile.border
+}
+
+#[class_impl]
+impl Shape_Impl for Impl<Tile> {
+    fn area(&self) -> i64 {
+        outer_side(self) * outer_side(self)
+    }
+
+    fn scale(&mut self, factor: i64) -> i64 {
+        let square = self.upcast_mut::<Square>();
+        square.side *= factor;
+        square.side
+    }
+}
+
+#[class_impl]
+impl Square_Impl for Impl<Tile> {
+    fn perimeter(&self) -> i64 {
+        4 * outer_side(self)
+    }
+}
+
+impl Tile {
+    pub fn new(id: i64, side: i64, data: Tile) -> Cls<Tile> {
+        let square = SquareLayout(ShapeLayout((), Shape { id }), Square { side });
+        Cls::new(TileLayout(square, data))
+    }
+}
+
+/// Gadget: Widget, implemented in Rust.
+#[repr(C)]
+pub struct Gadget {
+    pub parts: i64,
+}
+
+#[class]
+pub trait Gadget: Widget_Meta {}
+
+#[class_impl]
+impl Widget_Impl for Impl<Gadget> {
+    fn kind(&self) -> i64 {
+        2
+    }
+
+    fn weight(&self) -> i64 {
+        self.upcast::<Widget>().weight + self.parts
+    }
+}
+
+impl Gadget {
+    pub fn new(weight: i64, data: Gadget) -> Cls<Gadget> {
+        Cls::new(GadgetLayout(WidgetLayout((), Widget { weight }), data))
+    }
+}
@@ -0,0 +1,70 @@
+//! Class declarations for the C++ classes in `fixtures/constructors.cpp`, and a Rust class
+//! deriving from one whose base is initialized by the C++ constructor.
+
+use std::ptr::NonNull;
+
+use bridgeless::{class, class_impl, CRef, Cls, Impl, Init};
+
+/// Mirrors the private fields of the C++ class, which are set by its constructor.
+#[repr(C)]
+pub struct Engine {
+    rpm: i64,
+    serial: i64,
+}
+
+#[class]
+pub trait Engine {
+    #[constructor]
+    #[address(|| NonNull::new(engine_ctor as *mut ()))]
+    fn construct(rpm: i64);
+
+    fn power(&self) -> i64;
+}
+
+impl Engine {
+    pub fn rpm(&self) -> i64 {
+        self.rpm
+    }
+
+    pub fn serial(&self) -> i64 {
+        self.serial
+    }
+}
+
+extern "C" {
+    pub fn engine_new(rpm: i64) -> *mut Cls<Engine>;
+    pub fn engine_free(engine: *mut Cls<Engine>);
+    pub fn engine_power(engine: CRef<'_, Engine>) -> i64;
+    pub fn engine_serial(engine: CRef<'_, Engine>) -> i64;
+
+    // Only used for its address
+    #[link_name = "_ZN6EngineC2El"]
+    fn engine_ctor();
+}
+
+#[repr(C)]
+pub struct Turbo {
+    pub boost: i64,
+    /// Serial number given to the engine by the C++ constructor, read during initialization
+    pub base_serial: i64,
+}
+
+#[class]
+pub trait Turbo: Engine_Meta {}
+
+#[class_impl]
+impl Engine_Impl for Impl<Turbo> {
+    fn power(&self) -> i64 {
+        self.upcast::<Engine>().rpm * 2 + self.boost
+    }
+}
+
+impl Turbo {
+    /// Initializer of a turbocharged engine, constructing its base with the C++ constructor.
+    pub fn new(rpm: i64, boost: i64) -> impl Init<Turbo> {
+        Turbo::pin_init(Engine::construct(rpm), move |mut this| Turbo {
+            boost,
+            base_serial: this.base::<Engine>().serial,
+        })
+    }
+}
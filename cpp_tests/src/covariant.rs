@@ -0,0 +1,77 @@
+//! Class declarations for the C++ classes in `fixtures/covariant.cpp`, along with a Rust class
+//! providing a covariant override.
+
+use bridgeless::{class, class_impl, CRef, Cls, Impl};
+
+#[repr(C)]
+pub struct Animal {
+    pub legs: i64,
+}
+
+#[class]
+pub trait Animal {
+    fn clone(&self) -> *mut Cls<Animal>;
+    fn sound(&self) -> i64;
+}
+
+#[repr(C)]
+pub struct Dog {
+    pub tricks: i64,
+}
+
+#[class]
+pub trait Dog: Animal_Meta {
+    fn perform(&self) -> i64;
+}
+
+// Required to derive from `Dog` in Rust
+#[class_impl]
+impl Animal_Impl for Impl<Dog> {}
+
+extern "C" {
+    pub fn animal_new(legs: i64) -> *mut Cls<Animal>;
+    pub fn animal_free(animal: *mut Cls<Animal>);
+    pub fn dog_new(tricks: i64) -> *mut Cls<Dog>;
+    pub fn dog_free(dog: *mut Cls<Dog>);
+
+    pub fn animal_clone(animal: CRef<'_, Animal>) -> *mut Cls<Animal>;
+    pub fn dog_clone(dog: CRef<'_, Dog>) -> *mut Cls<Dog>;
+    pub fn dog_perform(dog: CRef<'_, Dog>) -> i64;
+    pub fn animal_sound(animal: CRef<'_, Animal>) -> i64;
+}
+
+/// Puppy: Dog, implemented in Rust. Clones are allocated with [`Box`].
+#[repr(C)]
+pub struct Puppy {
+    pub age: i64,
+}
+
+#[class]
+pub trait Puppy: Dog_Meta {}
+
+#[class_impl]
+impl Animal_Impl for Impl<Puppy> {
+    #[covariant]
+    fn clone(&self) -> *mut Cls<Puppy> {
+        let dog = self.upcast::<Dog>();
+        Box::into_raw(Box::new(Puppy::new(dog.tricks, self.age)))
+    }
+
+    fn sound(&self) -> i64 {
+        2
+    }
+}
+
+#[class_impl]
+impl Dog_Impl for Impl<Puppy> {
+    fn perform(&self) -> i64 {
+        self.upcast::<Dog>().tricks * self.age
+    }
+}
+
+impl Puppy {
+    pub fn new(tricks: i64, age: i64) -> Cls<Puppy> {
+        let dog = DogLayout(AnimalLayout((), Animal { legs: 4 }), Dog { tricks });
+        Cls::new(PuppyLayout(dog, Puppy { age }))
+    }
+}
@@ -0,0 +1,78 @@
+//! Class declarations for the C++ classes in `fixtures/destructor.cpp`, which have virtual
+//! destructors.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use bridgeless::{class, class_impl, CRef, Cls, Impl};
+
+#[repr(C)]
+pub struct Resource {
+    pub id: i64,
+}
+
+#[class]
+pub trait Resource {
+    #[destructor]
+    fn destructor(&mut self);
+    fn size(&self) -> i64;
+}
+
+#[repr(C)]
+pub struct Texture {
+    pub width: i64,
+    pub height: i64,
+}
+
+#[class]
+pub trait Texture: Resource_Meta {
+    fn is_square(&self) -> bool;
+}
+
+// Required to derive from `Texture` in Rust
+#[class_impl]
+impl Resource_Impl for Impl<Texture> {}
+
+/// Increments a counter when dropped.
+pub struct DropCounter(pub &'static AtomicU32);
+
+impl Drop for DropCounter {
+    fn drop(&mut self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[repr(C)]
+pub struct Sprite {
+    pub frames: i64,
+    pub drops: DropCounter,
+}
+
+#[class]
+pub trait Sprite: Texture_Meta {}
+
+#[class_impl]
+impl Resource_Impl for Impl<Sprite> {
+    fn size(&self) -> i64 {
+        let texture = self.upcast::<Texture>();
+        texture.width * texture.height * self.frames
+    }
+}
+
+#[class_impl]
+impl Texture_Impl for Impl<Sprite> {
+    fn is_square(&self) -> bool {
+        let texture = self.upcast::<Texture>();
+        texture.width == texture.height
+    }
+}
+
+extern "C" {
+    pub static resource_destructions: i64;
+    pub static texture_destructions: i64;
+
+    pub fn texture_new(id: i64, width: i64, height: i64) -> *mut Cls<Texture>;
+    pub fn resource_delete(resource: *mut Cls<Resource>);
+    pub fn resource_destroy(resource: *mut Cls<Resource>);
+    pub fn resource_size(resource: CRef<'_, Resource>) -> i64;
+    pub fn raw_delete(ptr: *mut u8);
+}
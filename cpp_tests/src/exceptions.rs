@@ -0,0 +1,20 @@
+//! Class declarations for the C++ classes in `fixtures/exceptions.cpp`, whose methods throw C++
+//! exceptions.
+
+use bridgeless::{class, Cls};
+
+#[repr(C)]
+pub struct Calculator {
+    pub computed: i64,
+}
+
+// The `C-unwind` ABI lets the exceptions unwind into Rust
+#[class(panic = "unwind")]
+pub trait Calculator {
+    fn divide(&mut self, dividend: i64, divisor: i64) -> i64;
+}
+
+extern "C" {
+    pub fn calculator_new() -> *mut Cls<Calculator>;
+    pub fn calculator_free(calculator: *mut Cls<Calculator>);
+}
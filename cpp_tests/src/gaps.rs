@@ -0,0 +1,38 @@
+//! Class declarations for the C++ classes in `fixtures/gaps.cpp`, which only declare some of
+//! their virtual methods.
+
+use bridgeless::{class, Cls};
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct Widget {
+    pub width: i64,
+    pub height: i64,
+}
+
+#[class]
+pub trait Widget {
+    fn id(&self) -> i64;
+
+    // Skips `secret` and `version`
+    #[offset(bytes = 0x18, gap = internal)]
+    fn area(&self) -> i64;
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct Panel {
+    pub depth: i64,
+}
+
+#[class]
+pub trait Panel: Widget_Meta {
+    // Offsets in bytes include the slots of the base class, skipping `layer`
+    #[offset(bytes = 0x28)]
+    fn depth(&self) -> i64;
+}
+
+extern "C" {
+    pub fn panel_new(width: i64, height: i64, depth: i64) -> *mut Cls<Panel>;
+    pub fn panel_free(panel: *mut Cls<Panel>);
+}
@@ -0,0 +1,23 @@
+//! Rust declarations of the C++ fixtures compiled by the build script.
+//!
+//! Each module mirrors the fixture file of the same name in `fixtures/` (or `fixtures/shared/`).
+//! The fixtures are only built on Linux, see `build.rs`.
+
+#![cfg(bridgeless_fixtures)]
+
+pub mod classes;
+pub mod constructors;
+pub mod covariant;
+pub mod destructor;
+pub mod exceptions;
+pub mod gaps;
+pub mod listeners;
+pub mod logger;
+pub mod nonvirtual;
+pub mod overloads;
+pub mod panics;
+pub mod rtti;
+pub mod runtime_overrides;
+pub mod sret;
+pub mod stl;
+pub mod templates;
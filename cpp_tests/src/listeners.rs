@@ -0,0 +1,62 @@
+//! Class declarations for the C++ classes in `fixtures/listeners.cpp`, and a Rust listener which
+//! registers itself with the C++ subsystem during construction.
+
+use std::ptr::NonNull;
+
+use bridgeless::{class, class_impl, Cls, Impl, Init};
+
+#[repr(C)]
+pub struct Listener {
+    pub channel: i64,
+}
+
+#[class]
+pub trait Listener {
+    fn notify(&mut self, event: i64) -> i64;
+}
+
+extern "C" {
+    pub fn listener_register(listener: *mut Cls<Listener>);
+    pub fn listener_unregister(listener: *mut Cls<Listener>);
+    pub fn listeners_broadcast(channel: i64, event: i64) -> i64;
+}
+
+/// Unregisters the listener when dropped.
+pub struct Registration(NonNull<Cls<Listener>>);
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        unsafe { listener_unregister(self.0.as_ptr()) };
+    }
+}
+
+#[repr(C)]
+pub struct Accumulator {
+    pub total: i64,
+    pub registration: Registration,
+}
+
+#[class]
+pub trait Accumulator: Listener_Meta {}
+
+#[class_impl]
+impl Listener_Impl for Impl<Accumulator> {
+    fn notify(&mut self, event: i64) -> i64 {
+        self.total += event;
+        self.total
+    }
+}
+
+impl Accumulator {
+    /// Initializer of an accumulator listening to `channel`, registered with the C++ subsystem.
+    pub fn new(channel: i64) -> impl Init<Accumulator> {
+        Accumulator::pin_init(Listener::pin_init(move |_| Listener { channel }), |this| {
+            let listener = this.this().cast();
+            unsafe { listener_register(listener.as_ptr()) };
+            Accumulator {
+                total: 0,
+                registration: Registration(listener),
+            }
+        })
+    }
+}
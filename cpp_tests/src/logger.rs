@@ -0,0 +1,54 @@
+//! Class declarations for the C++ classes in `fixtures/logger.cpp`, which have variadic methods,
+//! and a Rust logger deriving from them.
+
+use std::{ffi::c_char, ptr::NonNull};
+
+use bridgeless::{class, class_impl, Cls, Impl};
+
+#[repr(C)]
+pub struct Logger {
+    pub count: i64,
+    pub last: [u8; 128],
+}
+
+#[class]
+pub trait Logger {
+    fn log(&mut self, fmt: *const c_char, _: ...) -> i32;
+
+    fn level(&self) -> i64;
+
+    #[address(|| NonNull::new(logger_append as *mut ()))]
+    fn append(&mut self, fmt: *const c_char, _: ...) -> i32;
+}
+
+impl Logger {
+    /// Returns the last logged message.
+    pub fn last(&self) -> &str {
+        let len = self.last.iter().position(|&c| c == 0).unwrap_or(self.last.len());
+        std::str::from_utf8(&self.last[..len]).unwrap()
+    }
+}
+
+/// Rust logger overriding the level, which cannot implement the variadic `log`.
+#[repr(C)]
+pub struct Verbose;
+
+#[class]
+pub trait Verbose: Logger_Meta {}
+
+#[class_impl]
+impl Logger_Impl for Impl<Verbose> {
+    fn level(&self) -> i64 {
+        3
+    }
+}
+
+extern "C" {
+    pub fn logger_level(logger: *const Cls<Logger>) -> i64;
+    pub fn logger_new() -> *mut Cls<Logger>;
+    pub fn logger_free(logger: *mut Cls<Logger>);
+
+    // Only used for its address
+    #[link_name = "_ZN6Logger6appendEPKcz"]
+    fn logger_append();
+}
@@ -0,0 +1,82 @@
+//! Class declarations for the C++ classes in `fixtures/nonvirtual.cpp` and
+//! `fixtures/shared/nonvirtual.cpp`, whose non-virtual methods are bound by RVA, address and
+//! symbol name.
+
+use std::ptr::NonNull;
+
+use bridgeless::{class, class_impl, module::Module, Cls, Impl};
+
+#[repr(C)]
+pub struct Gauge {
+    pub level: i64,
+}
+
+#[class]
+pub trait Gauge {
+    fn capacity(&self) -> i64;
+
+    #[rva(gauge_remaining as *const () as usize - Module::main().base() as usize)]
+    fn remaining(&self) -> i64;
+
+    #[address(|| NonNull::new(gauge_fill as *mut ()))]
+    fn fill(&mut self, amount: i64);
+}
+
+#[repr(C)]
+#[derive(Debug)]
+pub struct Counter {
+    pub count: i64,
+}
+
+#[class]
+pub trait Counter {
+    fn step(&self) -> i64;
+
+    #[symbol("_ZNK7Counter3getEv")]
+    fn get(&self) -> i64;
+
+    #[symbol("_ZN7Counter7advanceEl")]
+    fn advance(&mut self, times: i64);
+}
+
+extern "C" {
+    pub fn gauge_new(level: i64) -> *mut Cls<Gauge>;
+    pub fn gauge_free(gauge: *mut Cls<Gauge>);
+    pub fn counter_new(count: i64) -> *mut Cls<Counter>;
+    pub fn counter_free(counter: *mut Cls<Counter>);
+
+    /// Implementation of `Gauge::capacity`, the first slot of the vtable of `Gauge`
+    #[link_name = "_ZNK5Gauge8capacityEv"]
+    pub fn gauge_capacity();
+
+    // Only used for their addresses
+    #[link_name = "_ZNK5Gauge9remainingEv"]
+    fn gauge_remaining();
+    #[link_name = "_ZN5Gauge4fillEl"]
+    fn gauge_fill();
+}
+
+/// Ticker: Counter, implemented in Rust. Its `step` is called by the C++ `advance`.
+#[repr(C)]
+pub struct Ticker {
+    pub step: i64,
+}
+
+#[class]
+pub trait Ticker: Counter_Meta {}
+
+#[class_impl]
+impl Counter_Impl for Impl<Ticker> {
+    fn step(&self) -> i64 {
+        self.step
+    }
+}
+
+impl Ticker {
+    pub fn new(count: i64, step: i64) -> Cls<Ticker> {
+        Cls::new(TickerLayout(
+            CounterLayout((), Counter { count }),
+            Ticker { step },
+        ))
+    }
+}
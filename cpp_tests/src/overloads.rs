@@ -0,0 +1,52 @@
+//! Class declarations for the C++ classes in `fixtures/overloads.cpp`, which have overloaded
+//! virtual methods.
+
+use bridgeless::{class, class_impl, CRef, Cls, Impl};
+
+#[repr(C)]
+pub struct Brush {
+    pub width: i64,
+}
+
+#[class]
+pub trait Brush {
+    #[cpp_name = "paint"]
+    fn paint_int(&self, length: i64) -> i64;
+
+    fn erase(&self) -> i64;
+
+    #[cpp_name = "paint"]
+    fn paint_double(&self, length: f64) -> i64;
+}
+
+extern "C" {
+    pub fn brush_new(width: i64) -> *mut Cls<Brush>;
+    pub fn brush_free(brush: *mut Cls<Brush>);
+    pub fn brush_paint_int(brush: CRef<'_, Brush>, length: i64) -> i64;
+    pub fn brush_paint_double(brush: CRef<'_, Brush>, length: f64) -> i64;
+    pub fn brush_erase(brush: CRef<'_, Brush>) -> i64;
+}
+
+/// Pen: Brush, implemented in Rust, overriding both `paint` overloads.
+#[repr(C)]
+pub struct Pen {
+    pub ink: i64,
+}
+
+#[class]
+pub trait Pen: Brush_Meta {}
+
+#[class_impl]
+impl Brush_Impl for Impl<Pen> {
+    fn paint_int(&self, length: i64) -> i64 {
+        self.ink * length
+    }
+
+    fn erase(&self) -> i64 {
+        0
+    }
+
+    fn paint_double(&self, length: f64) -> i64 {
+        (self.ink as f64 * length) as i64
+    }
+}
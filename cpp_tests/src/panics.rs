@@ -0,0 +1,69 @@
+//! Class declarations for the C++ classes in `fixtures/panics.cpp`, with Rust implementations
+//! which panic under different panic policies.
+
+use bridgeless::{class, class_impl, CRef, CRefMut, Impl};
+
+#[repr(C)]
+pub struct Handler {
+    pub handled: i64,
+}
+
+#[class(panic = "unwind")]
+pub trait Handler {
+    fn handle(&mut self, event: i64) -> i64;
+}
+
+#[repr(C)]
+pub struct Validator {
+    pub checks: i64,
+}
+
+#[class(panic = "catch")]
+pub trait Validator {
+    fn validate(&self, value: i64) -> bool;
+    fn limit(&self) -> i64;
+}
+
+extern "C" {
+    pub static handler_cleanups: i64;
+
+    pub fn handler_dispatch(handler: CRefMut<'_, Handler>, event: i64) -> i64;
+    pub fn validator_count_valid(validator: CRefMut<'_, Validator>, count: i64) -> i64;
+    pub fn validator_limit(validator: CRef<'_, Validator>) -> i64;
+}
+
+/// Handler which panics on negative events, unwinding through the C++ caller.
+#[repr(C)]
+pub struct Doubler;
+
+#[class]
+pub trait Doubler: Handler_Meta {}
+
+#[class_impl]
+impl Handler_Impl for Impl<Doubler> {
+    fn handle(&mut self, event: i64) -> i64 {
+        assert!(event >= 0, "negative event");
+        event * 2
+    }
+}
+
+/// Validator which panics on values above its limit, which are then rejected.
+#[repr(C)]
+pub struct Bounded {
+    pub max: i64,
+}
+
+#[class]
+pub trait Bounded: Validator_Meta {}
+
+#[class_impl]
+impl Validator_Impl for Impl<Bounded> {
+    fn validate(&self, value: i64) -> bool {
+        assert!(value <= self.max, "value out of bounds");
+        value % 2 == 0
+    }
+
+    fn limit(&self) -> i64 {
+        panic!("no limit")
+    }
+}
@@ -0,0 +1,36 @@
+//! Class declarations for the C++ classes in `fixtures/rtti.cpp`, whose vtables are looked up by
+//! RTTI name.
+
+use bridgeless::{class, class_impl, Cls, Impl};
+
+#[repr(C)]
+pub struct Player {
+    pub health: i64,
+}
+
+#[class]
+pub trait Player {
+    fn max_health(&self) -> i64;
+    fn damage(&mut self, amount: i64);
+}
+
+#[repr(C)]
+pub struct Boss {
+    pub phase: i64,
+}
+
+#[class]
+pub trait Boss: Player_Meta {
+    fn enrage(&mut self) -> i64;
+}
+
+// Required to derive from `Boss` in Rust
+#[class_impl]
+impl Player_Impl for Impl<Boss> {}
+
+extern "C" {
+    pub fn player_new(health: i64) -> *mut Cls<Player>;
+    pub fn player_free(player: *mut Cls<Player>);
+    pub fn boss_new(health: i64) -> *mut Cls<Boss>;
+    pub fn boss_free(boss: *mut Cls<Boss>);
+}
@@ -0,0 +1,138 @@
+//! Class declarations for the C++ classes in `fixtures/runtime_overrides.cpp`, and Rust classes
+//! overriding their methods at runtime.
+
+use std::sync::atomic::{AtomicU32, Ordering};
+
+use bridgeless::{class, class_impl, CRef, CRefMut, Class, Cls, Impl};
+
+#[repr(C)]
+pub struct Enemy {
+    pub health: i64,
+    pub strength: i64,
+}
+
+#[class]
+pub trait Enemy {
+    #[destructor]
+    fn destructor(&mut self);
+    fn attack(&self) -> i64;
+    fn take_damage(&mut self, damage: i64) -> i64;
+}
+
+extern "C" {
+    pub static enemies_destroyed: i64;
+    pub static mut enemy_spawn_hook: Option<unsafe extern "C" fn(enemy: *mut u8)>;
+
+    pub fn enemy_spawn(health: i64, strength: i64) -> *mut Cls<Enemy>;
+    pub fn enemy_free(enemy: *mut Cls<Enemy>);
+    pub fn enemy_attack(enemy: CRef<'_, Enemy>) -> i64;
+    pub fn enemy_take_damage(enemy: CRefMut<'_, Enemy>, damage: i64) -> i64;
+    pub fn enemy_is_exact(enemy: CRef<'_, Enemy>) -> bool;
+    pub fn enemy_vtable() -> <Enemy as Class>::VmtPtr;
+    // Return the `Enemy` base, as the layouts of classes without data are not FFI-safe
+    pub fn boss_spawn(health: i64, strength: i64) -> *mut Cls<Enemy>;
+    pub fn minion_spawn(health: i64, strength: i64) -> *mut Cls<Enemy>;
+}
+
+#[repr(C)]
+pub struct Boss;
+
+#[class]
+pub trait Boss: Enemy_Meta {}
+
+#[class_impl]
+impl Enemy_Impl for Impl<Boss> {}
+
+#[repr(C)]
+pub struct Minion;
+
+#[class]
+pub trait Minion: Enemy_Meta {}
+
+#[class_impl]
+impl Enemy_Impl for Impl<Minion> {}
+
+/// Enemy attacking twice as hard.
+#[repr(C)]
+pub struct Berserker;
+
+#[class]
+pub trait Berserker: Enemy_Meta {}
+
+#[class_impl]
+impl Enemy_Impl for Impl<Berserker> {
+    fn attack(&self) -> i64 {
+        self.upcast::<Enemy>().strength * 2
+    }
+}
+
+pub static BRUTES_DROPPED: AtomicU32 = AtomicU32::new(0);
+
+/// Boss attacking three times as hard, registered with `register` before `register_derived`.
+#[repr(C)]
+pub struct Brute;
+
+#[class]
+pub trait Brute: Boss_Meta {}
+
+#[class_impl]
+impl Boss_Impl for Impl<Brute> {}
+
+#[class_impl]
+impl Enemy_Impl for Impl<Brute> {
+    fn attack(&self) -> i64 {
+        self.upcast::<Enemy>().strength * 3
+    }
+}
+
+impl Drop for Brute {
+    fn drop(&mut self) {
+        BRUTES_DROPPED.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Minion attacking four times as hard, registered with `register_derived` before `register`.
+#[repr(C)]
+pub struct Guard;
+
+#[class]
+pub trait Guard: Minion_Meta {}
+
+#[class_impl]
+impl Minion_Impl for Impl<Guard> {}
+
+#[class_impl]
+impl Enemy_Impl for Impl<Guard> {
+    fn attack(&self) -> i64 {
+        self.upcast::<Enemy>().strength * 4
+    }
+}
+
+pub static CHAMPIONS_DROPPED: AtomicU32 = AtomicU32::new(0);
+
+/// Enemy created from Rust, deriving from the C++ class with the vtable returned by
+/// `enemy_vtable`.
+#[repr(C)]
+pub struct Champion {
+    pub bonus: i64,
+}
+
+#[class]
+pub trait Champion: Enemy_Meta {
+    fn taunt(&self) -> i64 {
+        self.bonus * 10
+    }
+}
+
+#[class_impl]
+impl Enemy_Impl for Impl<Champion> {
+    fn attack(&self) -> i64 {
+        self.upcast::<Enemy>().strength + self.bonus
+    }
+}
+
+impl Drop for Champion {
+    fn drop(&mut self) {
+        CHAMPIONS_DROPPED.fetch_add(1, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,83 @@
+//! Class declarations for the C++ classes in `fixtures/sret.cpp`, whose virtual methods return
+//! classes by value.
+
+use bridgeless::{class, class_impl, CRef, Cls, Impl};
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vector3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Extent {
+    pub width: i32,
+    pub height: i32,
+}
+
+/// Not trivially copyable in C++.
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq)]
+pub struct Handle {
+    pub id: i64,
+}
+
+#[repr(C)]
+pub struct Transform {
+    pub scale: i64,
+}
+
+#[class]
+pub trait Transform {
+    #[sret]
+    fn position(&self) -> Vector3;
+
+    #[sret]
+    fn extent(&self) -> Extent;
+
+    #[sret(non_trivial)]
+    fn handle(&self, offset: i64) -> Handle;
+}
+
+extern "C" {
+    pub fn transform_new(scale: i64) -> *mut Cls<Transform>;
+    pub fn transform_free(transform: *mut Cls<Transform>);
+    pub fn transform_position_sum(transform: CRef<'_, Transform>) -> f64;
+    pub fn transform_area(transform: CRef<'_, Transform>) -> i64;
+    pub fn transform_handle_id(transform: CRef<'_, Transform>, offset: i64) -> i64;
+}
+
+/// Offset: Transform, implemented in Rust.
+#[repr(C)]
+pub struct Offset {
+    pub origin: Vector3,
+}
+
+#[class]
+pub trait Offset: Transform_Meta {}
+
+#[class_impl]
+impl Transform_Impl for Impl<Offset> {
+    fn position(&self) -> Vector3 {
+        let scale = self.upcast::<Transform>().scale as f64;
+        Vector3 {
+            x: self.origin.x * scale,
+            y: self.origin.y * scale,
+            z: self.origin.z * scale,
+        }
+    }
+
+    fn extent(&self) -> Extent {
+        Extent {
+            width: 7,
+            height: self.upcast::<Transform>().scale as i32,
+        }
+    }
+
+    fn handle(&self, offset: i64) -> Handle {
+        Handle { id: -offset }
+    }
+}
@@ -0,0 +1,92 @@
+use std::{any::TypeId, ffi::c_char};
+
+use bridgeless::{stl::*, Class, ClassLayout, Cls, Destructor};
+
+#[repr(C)]
+pub struct StlFields {
+    pub name: CxxString<LibStdCxx>,
+    pub wide_name: CxxU16String<LibStdCxx>,
+    pub values: CxxVector<i32>,
+    pub boxed: CxxUniquePtr<i32>,
+    pub shared: CxxSharedPtr<i32>,
+    pub shared_copy: CxxSharedPtr<i32>,
+    pub empty_box: CxxUniquePtr<i32>,
+    pub empty_values: CxxVector<i64>,
+}
+
+extern "C" {
+    pub fn stl_fields_new(name: *const c_char, value_count: usize) -> *mut StlFields;
+    pub fn stl_fields_free(fields: *mut StlFields);
+    pub fn stl_fields_name(fields: *const StlFields) -> *const c_char;
+    pub fn stl_fields_name_len(fields: *const StlFields) -> usize;
+    pub fn stl_fields_values_sum(fields: *const StlFields) -> i64;
+    pub fn stl_fields_boxed(fields: *const StlFields) -> i32;
+    pub fn stl_fields_shared_use_count(fields: *const StlFields) -> usize;
+}
+
+/// Hand-written class declaration for `Entity`, a class without a vtable.
+#[repr(C)]
+pub struct Entity {
+    pub id: i32,
+}
+
+#[repr(C)]
+pub struct EntityLayout<VPtr: 'static + Copy>(pub VPtr, pub Entity);
+
+impl<VPtr: 'static + Copy> ClassLayout<VPtr> for EntityLayout<VPtr> {
+    type Data = Entity;
+
+    fn data(&self) -> &Self::Data {
+        &self.1
+    }
+    fn data_mut(&mut self) -> &mut Self::Data {
+        &mut self.1
+    }
+    fn vtable(&self) -> VPtr {
+        self.0
+    }
+    unsafe fn vtable_mut(&mut self) -> &mut VPtr {
+        &mut self.0
+    }
+}
+
+pub trait EntityInheritTrait {}
+
+unsafe impl Class for Entity {
+    type _InheritTrait = dyn EntityInheritTrait;
+
+    type VmtPart = ();
+    type Vmt = ();
+    type VmtPtr = ();
+    type Layout<VPtr: 'static + Copy> = EntityLayout<VPtr>;
+
+    #[inline(always)]
+    fn base_offset<C: Class>() -> Option<usize> {
+        (TypeId::of::<C>() == TypeId::of::<Entity>()).then_some(0)
+    }
+
+    #[inline(always)]
+    fn destructor(_: ()) -> Option<Destructor> {
+        None
+    }
+}
+
+#[repr(C)]
+pub struct Registry {
+    pub list: CxxList<i32, LibStdCxx>,
+    pub set: CxxSet<i32, LibStdCxx>,
+    pub multiset: CxxMultiSet<i32, LibStdCxx>,
+    pub entities: CxxMap<i32, *mut Cls<Entity>, LibStdCxx>,
+    pub multimap: CxxMultiMap<i32, i64, LibStdCxx>,
+    pub entities_by_hash: CxxUnorderedMap<i32, *mut Cls<Entity>, LibStdCxx>,
+    pub names: CxxUnorderedSet<CxxString<LibStdCxx>, LibStdCxx>,
+    pub entity_list: CxxVector<*mut Cls<Entity>>,
+    pub empty_list: CxxList<CxxString<LibStdCxx>, LibStdCxx>,
+    pub empty_map: CxxMap<i32, i32, LibStdCxx>,
+    pub empty_unordered_map: CxxUnorderedMap<i32, i32, LibStdCxx>,
+}
+
+extern "C" {
+    pub fn stl_registry_new(count: i32) -> *mut Registry;
+    pub fn stl_registry_free(registry: *mut Registry);
+}
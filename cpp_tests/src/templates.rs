@@ -0,0 +1,87 @@
+//! Class declarations for the class template in `fixtures/templates.cpp`, along with a generic
+//! Rust class deriving from it.
+
+use std::ops::AddAssign;
+
+use bridgeless::{class, class_impl, CRef, CRefMut, Cls, Impl};
+
+/// Mirror of the C++ `Array<T>` class template, including its method implementations.
+#[repr(C)]
+pub struct Array<T> {
+    pub len: i64,
+    pub items: [T; 4],
+}
+
+#[class]
+pub trait Array<T: Copy + Default + AddAssign> {
+    fn get(&self, index: i64) -> T {
+        self.items[index as usize]
+    }
+
+    fn set(&mut self, index: i64, value: T) {
+        self.items[index as usize] = value;
+    }
+
+    fn sum(&self) -> T {
+        let mut total = T::default();
+        for i in 0..self.len {
+            total += self.as_dyn().get(i);
+        }
+        total
+    }
+}
+
+impl<T: Copy + Default + AddAssign + 'static> Array<T> {
+    pub fn new(len: i64, fill: T) -> Cls<Array<T>> {
+        Cls::new(ArrayLayout(
+            (),
+            Array {
+                len,
+                items: [fill; 4],
+            },
+        ))
+    }
+}
+
+extern "C" {
+    pub fn array_i32_new(len: i64, fill: i32) -> *mut Cls<Array<i32>>;
+    pub fn array_i32_free(array: *mut Cls<Array<i32>>);
+    pub fn array_i32_get(array: CRef<'_, Array<i32>>, index: i64) -> i32;
+    pub fn array_i32_set(array: CRefMut<'_, Array<i32>>, index: i64, value: i32);
+    pub fn array_i32_sum(array: CRef<'_, Array<i32>>) -> i32;
+
+    pub fn array_f64_new(len: i64, fill: f64) -> *mut Cls<Array<f64>>;
+    pub fn array_f64_free(array: *mut Cls<Array<f64>>);
+    pub fn array_f64_get(array: CRef<'_, Array<f64>>, index: i64) -> f64;
+    pub fn array_f64_set(array: CRefMut<'_, Array<f64>>, index: i64, value: f64);
+    pub fn array_f64_sum(array: CRef<'_, Array<f64>>) -> f64;
+}
+
+/// Array whose items are read clamped to a maximum, implemented in Rust.
+#[repr(C)]
+pub struct Clamped<T> {
+    pub max: T,
+}
+
+#[class]
+pub trait Clamped<T: Copy + Default + AddAssign + PartialOrd>: Array_Meta<T> {}
+
+#[class_impl]
+impl<T: Copy + Default + AddAssign + PartialOrd + 'static> Array_Impl<T> for Impl<Clamped<T>> {
+    fn get(&self, index: i64) -> T {
+        let item = self.upcast::<Array<T>>().items[index as usize];
+        if item > self.max {
+            self.max
+        }
+        else {
+            item
+        }
+    }
+}
+
+impl<T: Copy + Default + AddAssign + PartialOrd + 'static> Clamped<T> {
+    pub fn new(items: [T; 4], max: T) -> Cls<Clamped<T>> {
+        let array = Array { len: 4, items };
+        Cls::new(ClampedLayout(ArrayLayout((), array), Clamped { max }))
+    }
+}
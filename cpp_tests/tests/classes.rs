@@ -0,0 +1,123 @@
+#![cfg(bridgeless_fixtures)]
+
+use std::ops::{Deref, DerefMut};
+
+use bridgeless::{CRef, CRefMut, Class, Cls, DynCls};
+use bridgeless_cpp_tests::classes::*;
+
+/// Owns a class instance allocated by C++, freeing it with the matching fixture function.
+struct CppObject<C: Class>(*mut Cls<C>, unsafe extern "C" fn(*mut Cls<C>));
+
+impl<C: Class> CppObject<C> {
+    fn new(ptr: *mut Cls<C>, free: unsafe extern "C" fn(*mut Cls<C>)) -> Self {
+        assert!(!ptr.is_null());
+        Self(ptr, free)
+    }
+}
+
+impl<C: Class> Deref for CppObject<C> {
+    type Target = DynCls<C>;
+
+    fn deref(&self) -> &Self::Target {
+        unsafe { (*self.0).as_dyn() }
+    }
+}
+
+impl<C: Class> DerefMut for CppObject<C> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        unsafe { (*self.0).as_dyn_mut() }
+    }
+}
+
+impl<C: Class> Drop for CppObject<C> {
+    fn drop(&mut self) {
+        unsafe { (self.1)(self.0) }
+    }
+}
+
+#[test]
+fn call_cpp_virtuals() {
+    let mut shape = CppObject::new(unsafe { shape_new(3) }, shape_free);
+
+    assert_eq!(shape.id, 3);
+    assert_eq!(shape.area(), 0);
+    assert_eq!(shape.scale(4), 12);
+    assert_eq!(shape.id, 12);
+}
+
+#[test]
+fn call_cpp_overrides() {
+    let mut square = CppObject::new(unsafe { square_new(1, 5) }, square_free);
+
+    assert_eq!(square.side, 5);
+    assert_eq!(square.upcast::<Shape>().id, 1);
+    assert_eq!(square.area(), 25);
+    assert_eq!(square.perimeter(), 20);
+
+    // Calls through the base class dispatch to the C++ override
+    let shape: &mut DynCls<Shape> = square.upcast_mut();
+    assert_eq!(shape.area(), 25);
+    assert_eq!(shape.scale(2), 10);
+    assert_eq!(square.side, 10);
+    assert_eq!(square.upcast::<Shape>().id, 1);
+}
+
+#[test]
+fn call_cpp_virtuals_after_gap() {
+    let widget = CppObject::new(unsafe { widget_new(40) }, widget_free);
+
+    assert_eq!(widget.kind(), 1);
+    assert_eq!(widget.weight(), 40);
+}
+
+#[test]
+fn cpp_calls_rust_overrides() {
+    let mut circle = Circle::new(7, Circle { radius: 2 });
+
+    assert_eq!(circle.area(), 12);
+    assert_eq!(unsafe { shape_area(CRef::from(&circle)) }, 12);
+    assert_eq!(unsafe { shape_scale(CRefMut::from(&mut circle), 3) }, 6);
+    assert_eq!(circle.radius, 6);
+    assert_eq!(circle.upcast::<Shape>().id, 7);
+    assert_eq!(unsafe { shape_area(CRef::from(&circle)) }, 108);
+}
+
+#[test]
+fn cpp_calls_rust_overrides_of_derived_class() {
+    let mut tile = Tile::new(2, 4, Tile { border: 1 });
+
+    assert_eq!(tile.area(), 36);
+    assert_eq!(tile.perimeter(), 24);
+    assert_eq!(unsafe { shape_area(CRef::from(&tile)) }, 36);
+    assert_eq!(unsafe { square_perimeter(CRef::from(&tile)) }, 24);
+
+    assert_eq!(unsafe { shape_scale(CRefMut::from(&mut tile), 2) }, 8);
+    assert_eq!(tile.upcast::<Square>().side, 8);
+    assert_eq!(unsafe { square_perimeter(CRef::from(&tile)) }, 40);
+}
+
+#[test]
+fn cpp_calls_rust_overrides_after_gap() {
+    let gadget = Gadget::new(10, Gadget { parts: 5 });
+
+    assert_eq!(gadget.kind(), 2);
+    assert_eq!(gadget.weight(), 15);
+    assert_eq!(unsafe { widget_kind(CRef::from(&gadget)) }, 2);
+    assert_eq!(unsafe { widget_weight(CRef::from(&gadget)) }, 15);
+}
+
+#[test]
+fn cpp_iterates_mixed_objects() {
+    let square = CppObject::new(unsafe { square_new(1, 3) }, square_free);
+    let circle = Circle::new(2, Circle { radius: 1 });
+    let tile = Tile::new(3, 1, Tile { border: 1 });
+
+    let shapes: [CRef<Shape>; 3] = [CRef::from(&*square), CRef::from(&circle), CRef::from(&tile)];
+    let total: i64 = shapes.iter().map(|shape| shape.area()).sum();
+
+    assert_eq!(total, 9 + 3 + 9);
+    assert_eq!(
+        unsafe { shapes_total_area(shapes.as_ptr(), shapes.len()) },
+        total
+    );
+}
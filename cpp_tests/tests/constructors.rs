@@ -0,0 +1,36 @@
+#![cfg(bridgeless_fixtures)]
+
+use std::{mem::MaybeUninit, ptr};
+
+use bridgeless::Cls;
+use bridgeless_cpp_tests::constructors::*;
+
+#[test]
+fn construct_base_with_cpp_constructor() {
+    let engine = unsafe { &*engine_new(1000) };
+    let serial = engine.serial();
+    assert_eq!(unsafe { engine_serial(engine.into()) }, serial);
+    assert_eq!(unsafe { engine_power(engine.into()) }, 2000);
+
+    let mut slot = MaybeUninit::uninit();
+    let turbo: &mut Cls<Turbo> = unsafe { &mut Cls::emplace(&mut slot, Turbo::new(3000, 500)) };
+
+    // Private fields are initialized by the C++ constructor
+    let base = turbo.upcast::<Engine>();
+    assert_eq!(base.rpm(), 3000);
+    assert_eq!(base.serial(), serial + 1);
+    assert_eq!(turbo.base_serial, serial + 1);
+    assert_eq!(unsafe { engine_serial(base.into()) }, serial + 1);
+
+    // The C++ vtable written by the constructor is replaced by the Rust one
+    assert_eq!(
+        unsafe { engine_power(turbo.upcast::<Engine>().into()) },
+        6500
+    );
+    assert_eq!(turbo.power(), 6500);
+
+    unsafe {
+        ptr::drop_in_place(turbo);
+        engine_free(engine as *const _ as *mut _);
+    }
+}
@@ -0,0 +1,46 @@
+#![cfg(bridgeless_fixtures)]
+
+use bridgeless::{CRef, Cls};
+use bridgeless_cpp_tests::covariant::*;
+
+#[test]
+fn call_cpp_covariant_override() {
+    let dog = unsafe { &mut *dog_new(3) };
+    let animal = unsafe { &mut *animal_new(2) };
+
+    // `Dog::clone` is called through the `Animal::clone` slot
+    let clone = dog.upcast::<Animal>().clone() as *mut Cls<Dog>;
+    let clone_ref = unsafe { &*clone };
+    assert_eq!(
+        (clone_ref.upcast::<Animal>().legs, clone_ref.tricks),
+        (4, 3)
+    );
+    assert_eq!(clone_ref.perform(), 3);
+    assert_eq!(unsafe { animal_sound(CRef::from(clone_ref)) }, 1);
+
+    let animal_clone = animal.clone();
+    assert_eq!(unsafe { &*animal_clone }.legs, 2);
+
+    unsafe {
+        dog_free(clone);
+        dog_free(dog);
+        animal_free(animal_clone);
+        animal_free(animal);
+    }
+}
+
+#[test]
+fn cpp_calls_rust_covariant_override() {
+    let puppy = Puppy::new(5, 3);
+
+    // C++ expects the `Animal::clone` slot of a `Dog` to return a `Dog*`
+    let clone = unsafe { dog_clone(CRef::from(&puppy)) };
+    assert_eq!(unsafe { dog_perform(CRef::new(&*clone)) }, 15);
+    let clone = unsafe { Box::from_raw(clone as *mut Cls<Puppy>) };
+    assert_eq!((clone.upcast::<Dog>().tricks, clone.age), (5, 3));
+    assert_eq!(unsafe { animal_sound(CRef::from(&*clone)) }, 2);
+
+    let clone = unsafe { animal_clone(CRef::from(&puppy)) };
+    let clone = unsafe { Box::from_raw(clone as *mut Cls<Puppy>) };
+    assert_eq!(clone.upcast::<Animal>().legs, 4);
+}
@@ -0,0 +1,77 @@
+#![cfg(bridgeless_fixtures)]
+
+use std::{
+    mem::ManuallyDrop,
+    ptr,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use bridgeless::{CRef, Class, ClassLayout, Cls};
+use bridgeless_cpp_tests::destructor::*;
+
+fn destructions() -> (i64, i64) {
+    unsafe { (resource_destructions, texture_destructions) }
+}
+
+#[test]
+fn destroy_cpp_instances() {
+    // Delete through the vtable of the base, like `delete resource` in C++
+    let before = destructions();
+    let texture = unsafe { &mut *texture_new(1, 2, 3) };
+    assert_eq!(texture.size(), 6);
+    let destructor =
+        Resource::destructor(texture.upcast::<Resource>().as_concrete().layout().vtable());
+    unsafe { destructor.unwrap().delete(texture as *mut Cls<Texture> as *mut u8) };
+    assert_eq!(destructions(), (before.0 + 1, before.1 + 1));
+
+    // Dropping a `Cls` calls the C++ destructor
+    let before = destructions();
+    let allocation = unsafe { texture_new(2, 4, 4) };
+    let texture = unsafe { ptr::read(allocation) };
+    unsafe { raw_delete(allocation as *mut u8) };
+    assert_eq!(texture.size(), 16);
+    assert!(texture.is_square());
+    drop(texture);
+    assert_eq!(destructions(), (before.0 + 1, before.1 + 1));
+
+    // Unless it is wrapped in a `ManuallyDrop`
+    let before = destructions();
+    let allocation = unsafe { texture_new(3, 1, 1) };
+    {
+        let _texture = ManuallyDrop::new(unsafe { ptr::read(allocation) });
+    }
+    assert_eq!(destructions(), before);
+    unsafe { resource_delete(allocation as *mut Cls<Resource>) };
+    assert_eq!(destructions(), (before.0 + 1, before.1 + 1));
+}
+
+fn new_sprite(frames: i64, drops: &'static AtomicU32) -> Cls<Sprite> {
+    Cls::new(SpriteLayout(
+        TextureLayout(
+            ResourceLayout((), Resource { id: 4 }),
+            Texture {
+                width: 2,
+                height: 2,
+            },
+        ),
+        Sprite {
+            frames,
+            drops: DropCounter(drops),
+        },
+    ))
+}
+
+#[test]
+fn destroy_rust_subclass() {
+    static DROPS: AtomicU32 = AtomicU32::new(0);
+
+    let sprite = new_sprite(3, &DROPS);
+    assert_eq!(unsafe { resource_size(CRef::from(&sprite)) }, 12);
+    drop(sprite);
+    assert_eq!(DROPS.load(Ordering::Relaxed), 1);
+
+    // C++ calls the destructor of the Rust class through the vtable
+    let mut sprite = ManuallyDrop::new(new_sprite(1, &DROPS));
+    unsafe { resource_destroy(&mut *sprite as *mut Cls<Sprite> as *mut Cls<Resource>) };
+    assert_eq!(DROPS.load(Ordering::Relaxed), 2);
+}
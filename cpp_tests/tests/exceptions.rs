@@ -0,0 +1,77 @@
+#![cfg(bridgeless_fixtures)]
+
+use std::panic::{self, AssertUnwindSafe};
+
+use bridgeless::{
+    exception::{try_call, ForeignException},
+    Cls,
+};
+use bridgeless_cpp_tests::exceptions::*;
+
+#[test]
+fn catch_std_exception() {
+    let calculator = unsafe { &mut *calculator_new() };
+
+    assert_eq!(try_call(|| calculator.divide(42, 2)), Ok(21));
+
+    let err = try_call(|| calculator.divide(42, 0)).unwrap_err();
+    assert_eq!(err.type_name(), "St12domain_error");
+    assert_eq!(err.what(), Some("division by zero"));
+    assert_eq!(
+        err.to_string(),
+        "C++ exception of type `St12domain_error`: division by zero"
+    );
+    assert_eq!(calculator.computed, 1);
+
+    unsafe { calculator_free(calculator) };
+}
+
+#[test]
+fn catch_any_exception() {
+    let calculator = unsafe { &mut *calculator_new() };
+
+    let err = try_call(|| calculator.divide(42, -1)).unwrap_err();
+    assert_eq!(err.type_name(), "l");
+    assert_eq!(err.what(), None);
+
+    unsafe { calculator_free(calculator) };
+}
+
+#[test]
+fn catch_exceptions_while_panicking() {
+    struct DivideOnDrop<'a> {
+        calculator: &'a mut Cls<Calculator>,
+        result: &'a mut Option<Result<i64, ForeignException>>,
+    }
+
+    impl Drop for DivideOnDrop<'_> {
+        fn drop(&mut self) {
+            *self.result = Some(try_call(|| self.calculator.divide(42, 0)));
+        }
+    }
+
+    let calculator = unsafe { &mut *calculator_new() };
+    let mut result = None;
+    let unwound = panic::catch_unwind(AssertUnwindSafe(|| {
+        let _guard = DivideOnDrop {
+            calculator: &mut *calculator,
+            result: &mut result,
+        };
+        panic!("unrelated panic");
+    }));
+    assert!(unwound.is_err());
+
+    let err = result.unwrap().unwrap_err();
+    assert_eq!(err.what(), Some("division by zero"));
+
+    unsafe { calculator_free(calculator) };
+}
+
+#[test]
+fn propagate_rust_panics() {
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        try_call(|| -> i64 { panic!("not a C++ exception") })
+    }));
+    let payload = result.expect_err("try_call should have panicked");
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"not a C++ exception"));
+}
@@ -0,0 +1,129 @@
+#![cfg(bridgeless_fixtures)]
+
+use bridgeless::{CRef, ClassLayout, OpaqueFn, VmtSlots};
+use bridgeless_cpp_tests::gaps::*;
+
+type Getter = unsafe extern "C" fn(this: *const u8) -> i64;
+type SlotGetter = unsafe extern "C" fn(this: *mut u8) -> i64;
+
+#[test]
+fn methods_after_gaps() {
+    let panel = unsafe { &mut *panel_new(3, 4, 5) };
+    assert_eq!(panel.id(), 1);
+    assert_eq!(panel.area(), 12);
+    assert_eq!(panel.depth(), 5);
+    unsafe { panel_free(panel) };
+}
+
+#[test]
+fn read_gap_slots() {
+    let panel = unsafe { &mut *panel_new(3, 4, 5) };
+    let vmt = panel.layout().vtable();
+    let this = panel as *const _ as *const u8;
+
+    let [secret, version] = vmt.0 .0.internal;
+    let call = |slot: OpaqueFn| unsafe { slot.cast::<Getter>().unwrap()(this) };
+    assert_eq!(call(secret), 42);
+    assert_eq!(call(version), 3);
+    assert_eq!(call(vmt.1._gap_0[0]), 7);
+
+    // Gap slots can be forwarded to another vtable
+    let mut copy = *vmt;
+    copy.0 .0.internal = [version, secret];
+    assert_eq!(call(copy.0 .0.internal[0]), 3);
+    assert!(!copy.0 .0.internal[1].as_ptr().is_null());
+
+    unsafe { panel_free(panel) };
+}
+
+#[test]
+fn call_slots_by_index() {
+    let panel = unsafe { &mut *panel_new(3, 4, 5) };
+    let vmt = panel.layout().vtable();
+    assert_eq!(vmt.slot_count(), 6);
+    assert_eq!(vmt.slot(3), vmt.0 .0.area.unwrap() as *const ());
+    assert_eq!(vmt.slot(1), vmt.0 .0.internal[0].as_ptr());
+
+    let panel = panel.as_dyn_mut();
+    unsafe {
+        assert_eq!(panel.call_slot::<SlotGetter, 1>(()), 42);
+        assert_eq!(panel.call_slot::<SlotGetter, 2>(()), 3);
+        assert_eq!(panel.call_slot::<SlotGetter, 4>(()), 7);
+        assert_eq!(panel.call_slot::<SlotGetter, 5>(()), 5);
+        // Slots of the base class through the base's declared vtable
+        assert_eq!(
+            panel.upcast_mut::<Widget>().call_slot::<SlotGetter, 3>(()),
+            12
+        );
+    }
+    unsafe { panel_free(panel.as_concrete_mut()) };
+}
+
+#[test]
+#[should_panic = "slot index 6 out of the bounds of the vtable (6 slots)"]
+fn slot_out_of_bounds() {
+    let panel = unsafe { &mut *panel_new(3, 4, 5) };
+    let vmt = *panel.layout().vtable();
+    unsafe { panel_free(panel) };
+    vmt.slot(6);
+}
+
+#[test]
+fn debug_format() {
+    let panel = unsafe { &mut *panel_new(3, 4, 5) };
+    let vmt = panel.layout().vtable();
+    let debug = format!("{:?}", panel.as_dyn());
+
+    let address = panel as *const _;
+    let header =
+        format!("DynCls {{ class: bridgeless_cpp_tests::gaps::Panel, address: {address:p}");
+    assert!(debug.starts_with(&header), "{debug}");
+    assert!(debug.contains(&format!("vptr: {vmt:p}")), "{debug}");
+    assert!(
+        debug.contains(&format!("id: {:p} in gaps-", vmt.slot(0))),
+        "{debug}"
+    );
+    assert!(
+        debug.contains(&format!("internal: [{:p} in gaps-", vmt.slot(1))),
+        "{debug}"
+    );
+    assert!(
+        debug.contains(&format!("_gap_0: [{:p} in gaps-", vmt.slot(4))),
+        "{debug}"
+    );
+    assert!(debug.ends_with(
+        "layout: PanelLayout { base: WidgetLayout { data: Widget { width: 3, height: 4 } }, \
+         data: Panel { depth: 5 } } }"
+    ));
+
+    unsafe { panel_free(panel) };
+}
+
+unsafe extern "C" fn doubled_area(this: CRef<'_, Widget>) -> i64 {
+    this.width * this.height * 2
+}
+
+#[test]
+fn typed_slots_after_gaps() {
+    assert_eq!(WidgetVmt::ID_SLOT, 0);
+    assert_eq!(WidgetVmt::AREA_SLOT, 3);
+    assert_eq!(PanelVmt::DEPTH_SLOT, 5);
+    assert_eq!(WidgetVmt::AREA_NAME, "area");
+
+    let panel = unsafe { &mut *panel_new(3, 4, 5) };
+    let vmt = panel.layout().vtable();
+    let widget = panel.upcast::<Widget>();
+    let area = vmt.0 .0.typed().area.unwrap();
+    assert_eq!(unsafe { area(widget.into()) }, 12);
+    let depth = vmt.1.typed().depth.unwrap();
+    assert_eq!(unsafe { depth(panel.as_dyn().into()) }, 5);
+
+    // Slots of a copy of the vtable can be replaced by typed functions
+    let mut copy = *vmt;
+    copy.0 .0.typed_mut().area = Some(doubled_area);
+    assert_eq!(copy.slot(WidgetVmt::AREA_SLOT), doubled_area as *const ());
+    let area = copy.0 .0.typed().area.unwrap();
+    assert_eq!(unsafe { area(widget.into()) }, 24);
+
+    unsafe { panel_free(panel) };
+}
@@ -0,0 +1,27 @@
+#![cfg(bridgeless_fixtures)]
+
+use std::{mem::MaybeUninit, ptr};
+
+use bridgeless::Cls;
+use bridgeless_cpp_tests::listeners::*;
+
+#[test]
+fn register_this_during_construction() {
+    let mut first = MaybeUninit::uninit();
+    let mut second = MaybeUninit::uninit();
+    let first: &mut Cls<Accumulator> =
+        unsafe { &mut Cls::emplace(&mut first, Accumulator::new(1)) };
+    let second: &mut Cls<Accumulator> =
+        unsafe { &mut Cls::emplace(&mut second, Accumulator::new(1)) };
+
+    assert_eq!(unsafe { listeners_broadcast(1, 5) }, 10);
+    assert_eq!(unsafe { listeners_broadcast(1, 2) }, 14);
+    assert_eq!(unsafe { listeners_broadcast(2, 2) }, 0);
+    assert_eq!((first.total, second.total), (7, 7));
+
+    first.total = 0;
+    unsafe { ptr::drop_in_place(second) };
+    assert_eq!(unsafe { listeners_broadcast(1, 1) }, 1);
+    unsafe { ptr::drop_in_place(first) };
+    assert_eq!(unsafe { listeners_broadcast(1, 1) }, 0);
+}
@@ -0,0 +1,61 @@
+#![cfg(bridgeless_fixtures)]
+
+use std::panic::{self, AssertUnwindSafe};
+
+use bridgeless::{Cls, Variadic};
+use bridgeless_cpp_tests::logger::*;
+
+#[test]
+fn call_variadic_methods() {
+    let logger = unsafe { &mut *logger_new() };
+
+    let Variadic { this, fun } = logger.log();
+    let len = unsafe {
+        fun(
+            this,
+            c"%s=%d (%.2f)".as_ptr(),
+            c"answer".as_ptr(),
+            42,
+            0.5f64,
+        )
+    };
+    assert_eq!(logger.last(), "answer=42 (0.50)");
+    assert_eq!(len, 16);
+    assert_eq!(logger.count, 1);
+
+    // Non-virtual variadic method
+    let append = logger.append();
+    unsafe { (append.fun)(append.this, c", %lld%c".as_ptr(), -7i64, b'!' as i32) };
+    assert_eq!(logger.last(), "answer=42 (0.50), -7!");
+    assert_eq!(logger.level(), 1);
+
+    unsafe { logger_free(logger) };
+}
+
+#[test]
+fn derive_rust_class_from_variadic_base() {
+    let mut verbose: Cls<Verbose> = Cls::new(VerboseLayout(
+        LoggerLayout(
+            (),
+            Logger {
+                count: 0,
+                last: [0; 128],
+            },
+        ),
+        Verbose,
+    ));
+    assert_eq!(verbose.upcast::<Logger>().level(), 3);
+    assert_eq!(
+        unsafe { logger_level(verbose.upcast::<Logger>().into()) },
+        3
+    );
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        verbose.upcast_mut::<Logger>().log();
+    }));
+    let payload = result.expect_err("log should have panicked");
+    assert_eq!(
+        payload.downcast_ref::<String>().map(String::as_str),
+        Some("Rust classes cannot implement the variadic method log")
+    );
+}
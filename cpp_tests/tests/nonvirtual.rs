@@ -0,0 +1,57 @@
+#![cfg(bridgeless_fixtures)]
+
+use bridgeless::{Cls, DynCls};
+use bridgeless_cpp_tests::nonvirtual::*;
+
+#[test]
+fn call_non_virtual_by_rva_and_address() {
+    let gauge = unsafe { &mut *gauge_new(30) };
+
+    assert_eq!(gauge.remaining(), 70);
+    gauge.fill(50);
+    assert_eq!(gauge.level, 80);
+    gauge.fill(50);
+    assert_eq!(gauge.level, 100);
+    assert_eq!(gauge.remaining(), 0);
+
+    unsafe { gauge_free(gauge) };
+}
+
+#[test]
+fn call_non_virtual_by_symbol() {
+    let counter = unsafe { &mut *counter_new(5) };
+
+    assert_eq!(counter.get(), 5);
+    counter.advance(3);
+    assert_eq!(counter.get(), 8);
+
+    unsafe { counter_free(counter) };
+}
+
+#[test]
+fn non_virtual_calls_rust_overrides() {
+    let mut ticker: Cls<Ticker> = Ticker::new(1, 10);
+
+    ticker.advance(2);
+    assert_eq!(ticker.get(), 21);
+
+    let counter: &mut DynCls<Counter> = ticker.upcast_mut();
+    counter.advance(1);
+    assert_eq!(counter.count, 31);
+}
+
+#[test]
+fn debug_shows_slot_symbols() {
+    let counter = unsafe { &mut *counter_new(5) };
+    let debug = format!("{counter:?}");
+
+    // `Counter::step` is exported by the shared library of the fixtures
+    assert!(
+        debug.contains("in libbridgeless_shared_fixtures.so+0x"),
+        "{debug}"
+    );
+    assert!(debug.contains("(_ZNK7Counter4stepEv+0x0)"), "{debug}");
+    assert!(debug.ends_with("layout: CounterLayout { data: Counter { count: 5 } } }"));
+
+    unsafe { counter_free(counter) };
+}
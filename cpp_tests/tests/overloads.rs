@@ -0,0 +1,25 @@
+#![cfg(bridgeless_fixtures)]
+
+use bridgeless::Cls;
+use bridgeless_cpp_tests::overloads::*;
+
+#[test]
+fn call_cpp_overloads() {
+    let brush = unsafe { &*brush_new(3) };
+    assert_eq!(brush.paint_int(2), 6);
+    assert_eq!(brush.paint_double(0.5), 15);
+    assert_eq!(brush.erase(), -3);
+    unsafe { brush_free(brush as *const _ as *mut _) };
+}
+
+#[test]
+fn override_overloads() {
+    let pen: Cls<Pen> = Cls::new(PenLayout(
+        BrushLayout((), Brush { width: 1 }),
+        Pen { ink: 4 },
+    ));
+    let brush = pen.upcast::<Brush>();
+    assert_eq!(unsafe { brush_paint_int(brush.into(), 3) }, 12);
+    assert_eq!(unsafe { brush_paint_double(brush.into(), 0.5) }, 2);
+    assert_eq!(unsafe { brush_erase(brush.into()) }, 0);
+}
@@ -0,0 +1,41 @@
+#![cfg(bridgeless_fixtures)]
+
+use std::panic::{self, AssertUnwindSafe};
+
+use bridgeless::Cls;
+use bridgeless_cpp_tests::panics::*;
+
+#[test]
+fn unwind_through_cpp_caller() {
+    let mut doubler: Cls<Doubler> = Cls::new(DoublerLayout(
+        HandlerLayout((), Handler { handled: 0 }),
+        Doubler,
+    ));
+    assert_eq!(unsafe { handler_dispatch((&mut doubler).into(), 4) }, 8);
+
+    let cleanups = unsafe { handler_cleanups };
+    let result = panic::catch_unwind(AssertUnwindSafe(|| unsafe {
+        handler_dispatch((&mut doubler).into(), -1)
+    }));
+    let payload = result.expect_err("handler should have panicked");
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"negative event"));
+
+    // C++ destructors ran while unwinding
+    assert_eq!(unsafe { handler_cleanups }, cleanups + 1);
+    assert_eq!(doubler.upcast::<Handler>().handled, 2);
+}
+
+#[test]
+fn catch_and_return_default() {
+    let mut bounded: Cls<Bounded> = Cls::new(BoundedLayout(
+        ValidatorLayout((), Validator { checks: 0 }),
+        Bounded { max: 5 },
+    ));
+    // 0, 2 and 4 are valid, values above 5 panic and are rejected
+    assert_eq!(
+        unsafe { validator_count_valid((&mut bounded).into(), 10) },
+        3
+    );
+    assert_eq!(unsafe { validator_limit((&bounded).into()) }, 0);
+    assert_eq!(bounded.upcast::<Validator>().checks, 10);
+}
@@ -0,0 +1,37 @@
+#![cfg(bridgeless_fixtures)]
+
+use bridgeless::{module::Module, registry, rtti, DynCls};
+use bridgeless_cpp_tests::rtti::*;
+
+#[test]
+fn check_cpp_instances_by_vtable() {
+    let module = Module::main();
+    unsafe {
+        let player_vmt = rtti::find_class_vmt::<Player>(module, "game::Player").unwrap();
+        let boss_vmt = rtti::find_class_vmt::<Boss>(module, "game::Boss").unwrap();
+        registry::register_vtable::<Player>(player_vmt).unwrap();
+        registry::register_vtable::<Boss>(boss_vmt).unwrap();
+    }
+
+    let player = unsafe { &mut *player_new(50) };
+    let boss = unsafe { &mut *boss_new(500) };
+
+    let as_player: &mut DynCls<Player> = boss.upcast_mut();
+    assert!(as_player.is_instance_of::<Boss>());
+    assert!(!as_player.is_instance_of::<Player>());
+    let concrete = unsafe { as_player.try_as_concrete_mut::<Boss>() }.unwrap();
+    assert_eq!(concrete.enrage(), 2);
+    assert_eq!(concrete.phase, 2);
+
+    let as_player = player.as_dyn();
+    assert!(as_player.try_as_concrete::<Boss>().is_none());
+    assert_eq!(
+        as_player.try_as_concrete::<Player>().map(|p| p.health),
+        Some(50)
+    );
+
+    unsafe {
+        player_free(player);
+        boss_free(boss);
+    }
+}
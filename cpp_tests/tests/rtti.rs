@@ -0,0 +1,101 @@
+#![cfg(bridgeless_fixtures)]
+
+use std::ptr::NonNull;
+
+use bridgeless::{
+    module::Module,
+    rtti::{self, RttiError},
+    Class, ClassLayout, Cls,
+};
+use bridgeless_cpp_tests::{
+    classes::*,
+    nonvirtual::{counter_free, counter_new},
+    rtti::*,
+    templates::*,
+};
+
+/// Address of the vtable of a class instance.
+fn vptr<C: Class>(instance: &Cls<C>) -> NonNull<u8> {
+    unsafe { *(instance as *const Cls<C> as *const NonNull<u8>) }
+}
+
+#[test]
+fn find_vtables_by_name() {
+    let module = Module::main();
+    let player = unsafe { &*player_new(50) };
+    let boss = unsafe { &*boss_new(500) };
+    let square = unsafe { &*square_new(1, 2) };
+
+    unsafe {
+        assert_eq!(rtti::find_vtable(module, "game::Player"), Ok(vptr(player)));
+        assert_eq!(rtti::find_vtable(module, "game::Boss"), Ok(vptr(boss)));
+        assert_eq!(rtti::find_vtable(module, "Square"), Ok(vptr(square)));
+        assert_eq!(
+            rtti::find_vtable_mangled(module, b"N4game4BossE"),
+            Some(vptr(boss))
+        );
+
+        assert_eq!(
+            rtti::find_vtable(module, "game::Missing"),
+            Err(RttiError::NotFound)
+        );
+        assert_eq!(
+            rtti::find_vtable(module, "Array<int>"),
+            Err(RttiError::InvalidName)
+        );
+        assert_eq!(
+            rtti::find_vtable(module, "game::"),
+            Err(RttiError::InvalidName)
+        );
+
+        player_free(player as *const _ as *mut _);
+        boss_free(boss as *const _ as *mut _);
+        square_free(square as *const _ as *mut _);
+    }
+}
+
+#[test]
+fn find_template_and_shared_library_vtables() {
+    let array = unsafe { &*array_i32_new(1, 1) };
+    let counter = unsafe { &*counter_new(0) };
+    let shared = Module::containing(counter_new as *const ()).unwrap();
+
+    unsafe {
+        assert_eq!(
+            rtti::find_vtable_mangled(Module::main(), b"5ArrayIiE"),
+            Some(vptr(array))
+        );
+        assert_eq!(rtti::find_vtable(shared, "Counter"), Ok(vptr(counter)));
+        assert_eq!(
+            rtti::find_vtable(Module::main(), "Counter"),
+            Err(RttiError::NotFound)
+        );
+
+        array_i32_free(array as *const _ as *mut _);
+        counter_free(counter as *const _ as *mut _);
+    }
+}
+
+#[test]
+fn find_validated_class_vmts() {
+    let module = Module::main();
+    let boss = unsafe { &mut *boss_new(500) };
+
+    let boss_vmt = unsafe { rtti::find_class_vmt::<Boss>(module, "game::Boss") }.unwrap();
+    assert!(std::ptr::eq(boss_vmt, boss.layout().vtable()));
+    assert_eq!(boss.enrage(), 2);
+
+    let player_vmt = unsafe { rtti::find_class_vmt::<Player>(module, "game::Player") }.unwrap();
+    assert_eq!(
+        unsafe { (player_vmt.0.max_health.unwrap())(&*(boss as *const _ as *const u8)) },
+        100
+    );
+
+    // `Player` has fewer virtual methods than `Boss`
+    assert_eq!(
+        unsafe { rtti::find_class_vmt::<Boss>(module, "game::Player") }.map(|_| ()),
+        Err(RttiError::InvalidSlot(2))
+    );
+
+    unsafe { boss_free(boss) };
+}
@@ -0,0 +1,148 @@
+#![cfg(bridgeless_fixtures)]
+
+use std::{
+    ptr::{self, addr_of_mut},
+    sync::atomic::Ordering,
+    thread,
+};
+
+use bridgeless::{overrides, ClassLayout, Cls};
+use bridgeless_cpp_tests::runtime_overrides::*;
+
+#[test]
+fn override_spawned_enemies() {
+    let enemy = unsafe { &mut *enemy_spawn(10, 3) };
+    let base_vmt = enemy.layout().vtable();
+    assert!(overrides::vtable::<Berserker>().is_none());
+
+    // Concurrent registrations build a single vtable
+    let vtables: Vec<_> = (0..8)
+        .map(|_| {
+            thread::spawn(move || {
+                let vmt = unsafe { overrides::register::<Berserker, Enemy>(base_vmt) }.unwrap();
+                ptr::from_ref(vmt) as usize
+            })
+        })
+        .collect::<Vec<_>>()
+        .into_iter()
+        .map(|handle| handle.join().unwrap())
+        .collect();
+    assert!(vtables.iter().all(|&vtable| vtable == vtables[0]));
+    let vmt = overrides::vtable::<Berserker>().unwrap();
+    assert_eq!(ptr::from_ref(vmt) as usize, vtables[0]);
+
+    // Instances created before the hook was installed can be overridden directly
+    assert_eq!(unsafe { enemy_attack((&*enemy).into()) }, 3);
+    assert!(overrides::apply(enemy.as_dyn_mut()));
+    assert!(!overrides::apply(enemy.as_dyn_mut()));
+    assert_eq!(unsafe { enemy_attack((&*enemy).into()) }, 6);
+
+    unsafe { *addr_of_mut!(enemy_spawn_hook) = Some(overrides::on_construct) };
+    let spawned = unsafe { &mut *enemy_spawn(20, 5) };
+    unsafe { *addr_of_mut!(enemy_spawn_hook) = None };
+
+    assert_eq!(unsafe { enemy_attack((&*spawned).into()) }, 10);
+    assert_eq!(spawned.attack(), 10);
+
+    // Methods which are not overridden, the destructor and RTTI come from the C++ vtable
+    assert_eq!(unsafe { enemy_take_damage((&mut *spawned).into(), 4) }, 16);
+    assert!(unsafe { enemy_is_exact((&*spawned).into()) });
+    let destroyed = unsafe { enemies_destroyed };
+    unsafe { enemy_free(spawned) };
+    unsafe { enemy_free(enemy) };
+    assert_eq!(unsafe { enemies_destroyed }, destroyed + 2);
+}
+
+#[test]
+fn derive_from_runtime_vtable() {
+    let base_vmt = unsafe { enemy_vtable() };
+    let vmt = unsafe { overrides::register_derived::<Champion, Enemy>(base_vmt) }.unwrap();
+    let again = unsafe { overrides::register_derived::<Champion, Enemy>(base_vmt) }.unwrap();
+    assert!(ptr::eq(vmt, again));
+
+    let mut champion: Cls<Champion> = unsafe {
+        Cls::with_vtable(
+            ChampionLayout(
+                EnemyLayout(
+                    (),
+                    Enemy {
+                        health: 30,
+                        strength: 4,
+                    },
+                ),
+                Champion { bonus: 3 },
+            ),
+            vmt,
+        )
+    };
+    assert_eq!(unsafe { enemy_attack((&champion).into()) }, 7);
+    assert_eq!(champion.taunt(), 30);
+    assert_eq!(unsafe { enemy_take_damage((&mut champion).into(), 5) }, 25);
+    assert!(unsafe { enemy_is_exact((&champion).into()) });
+
+    // Instances created from Rust are not affected by `apply`
+    assert!(!overrides::apply(champion.upcast_mut::<Enemy>()));
+
+    let dropped = CHAMPIONS_DROPPED.load(Ordering::Relaxed);
+    drop(champion);
+    assert_eq!(CHAMPIONS_DROPPED.load(Ordering::Relaxed), dropped + 1);
+}
+
+#[test]
+fn register_then_register_derived() {
+    let boss = unsafe { &mut *boss_spawn(0, 0) };
+    let base_vmt = unsafe { boss.as_dyn().downcast::<Boss>() }.as_concrete().layout().vtable();
+    unsafe { enemy_free(boss) };
+    let vmt = unsafe { overrides::register::<Brute, Boss>(base_vmt) }.unwrap();
+    let derived = unsafe { overrides::register_derived::<Brute, Boss>(base_vmt) }.unwrap();
+    assert!(!ptr::eq(vmt, derived));
+    assert!(ptr::eq(overrides::vtable::<Brute>().unwrap(), vmt));
+    assert!(ptr::eq(
+        overrides::derived_vtable::<Brute>().unwrap(),
+        derived
+    ));
+
+    // The vtable of instances created from Rust drops them, unlike the C++ destructor
+    let brute: Cls<Brute> = unsafe {
+        Cls::with_vtable(
+            BruteLayout(
+                BossLayout(
+                    EnemyLayout(
+                        (),
+                        Enemy {
+                            health: 10,
+                            strength: 2,
+                        },
+                    ),
+                    Boss,
+                ),
+                Brute,
+            ),
+            derived,
+        )
+    };
+    assert_eq!(unsafe { enemy_attack(brute.upcast::<Enemy>().into()) }, 6);
+    let dropped = BRUTES_DROPPED.load(Ordering::Relaxed);
+    drop(brute);
+    assert_eq!(BRUTES_DROPPED.load(Ordering::Relaxed), dropped + 1);
+}
+
+#[test]
+fn register_derived_then_register() {
+    let enemy = unsafe { &mut *minion_spawn(10, 3) };
+    let minion = unsafe { enemy.as_dyn_mut().downcast_mut::<Minion>() };
+    let base_vmt = minion.as_concrete().layout().vtable();
+    let derived = unsafe { overrides::register_derived::<Guard, Minion>(base_vmt) }.unwrap();
+    let vmt = unsafe { overrides::register::<Guard, Minion>(base_vmt) }.unwrap();
+    assert!(!ptr::eq(vmt, derived));
+
+    // The vtable built by `register` is used by `apply`, even if `register_derived` came first
+    assert!(overrides::apply(minion));
+    assert_eq!(
+        minion.as_concrete().layout().vtable() as *const _ as usize,
+        vmt as *const _ as usize
+    );
+    let enemy = minion.upcast_mut::<Enemy>();
+    assert_eq!(unsafe { enemy_attack((&*enemy).into()) }, 12);
+    unsafe { enemy_free(enemy.into()) };
+}
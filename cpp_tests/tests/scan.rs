@@ -0,0 +1,57 @@
+#![cfg(bridgeless_fixtures)]
+
+use std::ptr::NonNull;
+
+use bridgeless::{
+    module::Module,
+    scan::{self, Pattern},
+};
+use bridgeless_cpp_tests::nonvirtual::*;
+
+#[test]
+fn find_cpp_vtable_by_slot_contents() {
+    let gauge = unsafe { &mut *gauge_new(40) };
+    let vptr = unsafe { *(gauge as *const _ as *const NonNull<u8>) };
+
+    // Any vtable whose first slot is `Gauge::capacity`
+    let capacity = gauge_capacity as *const () as usize;
+    let pattern = Pattern::from_bytes(&capacity.to_ne_bytes());
+    let module = Module::main();
+    let candidates: Vec<_> = module
+        .sections()
+        .filter(|section| section.is_readable())
+        .flat_map(|section| {
+            let bytes = unsafe { section.bytes() };
+            pattern
+                .find_iter(bytes)
+                .map(move |offset| unsafe { section.address().add(offset) })
+        })
+        .collect();
+    assert!(candidates.contains(&(vptr.as_ptr() as *const u8)));
+
+    let vmt = unsafe { scan::vmt::<Gauge>(vptr) };
+    let capacity_slot = vmt.0.capacity.unwrap();
+    assert_eq!(capacity_slot as usize, capacity);
+    assert_eq!(
+        unsafe { capacity_slot(&*(gauge as *const _ as *const u8)) },
+        100
+    );
+
+    unsafe { gauge_free(gauge) };
+}
+
+#[test]
+fn shared_library_sections() {
+    let module = Module::containing(counter_new as *const ()).unwrap();
+    assert_ne!(module, Module::main());
+
+    let code = module.section_containing(counter_new as *const ()).unwrap();
+    assert!(code.is_executable());
+
+    let prologue = unsafe { std::slice::from_raw_parts(counter_new as *const u8, 16) };
+    let found = unsafe { code.find_pattern(&Pattern::from_bytes(prologue)) };
+    assert_eq!(
+        found.map(|f| f.as_ptr() as usize),
+        Some(counter_new as *const () as usize)
+    );
+}
@@ -0,0 +1,44 @@
+#![cfg(bridgeless_fixtures)]
+
+use bridgeless::Cls;
+use bridgeless_cpp_tests::sret::*;
+
+#[test]
+fn call_cpp_methods_returning_classes() {
+    let transform = unsafe { &*transform_new(2) };
+    assert_eq!(
+        transform.position(),
+        Vector3 {
+            x: 2.0,
+            y: 4.0,
+            z: 6.0
+        }
+    );
+    assert_eq!(
+        transform.extent(),
+        Extent {
+            width: 2,
+            height: 4
+        }
+    );
+    assert_eq!(transform.handle(5), Handle { id: 7 });
+    unsafe { transform_free(transform as *const _ as *mut _) };
+}
+
+#[test]
+fn override_methods_returning_classes() {
+    let origin = Vector3 {
+        x: 1.0,
+        y: 0.5,
+        z: -1.0,
+    };
+    let offset: Cls<Offset> = Cls::new(OffsetLayout(
+        TransformLayout((), Transform { scale: 4 }),
+        Offset { origin },
+    ));
+    let transform = offset.upcast::<Transform>();
+    assert_eq!(unsafe { transform_position_sum(transform.into()) }, 2.0);
+    assert_eq!(unsafe { transform_area(transform.into()) }, 28);
+    assert_eq!(unsafe { transform_handle_id(transform.into(), 3) }, -3);
+    assert_eq!(offset.handle(3), Handle { id: -3 });
+}
@@ -0,0 +1,289 @@
+#![cfg(bridgeless_fixtures)]
+
+use std::ffi::{CStr, CString};
+
+use bridgeless::stl::{CapacityError, ClassIterExt};
+use bridgeless_cpp_tests::stl::*;
+
+/// Owns a `StlFields` instance allocated by C++.
+struct Fixture(*mut StlFields);
+
+impl Fixture {
+    fn new(name: &str, value_count: usize) -> Self {
+        let name = CString::new(name).unwrap();
+        Self(unsafe { stl_fields_new(name.as_ptr(), value_count) })
+    }
+
+    fn get(&self) -> &StlFields {
+        unsafe { &*self.0 }
+    }
+
+    fn get_mut(&mut self) -> &mut StlFields {
+        unsafe { &mut *self.0 }
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        unsafe { stl_fields_free(self.0) }
+    }
+}
+
+const LONG_NAME: &str = "a name long enough to not fit in the small string buffer";
+
+#[test]
+fn read_small_string() {
+    let fixture = Fixture::new("short", 0);
+    let name = &fixture.get().name;
+
+    assert_eq!(name.len(), 5);
+    assert_eq!(name.capacity(), 15);
+    assert_eq!(name, "short");
+    assert_eq!(name.as_c_str(), c"short");
+    assert_eq!(name.as_ptr() as *const _, unsafe {
+        stl_fields_name(fixture.0)
+    });
+}
+
+#[test]
+fn read_heap_string() {
+    let fixture = Fixture::new(LONG_NAME, 0);
+    let name = &fixture.get().name;
+
+    assert_eq!(name.len(), LONG_NAME.len());
+    assert!(name.capacity() >= LONG_NAME.len());
+    assert_eq!(name.to_str(), Ok(LONG_NAME));
+    assert_eq!(format!("{name:?}"), format!("{LONG_NAME:?}"));
+}
+
+#[test]
+fn read_wide_string() {
+    let fixture = Fixture::new("wide", 0);
+    let wide_name = &fixture.get().wide_name;
+
+    assert_eq!(wide_name.capacity(), 7);
+    assert_eq!(
+        wide_name.as_slice(),
+        "wide".encode_utf16().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn write_string_within_capacity() {
+    for initial in ["short", LONG_NAME] {
+        let mut fixture = Fixture::new(initial, 0);
+        let name = &mut fixture.get_mut().name;
+
+        name.try_assign_str("renamed").unwrap();
+        name.try_push(b"!").unwrap();
+        name.as_mut_slice()[0] = b'R';
+        assert_eq!(name, "Renamed!");
+
+        let cpp_name = unsafe { CStr::from_ptr(stl_fields_name(fixture.0)) };
+        assert_eq!(cpp_name, c"Renamed!");
+        assert_eq!(unsafe { stl_fields_name_len(fixture.0) }, 8);
+    }
+}
+
+#[test]
+fn write_string_over_capacity() {
+    let mut fixture = Fixture::new("short", 0);
+    let name = &mut fixture.get_mut().name;
+
+    assert_eq!(
+        name.try_assign_str(LONG_NAME),
+        Err(CapacityError {
+            required: LONG_NAME.len(),
+            capacity: 15
+        })
+    );
+    assert!(name.try_push(b"0123456789a").is_err());
+    assert_eq!(name, "short");
+
+    name.truncate(2);
+    assert_eq!(name, "sh");
+    assert_eq!(unsafe { stl_fields_name_len(fixture.0) }, 2);
+}
+
+#[test]
+fn read_write_vector() {
+    let mut fixture = Fixture::new("", 5);
+    let values = &mut fixture.get_mut().values;
+
+    assert_eq!(values.len(), 5);
+    assert!(values.capacity() >= 5);
+    assert_eq!(values.as_slice(), [0, 10, 20, 30, 40]);
+
+    values[4] = 100;
+    for v in values.iter_mut() {
+        *v += 1;
+    }
+    assert_eq!(
+        unsafe { stl_fields_values_sum(fixture.0) },
+        1 + 11 + 21 + 31 + 101
+    );
+}
+
+#[test]
+fn read_empty_vector() {
+    let fixture = Fixture::new("", 0);
+
+    assert!(fixture.get().empty_values.is_empty());
+    assert!(fixture.get().empty_values.as_ptr().is_null());
+    assert_eq!(fixture.get().empty_values.as_slice(), &[] as &[i64]);
+}
+
+#[test]
+fn read_write_unique_ptr() {
+    let mut fixture = Fixture::new("", 0);
+
+    assert!(fixture.get().empty_box.is_null());
+    assert_eq!(fixture.get().empty_box.get(), None);
+    assert_eq!(fixture.get().boxed.get(), Some(&42));
+
+    *fixture.get_mut().boxed.get_mut().unwrap() = 7;
+    assert_eq!(unsafe { stl_fields_boxed(fixture.0) }, 7);
+}
+
+#[test]
+fn read_shared_ptr() {
+    let fixture = Fixture::new("", 0);
+    let fields = fixture.get();
+
+    assert_eq!(fields.shared.get(), Some(&1337));
+    assert_eq!(fields.shared.as_ptr(), fields.shared_copy.as_ptr());
+    assert_eq!(fields.shared.use_count(), 2);
+    assert_eq!(unsafe { stl_fields_shared_use_count(fixture.0) }, 2);
+}
+
+/// Owns a `Registry` instance allocated by C++.
+struct RegistryFixture(*mut Registry);
+
+impl RegistryFixture {
+    fn new(count: i32) -> Self {
+        Self(unsafe { stl_registry_new(count) })
+    }
+
+    fn get(&self) -> &Registry {
+        unsafe { &*self.0 }
+    }
+}
+
+impl Drop for RegistryFixture {
+    fn drop(&mut self) {
+        unsafe { stl_registry_free(self.0) }
+    }
+}
+
+/// Keys in the order the fixture inserts them.
+fn insertion_order(count: i32) -> Vec<i32> {
+    (0..count).map(|i| (i * 7) % count).collect()
+}
+
+#[test]
+fn traverse_list() {
+    let fixture = RegistryFixture::new(10);
+    let registry = fixture.get();
+
+    assert_eq!(registry.list.len(), 10);
+    assert_eq!(registry.list.front(), Some(&0));
+    assert_eq!(
+        registry.list.iter().copied().collect::<Vec<_>>(),
+        insertion_order(10)
+    );
+    assert!(registry.empty_list.is_empty());
+    assert_eq!(registry.empty_list.iter().next(), None);
+}
+
+#[test]
+fn traverse_set() {
+    for count in [0, 1, 2, 3, 10, 100] {
+        let fixture = RegistryFixture::new(count);
+        let registry = fixture.get();
+
+        assert_eq!(registry.set.len(), count as usize);
+        assert_eq!(
+            registry.set.iter().copied().collect::<Vec<_>>(),
+            Vec::from_iter(0..count)
+        );
+
+        let mut expected: Vec<_> = (0..count).map(|k| k / 2).collect();
+        expected.sort();
+        assert_eq!(
+            registry.multiset.iter().copied().collect::<Vec<_>>(),
+            expected
+        );
+    }
+}
+
+#[test]
+fn traverse_map() {
+    let fixture = RegistryFixture::new(10);
+    let registry = fixture.get();
+
+    assert_eq!(registry.entities.len(), 10);
+    assert_eq!(
+        registry.entities.keys().copied().collect::<Vec<_>>(),
+        Vec::from_iter(0..10)
+    );
+    for (key, entity) in &registry.entities {
+        assert_eq!(unsafe { (&**entity).id }, *key);
+    }
+
+    let multimap: Vec<_> = registry.multimap.iter().map(|(k, v)| (*k, *v)).collect();
+    assert_eq!(multimap.len(), 10);
+    assert!(multimap.windows(2).all(|w| w[0].0 <= w[1].0));
+    assert!(multimap.iter().all(|(k, v)| *k == (*v / 100) as i32 / 2));
+
+    assert_eq!(registry.empty_map.iter().next(), None);
+    assert_eq!(format!("{:?}", registry.empty_map), "{}");
+}
+
+#[test]
+fn traverse_unordered_map() {
+    let fixture = RegistryFixture::new(10);
+    let registry = fixture.get();
+
+    let mut keys: Vec<_> = registry.entities_by_hash.keys().copied().collect();
+    keys.sort();
+    assert_eq!(keys, Vec::from_iter(0..10));
+    assert_eq!(
+        registry.entities_by_hash.get(&3).map(|e| unsafe { (&**e).id }),
+        Some(3)
+    );
+    assert_eq!(registry.entities_by_hash.get(&10), None);
+
+    let mut names: Vec<_> = registry.names.iter().map(|s| s.to_str().unwrap()).collect();
+    names.sort();
+    assert_eq!(names.len(), 10);
+    assert_eq!(names[0], "entity #0");
+    assert_eq!(names[9], "entity #9");
+
+    assert!(registry.empty_unordered_map.is_empty());
+    assert_eq!(registry.empty_unordered_map.iter().len(), 0);
+}
+
+#[test]
+fn iterate_class_pointers() {
+    let fixture = RegistryFixture::new(10);
+    let registry = fixture.get();
+
+    // The fixture owns the entities for as long as the registry is borrowed
+    let ids: Vec<_> = unsafe { registry.entities.values().classes_unchecked() }
+        .map(|e| e.id)
+        .collect();
+    assert_eq!(ids, Vec::from_iter(0..10));
+
+    let mut ids: Vec<_> = unsafe { registry.entities_by_hash.values().classes_unchecked() }
+        .map(|e| e.id)
+        .collect();
+    ids.sort();
+    assert_eq!(ids, Vec::from_iter(0..10));
+
+    // Null pointers are skipped
+    assert_eq!(registry.entity_list.len(), 20);
+    let ids: Vec<_> = unsafe { registry.entity_list.iter().classes_unchecked() }
+        .map(|e| e.id)
+        .collect();
+    assert_eq!(ids, insertion_order(10));
+}
@@ -0,0 +1,68 @@
+#![cfg(bridgeless_fixtures)]
+
+use bridgeless::{CRef, CRefMut, Class, DynCls};
+use bridgeless_cpp_tests::templates::*;
+
+#[test]
+fn call_cpp_template_virtuals() {
+    let ints = unsafe { array_i32_new(3, 2) };
+    let floats = unsafe { array_f64_new(2, 0.25) };
+    let (ints, floats) = unsafe { ((*ints).as_dyn_mut(), (*floats).as_dyn_mut()) };
+
+    assert_eq!(ints.len, 3);
+    assert_eq!(ints.sum(), 6);
+    ints.set(1, 10);
+    assert_eq!(ints.get(1), 10);
+    assert_eq!(ints.items, [2, 10, 2, 2]);
+    assert_eq!(ints.sum(), 14);
+
+    floats.set(0, 1.5);
+    assert_eq!(floats.get(0), 1.5);
+    assert_eq!(floats.sum(), 1.75);
+
+    unsafe {
+        array_i32_free(ints.as_concrete_mut());
+        array_f64_free(floats.as_concrete_mut());
+    }
+}
+
+#[test]
+fn cpp_calls_rust_template_impls() {
+    let mut ints = Array::new(4, 3i32);
+    let mut floats = Array::new(1, 0.5f64);
+
+    unsafe { array_i32_set(CRefMut::new_mut(&mut ints), 3, 7) };
+    assert_eq!(ints.items, [3, 3, 3, 7]);
+    assert_eq!(ints.sum(), 16);
+    assert_eq!(unsafe { array_i32_sum(CRef::new(&ints)) }, 16);
+
+    unsafe { array_f64_set(CRefMut::new_mut(&mut floats), 0, 2.5) };
+    assert_eq!(unsafe { array_f64_get(CRef::new(&floats), 0) }, 2.5);
+    assert_eq!(unsafe { array_f64_sum(CRef::new(&floats)) }, 2.5);
+}
+
+#[test]
+fn cpp_calls_rust_overrides_of_generic_class() {
+    let ints = Clamped::new([1, 5, 9, 2], 4i32);
+    let floats = Clamped::new([0.5, 3.0, 1.0, 8.0], 2.0f64);
+
+    assert_eq!(ints.get(2), 4);
+    assert_eq!(unsafe { array_i32_get(CRef::from(&ints), 1) }, 4);
+    // `sum` is implemented in C++ and calls `get` through the vtable
+    assert_eq!(unsafe { array_i32_sum(CRef::from(&ints)) }, 1 + 4 + 4 + 2);
+    assert_eq!(
+        unsafe { array_f64_sum(CRef::from(&floats)) },
+        0.5 + 2.0 + 1.0 + 2.0
+    );
+
+    let array: &DynCls<Array<f64>> = floats.upcast();
+    assert_eq!(array.get(3), 2.0);
+    assert_eq!(array.sum(), 5.5);
+}
+
+#[test]
+fn template_instantiations_are_distinct_classes() {
+    assert_eq!(Clamped::<i32>::base_offset::<Array<i32>>(), Some(0));
+    assert_eq!(Clamped::<i32>::base_offset::<Array<f64>>(), None);
+    assert_eq!(Array::<f64>::base_offset::<Array<i32>>(), None);
+}
@@ -56,3 +56,31 @@ pub fn generics_to_path_args(generics: &Generics) -> PathArguments {
         gt_token: Token![>](Span::call_site()),
     })
 }
+
+pub fn append_path(path: &Path, ident: &str, args: &PathArguments) -> Path {
+    let mut path = path.clone();
+    path.segments.push(PathSegment {
+        ident: Ident::new(ident, Span::call_site()),
+        arguments: args.clone(),
+    });
+    path
+}
+
+/// Whether the type is a reference or a pointer, which has no default value.
+pub fn is_pointer_like(ty: &Type) -> bool {
+    match ty {
+        Type::Reference(_) | Type::Ptr(_) | Type::BareFn(_) => true,
+        Type::Group(group) => is_pointer_like(&group.elem),
+        Type::Paren(paren) => is_pointer_like(&paren.elem),
+        Type::Path(path) => {
+            path.qself.is_none()
+                && path.path.segments.last().is_some_and(|segment| {
+                    matches!(
+                        segment.ident.to_string().as_str(),
+                        "CRef" | "CRefMut" | "NonNull"
+                    )
+                })
+        }
+        _ => false,
+    }
+}
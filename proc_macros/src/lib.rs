@@ -1,72 +1,423 @@
 use proc_macro::TokenStream;
 use proc_macro2 as pm2;
-use proc_macro_error::{
-    abort, abort_call_site, emit_call_site_error, emit_error, proc_macro_error,
-};
-use quote::{quote, ToTokens};
+use proc_macro_error::{abort, abort_call_site, emit_error, proc_macro_error};
+use quote::{format_ident, quote, quote_spanned};
+use syn::ext::IdentExt;
+use syn::parse::{Parse, ParseStream, Parser};
+use syn::punctuated::Punctuated;
+use syn::spanned::Spanned;
 use syn::*;
 
 mod helpers;
+mod lifetimes;
 
 #[proc_macro_error]
 #[proc_macro_derive(Class)]
 pub fn derive_class(input: TokenStream) -> TokenStream {
-    let input = parse_macro_input!(input as DeriveInput);
+    let _input = parse_macro_input!(input as DeriveInput);
 
     todo!()
 }
 
-fn consume_offset(attrs: &mut Vec<Attribute>) -> Option<(Attribute, usize)> {
+/// Explicit vtable slot of a virtual method, set with `#[offset(...)]`.
+#[derive(Clone, Copy)]
+enum SlotOffset {
+    /// Index of the slot in the vtable part of the class.
+    Index(usize),
+    /// Offset in bytes of the slot from the start of the vtable, including the slots of bases,
+    /// with the span of the attribute to report invalid offsets.
+    Bytes(usize, pm2::Span),
+}
+
+/// Arguments of an `#[offset(...)]` attribute: the slot of the method, optionally followed by
+/// the name of the gap before it, e.g. `#[offset(bytes = 0x48, gap = unknown)]`.
+struct OffsetArgs {
+    offset: SlotOffset,
+    gap: Option<Ident>,
+}
+
+impl Parse for OffsetArgs {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let offset = if input.peek(LitInt) {
+            SlotOffset::Index(input.parse::<LitInt>()?.base10_parse()?)
+        }
+        else {
+            let key = input.parse::<Ident>().ok().filter(|key| key == "bytes");
+            if key.is_none() {
+                return Err(input.error("expected a slot index or `bytes = ...`"));
+            }
+            input.parse::<Token![=]>()?;
+            let bytes = input.parse::<LitInt>()?;
+            SlotOffset::Bytes(bytes.base10_parse()?, bytes.span())
+        };
+        let mut gap = None;
+        if input.parse::<Option<Token![,]>>()?.is_some() && !input.is_empty() {
+            let key = input.parse::<Ident>().ok().filter(|key| key == "gap");
+            if key.is_none() {
+                return Err(input.error("expected `gap = name`"));
+            }
+            input.parse::<Token![=]>()?;
+            gap = Some(input.parse()?);
+        }
+        Ok(Self { offset, gap })
+    }
+}
+
+fn consume_offset(attrs: &mut Vec<Attribute>) -> Option<(Attribute, OffsetArgs)> {
     let mut offset_attrs = Vec::new();
     attrs.retain(|attr| {
         attr.path()
             .is_ident("offset")
             .then(|| offset_attrs.push(attr.clone()))
-            .is_some()
+            .is_none()
     });
 
     for attr in offset_attrs.iter().skip(1) {
         emit_error!(attr, "duplicate offset attribute is not allowed");
     }
 
-    offset_attrs.first().and_then(|attr| {
-        attr.parse_args::<pm2::Literal>()
-            .ok()
-            .and_then(|lit| str::parse::<usize>(&lit.to_string()).ok().map(|o| (attr.clone(), o)))
-            .or_else(|| {
-                emit_error!(attr, "must provide valid usize literal as an argument");
-                None
-            })
+    offset_attrs.first().and_then(|attr| match attr.parse_args::<OffsetArgs>() {
+        Ok(args) => Some((attr.clone(), args)),
+        Err(err) => {
+            emit_error!(err.span(), "{}", err);
+            None
+        }
     })
 }
 
+/// Removes the attribute binding a non-virtual method to an address from `attrs`, returning an
+/// expression evaluating to its `AddressSource`.
+fn consume_address(attrs: &mut Vec<Attribute>) -> Option<pm2::TokenStream> {
+    let mut sources = Vec::new();
+    attrs.retain(|attr| {
+        let path = attr.path();
+        let source = if path.is_ident("rva") {
+            attr.parse_args::<Expr>().map(|rva| quote!(::bridgeless::address::Rva(#rva)))
+        }
+        else if path.is_ident("symbol") {
+            attr.parse_args::<LitStr>().and_then(|name| {
+                let c_name = std::ffi::CString::new(name.value()).map_err(|_| {
+                    syn::Error::new(name.span(), "symbol names cannot contain NUL bytes")
+                })?;
+                let c_name = pm2::Literal::c_string(&c_name);
+                Ok(quote!(::bridgeless::address::Symbol(#c_name)))
+            })
+        }
+        else if path.is_ident("address") {
+            attr.parse_args::<Expr>().map(|source| quote!(#source))
+        }
+        else {
+            return true;
+        };
+        match source {
+            Ok(source) => sources.push((attr.clone(), source)),
+            Err(err) => emit_error!(err.span(), "{}", err),
+        }
+        false
+    });
+
+    for (attr, _) in sources.iter().skip(1) {
+        emit_error!(
+            attr,
+            "a non-virtual method can only be bound to a single address"
+        );
+    }
+    sources.into_iter().next().map(|(_, source)| source)
+}
+
+/// Removes the `#[cpp_name = "..."]` attribute from `attrs`, returning the C++ name of the method.
+fn consume_cpp_name(attrs: &mut Vec<Attribute>) -> Option<String> {
+    let mut names = Vec::new();
+    attrs.retain(|attr| {
+        if !attr.path().is_ident("cpp_name") {
+            return true;
+        }
+        let name = match &attr.meta {
+            Meta::NameValue(MetaNameValue {
+                value:
+                    Expr::Lit(ExprLit {
+                        lit: Lit::Str(name),
+                        ..
+                    }),
+                ..
+            }) => Some(name.value()),
+            _ => None,
+        };
+        match name {
+            Some(name) => names.push((attr.clone(), name)),
+            None => emit_error!(attr, "expected a string literal: `#[cpp_name = \"name\"]`"),
+        }
+        false
+    });
+
+    for (attr, _) in names.iter().skip(1) {
+        emit_error!(attr, "duplicate cpp_name attribute is not allowed");
+    }
+    names.into_iter().next().map(|(_, name)| name)
+}
+
+/// What the thunks of a class do when the Rust implementation of a method panics, set with
+/// `#[class(panic = "...")]`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum PanicPolicy {
+    /// Abort the process with a diagnostic naming the method.
+    Abort,
+    /// Catch the panic and return the default value of the return type.
+    Catch,
+    /// Unwind into the C++ caller, using the `C-unwind` ABI.
+    Unwind,
+}
+
+impl PanicPolicy {
+    fn from_args(attr: TokenStream) -> Self {
+        let args = match Punctuated::<MetaNameValue, Token![,]>::parse_terminated.parse(attr) {
+            Ok(args) => args,
+            Err(err) => abort!(err.span(), "{}", err),
+        };
+
+        let mut policy = PanicPolicy::Abort;
+        for arg in args {
+            if !arg.path.is_ident("panic") {
+                abort!(arg.path, "unknown class argument, expected `panic`");
+            }
+            policy = match &arg.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(value),
+                    ..
+                }) => match value.value().as_str() {
+                    "abort" => PanicPolicy::Abort,
+                    "catch" => PanicPolicy::Catch,
+                    "unwind" => PanicPolicy::Unwind,
+                    _ => abort!(
+                        value,
+                        "panic policy must be \"abort\", \"catch\" or \"unwind\""
+                    ),
+                },
+                other => abort!(other, "expected a string literal"),
+            };
+        }
+        policy
+    }
+
+    /// ABI of the functions in the vtable of the class.
+    fn abi(self) -> LitStr {
+        let abi = match self {
+            PanicPolicy::Unwind => "C-unwind",
+            _ => "C",
+        };
+        LitStr::new(abi, pm2::Span::call_site())
+    }
+}
+
+/// How the return value of a method is passed, set with `#[sret]`.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Sret {
+    /// Return convention of C functions.
+    No,
+    /// C++ class returned by value, through a hidden pointer after `this` with the MSVC ABI.
+    Class,
+    /// C++ class which is not trivially copyable, always returned through a hidden pointer.
+    NonTrivial,
+}
+
+/// Removes the `#[sret]` attribute from `attrs`, returning how the method returns its value.
+fn consume_sret(attrs: &mut Vec<Attribute>) -> Option<(Attribute, Sret)> {
+    let mut srets = Vec::new();
+    attrs.retain(|attr| {
+        if !attr.path().is_ident("sret") {
+            return true;
+        }
+        let sret = match &attr.meta {
+            Meta::Path(_) => Some(Sret::Class),
+            Meta::List(_) => match attr.parse_args::<Ident>() {
+                Ok(arg) if arg == "non_trivial" => Some(Sret::NonTrivial),
+                _ => None,
+            },
+            Meta::NameValue(_) => None,
+        };
+        match sret {
+            Some(sret) => srets.push((attr.clone(), sret)),
+            None => emit_error!(attr, "expected `#[sret]` or `#[sret(non_trivial)]`"),
+        }
+        false
+    });
+
+    for (attr, _) in srets.iter().skip(1) {
+        emit_error!(attr, "duplicate sret attribute is not allowed");
+    }
+    srets.into_iter().next()
+}
+
+/// Removes the `#[destructor]` attribute from `attrs`, returning true if it was present.
+fn consume_destructor(attrs: &mut Vec<Attribute>) -> bool {
+    let len = attrs.len();
+    attrs.retain(|attr| !attr.path().is_ident("destructor"));
+    attrs.len() != len
+}
+
+/// C++ constructor of a class bound to an address, declared with `#[constructor]`.
+struct Constructor {
+    fun: TraitItemFn,
+    address: pm2::TokenStream,
+    arg_names: Vec<Ident>,
+}
+
+impl Constructor {
+    /// Removes the constructors from the items of `trait_def`.
+    fn take_from_trait_def(trait_def: &mut ItemTrait) -> Vec<Self> {
+        let mut constructors = Vec::new();
+        trait_def.items.retain_mut(|item| {
+            let fun = match item {
+                TraitItem::Fn(fun) => fun,
+                _ => return true,
+            };
+            let len = fun.attrs.len();
+            fun.attrs.retain(|attr| !attr.path().is_ident("constructor"));
+            if fun.attrs.len() == len {
+                return true;
+            }
+
+            let mut fun = fun.clone();
+            let address = consume_address(&mut fun.attrs).unwrap_or_else(|| {
+                emit_error!(
+                    fun.sig,
+                    "constructor must be bound to an address with #[rva], #[symbol] or #[address]"
+                );
+                quote!(::bridgeless::address::Rva(0))
+            });
+            if let Some(block) = &fun.default {
+                emit_error!(block, "constructor bindings cannot have a body");
+            }
+            if let Some(receiver) = fun.sig.receiver() {
+                emit_error!(receiver, "constructor cannot have a receiver");
+            }
+            if let ReturnType::Type(..) = fun.sig.output {
+                emit_error!(fun.sig.output, "constructor cannot have a return type");
+            }
+            if !fun.sig.generics.params.is_empty() {
+                emit_error!(fun.sig.generics, "constructor cannot be generic");
+            }
+
+            let arg_names = fun
+                .sig
+                .inputs
+                .iter()
+                .enumerate()
+                .map(|(i, arg)| match arg {
+                    FnArg::Typed(pt) => match pt.pat.as_ref() {
+                        Pat::Ident(pi) if pi.subpat.is_none() => pi.ident.clone(),
+                        _ => format_ident!("arg{}", i),
+                    },
+                    FnArg::Receiver(_) => format_ident!("arg{}", i),
+                })
+                .collect();
+
+            constructors.push(Constructor {
+                fun,
+                address,
+                arg_names,
+            });
+            false
+        });
+        constructors
+    }
+}
+
 struct VmtFn {
     fun: TraitItemFn,
-    offset: usize,
+    /// Source of the address of a non-virtual method, which has no vtable slot.
+    address: Option<pm2::TokenStream>,
+    /// Whether this is the virtual destructor, which takes `Destructor::SLOTS` slots.
+    destructor: bool,
+    /// Name of the method in C++, used to group overloads.
+    cpp_name: String,
+    /// Whether the method takes C variadic arguments, in which case it can only be called.
+    variadic: bool,
+    sret: Sret,
+    /// ABI of the function, which depends on the panic policy of the class.
+    abi: LitStr,
+    /// Explicit slot of the method, which otherwise follows the previous one.
+    offset: Option<SlotOffset>,
+    /// Name of the gap before the explicit slot of the method.
+    gap: Option<Ident>,
     receiver_mutability: Option<Token![mut]>,
+    arg_names: Vec<Ident>,
+    explicit_sig: lifetimes::ExplicitSig,
 }
 
 impl VmtFn {
     fn from_trait_def(trait_def: &ItemTrait) -> impl Iterator<Item = Self> + use<'_> {
-        let mut offset_counter = 0;
+        // Minimum index of the next explicit slot, unknown after a byte offset
+        let mut offset_counter = Some(0);
+        let mut has_destructor = false;
         trait_def.items.iter().filter_map(move |item| match item {
             TraitItem::Fn(fun) => {
                 let mut fun = fun.clone();
-                let mut offset = offset_counter;
-                if let Some((attr, ofs)) = consume_offset(&mut fun.attrs) {
-                    if ofs < offset_counter {
-                        abort!(attr, "offset must be strictly increasing");
+                let address = consume_address(&mut fun.attrs);
+                let destructor = consume_destructor(&mut fun.attrs);
+                let cpp_name =
+                    consume_cpp_name(&mut fun.attrs).unwrap_or_else(|| fun.sig.ident.to_string());
+                if destructor {
+                    if address.is_some() {
+                        emit_error!(fun.sig, "a virtual destructor cannot be non-virtual");
+                    }
+                    if let Some(block) = &fun.default {
+                        emit_error!(
+                            block,
+                            "virtual destructors cannot have a body, class data is dropped instead"
+                        );
                     }
-                    else {
-                        offset = ofs;
+                    let mut_receiver = matches!(
+                        fun.sig.inputs.first(),
+                        Some(FnArg::Receiver(r)) if r.reference.is_some() && r.mutability.is_some()
+                    );
+                    if !mut_receiver
+                        || fun.sig.inputs.len() != 1
+                        || matches!(fun.sig.output, ReturnType::Type(..))
+                    {
+                        emit_error!(
+                            fun.sig,
+                            "virtual destructor must have a `&mut self` receiver only"
+                        );
+                    }
+                    if std::mem::replace(&mut has_destructor, true) {
+                        emit_error!(fun.sig, "class can only have a single virtual destructor");
+                    }
+                }
+                let (mut offset, mut gap) = (None, None);
+                if address.is_some() {
+                    if let Some((attr, _)) = consume_offset(&mut fun.attrs) {
+                        emit_error!(attr, "non-virtual methods do not have a vtable offset");
+                    }
+                    if let Some(block) = &fun.default {
+                        emit_error!(block, "non-virtual method bindings cannot have a body");
                     }
                 }
-                offset_counter = offset + 1;
+                else if let Some((attr, args)) = consume_offset(&mut fun.attrs) {
+                    match (args.offset, offset_counter) {
+                        (SlotOffset::Index(ofs), Some(min)) if ofs < min => {
+                            abort!(attr, "offset must be strictly increasing");
+                        }
+                        // Slot indices cannot depend on the generic parameters of the class
+                        (SlotOffset::Bytes(..), _) if !trait_def.generics.params.is_empty() => {
+                            emit_error!(attr, "byte offsets are not supported by generic classes");
+                        }
+                        _ => (),
+                    }
+                    (offset, gap) = (Some(args.offset), args.gap);
+                }
+                if address.is_none() {
+                    offset_counter = match offset {
+                        Some(SlotOffset::Index(ofs)) => Some(ofs + 1),
+                        Some(SlotOffset::Bytes(..)) => None,
+                        None => offset_counter.map(|ofs| ofs + 1),
+                    };
+                }
 
                 let receiver_mutability = match fun.sig.inputs.first() {
                     Some(FnArg::Receiver(r)) => {
-                        if (r.colon_token.is_none() && r.reference.is_some()) {
-                            r.mutability.clone()
+                        if r.colon_token.is_none() && r.reference.is_some() {
+                            r.mutability
                         }
                         else {
                             emit_error!(
@@ -85,10 +436,61 @@ impl VmtFn {
                     }
                 };
 
+                for param in fun.sig.generics.params.iter() {
+                    if !matches!(param, GenericParam::Lifetime(_)) {
+                        emit_error!(param, "virtual function can only be generic over lifetimes");
+                    }
+                }
+                let variadic = fun.sig.variadic.is_some();
+                let sret = match consume_sret(&mut fun.attrs) {
+                    Some((attr, sret)) => {
+                        if matches!(fun.sig.output, ReturnType::Default) {
+                            emit_error!(attr, "#[sret] methods must return a value");
+                        }
+                        if variadic {
+                            emit_error!(attr, "#[sret] methods cannot be variadic");
+                        }
+                        sret
+                    }
+                    None => Sret::No,
+                };
+                if let (true, Some(block)) = (variadic, &fun.default) {
+                    emit_error!(
+                        block,
+                        "variadic methods cannot have a body, as defining C variadic functions is unstable"
+                    );
+                }
+                // Thunks are always generated with the C++ ABI of the target
+                fun.sig.abi = None;
+
+                let arg_names = fun
+                    .sig
+                    .inputs
+                    .iter()
+                    .skip(1)
+                    .enumerate()
+                    .map(|(i, arg)| match arg {
+                        FnArg::Typed(pt) => match pt.pat.as_ref() {
+                            Pat::Ident(pi) if pi.subpat.is_none() => pi.ident.clone(),
+                            _ => format_ident!("arg{}", i),
+                        },
+                        FnArg::Receiver(r) => abort!(r, "unexpected receiver"),
+                    })
+                    .collect();
+
                 Some(VmtFn {
+                    address,
+                    destructor,
+                    cpp_name,
+                    variadic,
+                    sret,
+                    abi: PanicPolicy::Abort.abi(),
                     offset,
+                    gap,
+                    explicit_sig: lifetimes::explicit_sig(&fun.sig),
                     fun,
                     receiver_mutability,
+                    arg_names,
                 })
             }
             other => {
@@ -97,25 +499,146 @@ impl VmtFn {
             }
         })
     }
+
+    fn ident(&self) -> &Ident {
+        &self.fun.sig.ident
+    }
+
+    /// Name of the associated const of the `_Impl` trait which is true when the method is
+    /// implemented by the wrapper type.
+    fn overrides_ident(&self) -> Ident {
+        format_ident!("__OVERRIDES_{}", self.ident())
+    }
+
+    /// Type of the vtable field of the method with the given ABI, which is optional to allow
+    /// inheritance.
+    fn slot_type(&self, msvc: bool) -> pm2::TokenStream {
+        match self.destructor {
+            true => quote!(::bridgeless::Destructor),
+            false => {
+                let bare_fn = self.bare_fn(msvc);
+                quote!(::core::option::Option<#bare_fn>)
+            }
+        }
+    }
+
+    /// Whether the return value is written through a hidden pointer with the given ABI.
+    fn indirect_return(&self, msvc: bool) -> bool {
+        match self.sret {
+            Sret::No => false,
+            Sret::Class => msvc,
+            Sret::NonTrivial => true,
+        }
+    }
+
+    /// Type of the function pointer stored in the vtable with the given ABI. The hidden return
+    /// pointer comes before `this` with the Itanium ABI, and after it with the MSVC ABI.
+    fn bare_fn(&self, msvc: bool) -> pm2::TokenStream {
+        let lt = &self.explicit_sig.receiver;
+        let mutability = &self.receiver_mutability;
+        self.bare_fn_with_this(msvc, quote!(&#lt #mutability u8))
+    }
+
+    /// Type of the function pointer of the slot in the typed view of the vtable of the class
+    /// `ty`, which takes `this` as a `CRef` to the class.
+    fn typed_bare_fn(&self, msvc: bool, ty: &impl quote::ToTokens) -> pm2::TokenStream {
+        let lt = &self.explicit_sig.receiver;
+        let this = match self.receiver_mutability {
+            Some(_) => quote!(::bridgeless::CRefMut<#lt, #ty>),
+            None => quote!(::bridgeless::CRef<#lt, #ty>),
+        };
+        self.bare_fn_with_this(msvc, this)
+    }
+
+    fn bare_fn_with_this(&self, msvc: bool, this: pm2::TokenStream) -> pm2::TokenStream {
+        let lifetimes = &self.explicit_sig.lifetimes;
+        let arg_types = &self.explicit_sig.inputs;
+        let output = &self.explicit_sig.output;
+        let variadic = self.variadic.then(|| quote!(...));
+        let abi = &self.abi;
+
+        let ret_ty = match output {
+            ReturnType::Type(_, ty) if self.indirect_return(msvc) => ty,
+            _ => {
+                return quote! {
+                    for<#(#lifetimes),*> unsafe extern #abi fn(#this, #(#arg_types,)* #variadic) #output
+                }
+            }
+        };
+        let (first, second) = match msvc {
+            true => (this, quote!(*mut #ret_ty)),
+            false => (quote!(*mut #ret_ty), this),
+        };
+        quote! {
+            for<#(#lifetimes),*> unsafe extern #abi fn(#first, #second, #(#arg_types),*) -> *mut #ret_ty
+        }
+    }
+
+    /// Expression calling the function pointer `_bridgeless_fn` with the arguments of the
+    /// method, passing a hidden return pointer when required by the target ABI.
+    fn call(&self, this: &pm2::TokenStream) -> pm2::TokenStream {
+        let arg_names = &self.arg_names;
+        let call_with = |msvc: bool| {
+            if !self.indirect_return(msvc) {
+                return quote!((_bridgeless_fn)(#this, #(#arg_names),*));
+            }
+            let ret = quote!(_bridgeless_ret.as_mut_ptr());
+            let (first, second) = match msvc {
+                true => (this.clone(), ret),
+                false => (ret, this.clone()),
+            };
+            quote! {{
+                let mut _bridgeless_ret = ::core::mem::MaybeUninit::uninit();
+                (_bridgeless_fn)(#first, #second, #(#arg_names),*);
+                _bridgeless_ret.assume_init()
+            }}
+        };
+
+        if self.sret == Sret::No {
+            return call_with(false);
+        }
+        let itanium = call_with(false);
+        let msvc = call_with(true);
+        quote! {{
+            #[cfg(not(target_env = "msvc"))]
+            let _bridgeless_ret = #itanium;
+            #[cfg(target_env = "msvc")]
+            let _bridgeless_ret = #msvc;
+            _bridgeless_ret
+        }}
+    }
+
+    /// Signature of the method of a variadic function, which only takes the receiver and returns
+    /// the function bound to it.
+    fn variadic_sig(&self) -> Signature {
+        let mut sig = self.fun.sig.clone();
+        let mutability = &self.receiver_mutability;
+        let bare_fn = self.bare_fn(false);
+        sig.inputs = sig.inputs.into_iter().take(1).collect();
+        sig.variadic = None;
+        sig.output = parse_quote!(-> ::bridgeless::Variadic<&#mutability u8, #bare_fn>);
+        sig
+    }
+
+    /// Signature with all argument patterns replaced by their generated names.
+    fn named_sig(&self) -> Signature {
+        let mut sig = self.fun.sig.clone();
+        for (arg, name) in sig.inputs.iter_mut().skip(1).zip(&self.arg_names) {
+            if let FnArg::Typed(pt) = arg {
+                *pt.pat = parse_quote!(#name);
+            }
+        }
+        sig
+    }
 }
 
 struct BaseClass {
-    meta_path: Path,
     data_path: Path,
     inherit_trait_path: Path,
     vmt_parts_trait_path: Path,
 }
 impl BaseClass {
     fn from_meta_path(meta_path: &Path) -> Self {
-        fn append_path(path: &Path, ident: &str, args: &PathArguments) -> Path {
-            let mut path = path.clone();
-            path.segments.push(PathSegment {
-                ident: Ident::new(ident, pm2::Span::call_site()),
-                arguments: args.clone(),
-            });
-            path
-        }
-
         let mut meta_path = meta_path.clone();
         let args = meta_path
             .segments
@@ -124,27 +647,35 @@ impl BaseClass {
             .unwrap_or_default();
 
         Self {
-            data_path: append_path(&meta_path, "Data", &args),
-            inherit_trait_path: append_path(&meta_path, "InheritTrait", &args),
-            vmt_parts_trait_path: append_path(&meta_path, "HasVmtParts", &args),
-            meta_path,
+            data_path: helpers::append_path(&meta_path, "Data", &args),
+            inherit_trait_path: helpers::append_path(&meta_path, "InheritTrait", &args),
+            vmt_parts_trait_path: helpers::append_path(&meta_path, "HasVmtParts", &args),
         }
     }
 }
 
+/// A slot in the part of the vtable introduced by a class.
+enum VmtSlot<'a> {
+    Fn(&'a VmtFn),
+    /// Unbound slots, with an expression evaluating to their count.
+    Gap(Ident, pm2::TokenStream),
+}
+
 struct ClassInfo {
     vis: Visibility,
     name: Ident,
     name_with_args: Path,
     generics: Generics,
     unbounded_generics: Generics,
-    generic_args: PathArguments,
-    bases: Vec<BaseClass>,
+    base: Option<BaseClass>,
     methods: Vec<VmtFn>,
+    non_virtual: Vec<VmtFn>,
+    constructors: Vec<Constructor>,
+    panic: PanicPolicy,
 }
 
 impl ClassInfo {
-    fn new(trait_def: ItemTrait) -> Self {
+    fn new(mut trait_def: ItemTrait, panic: PanicPolicy) -> Self {
         if trait_def.auto_token.is_some() {
             abort!(trait_def.auto_token, "class vtable cannot be auto")
         }
@@ -152,11 +683,20 @@ impl ClassInfo {
             abort!(trait_def.unsafety, "class vtable cannot be unsafe")
         }
 
-        let generics = trait_def.generics.clone();
-        generics.params.iter().for_each(|p| match p {
-            GenericParam::Lifetime(_) => emit_error!(p, "class cannot be generic over lifetime"),
-            _ => (),
-        });
+        let mut generics = trait_def.generics.clone();
+        for param in generics.params.iter_mut() {
+            match param {
+                GenericParam::Lifetime(_) => {
+                    emit_error!(param, "class cannot be generic over lifetime")
+                }
+                // Class types must be 'static to be identified by their TypeId
+                GenericParam::Type(t) => {
+                    t.colon_token.get_or_insert_with(Default::default);
+                    t.bounds.push(parse_quote!('static));
+                }
+                GenericParam::Const(_) => (),
+            }
+        }
 
         let generic_args = helpers::generics_to_path_args(&generics);
 
@@ -165,23 +705,72 @@ impl ClassInfo {
         let mut name_with_args = Path::from(name.clone());
         name_with_args.segments.last_mut().unwrap().arguments = generic_args.clone();
 
-        let bases: Vec<_> = trait_def
-            .supertraits
-            .iter()
-            .filter_map(|bound| match bound {
-                TypeParamBound::Trait(tr) => Some(BaseClass::from_meta_path(&tr.path)),
-                TypeParamBound::Lifetime(lt) => {
-                    emit_error!(
-                        lt,
-                        "Bases must be defined using class meta modules, e.g. MyClass_Meta"
-                    );
-                    None
-                }
-                _ => abort_call_site!(
+        let mut bases = trait_def.supertraits.iter().filter_map(|bound| match bound {
+            TypeParamBound::Trait(tr) => Some((tr, BaseClass::from_meta_path(&tr.path))),
+            TypeParamBound::Lifetime(lt) => {
+                emit_error!(
+                    lt,
                     "Bases must be defined using class meta modules, e.g. MyClass_Meta"
-                ),
+                );
+                None
+            }
+            _ => abort_call_site!(
+                "Bases must be defined using class meta modules, e.g. MyClass_Meta"
+            ),
+        });
+
+        let base = bases.next().map(|(_, base)| base);
+        for (tr, _) in bases {
+            emit_error!(tr, "class can only have a single base");
+        }
+
+        let constructors = Constructor::take_from_trait_def(&mut trait_def);
+        let (non_virtual, methods): (Vec<_>, Vec<_>) = VmtFn::from_trait_def(&trait_def)
+            .map(|method| VmtFn {
+                abi: panic.abi(),
+                ..method
             })
-            .collect();
+            .partition(|method| method.address.is_some());
+
+        // Resolved addresses are cached in a static, which is shared by all the instantiations
+        if generics.type_params().next().is_some() || generics.const_params().next().is_some() {
+            let sigs = non_virtual.iter().map(|method| &method.fun.sig);
+            for sig in sigs.chain(constructors.iter().map(|ctor| &ctor.fun.sig)) {
+                emit_error!(
+                    sig,
+                    "generic classes cannot bind functions to an address, as the resolved \
+                     address would be shared by all their instantiations"
+                );
+            }
+        }
+
+        // The destructors of Rust classes only drop their layout, so they would skip the C++
+        // destructor of a base built by a constructor
+        if methods.iter().any(|method| method.destructor) {
+            for ctor in &constructors {
+                emit_error!(
+                    ctor.fun.sig,
+                    "#[constructor] is not supported on classes with a virtual destructor, which \
+                     Rust classes deriving from them would not run";
+                    help = "the C++ destructor of a base built by a constructor must be trivial"
+                );
+            }
+        }
+
+        // Thunks of the `catch` policy return the default value of the return type on panic
+        if panic == PanicPolicy::Catch {
+            for method in &methods {
+                match &method.explicit_sig.output {
+                    ReturnType::Type(_, ty) if helpers::is_pointer_like(ty) => emit_error!(
+                        ty,
+                        "methods of classes with the \"catch\" panic policy must return a type \
+                         implementing `Default`, which references and pointers do not";
+                        help = "use the \"abort\" or \"unwind\" panic policy for this class"
+                    ),
+                    _ => (),
+                }
+            }
+        }
 
         Self {
             vis: trait_def.vis.clone(),
@@ -189,59 +778,1519 @@ impl ClassInfo {
             name_with_args,
             unbounded_generics: helpers::unbounded_generics(&generics),
             generics,
-            generic_args,
-            bases,
-            methods: VmtFn::from_trait_def(&trait_def).collect(),
+            base,
+            methods,
+            non_virtual,
+            constructors,
+            panic,
+        }
+    }
+
+    fn suffixed(&self, suffix: &str) -> Ident {
+        format_ident!("{}{}", self.name, suffix)
+    }
+
+    fn params(&self) -> impl Iterator<Item = &GenericParam> {
+        self.generics.params.iter()
+    }
+
+    fn predicates(&self) -> impl Iterator<Item = &WherePredicate> {
+        self.generics.where_clause.iter().flat_map(|w| w.predicates.iter())
+    }
+
+    fn args(&self) -> Vec<&Ident> {
+        self.params()
+            .filter_map(|p| match p {
+                GenericParam::Type(t) => Some(&t.ident),
+                GenericParam::Const(c) => Some(&c.ident),
+                GenericParam::Lifetime(_) => None,
+            })
+            .collect()
+    }
+
+    /// Marker field used by structs which don't otherwise use the type parameters of the class,
+    /// along with its initializer.
+    fn marker_field(&self) -> (pm2::TokenStream, pm2::TokenStream) {
+        let type_args: Vec<_> = self.generics.type_params().map(|t| &t.ident).collect();
+        if type_args.is_empty() {
+            Default::default()
+        }
+        else {
+            (
+                quote!(_marker: ::core::marker::PhantomData<fn() -> (#(#type_args,)*)>,),
+                quote!(_marker: ::core::marker::PhantomData,),
+            )
         }
     }
+
+    /// Virtual methods in the order of their slots with the MSVC ABI, which groups overloads at
+    /// the position of the first one in reverse declaration order.
+    fn msvc_method_order(&self) -> Vec<&VmtFn> {
+        let mut order: Vec<&VmtFn> = Vec::with_capacity(self.methods.len());
+        for method in &self.methods {
+            if order.iter().any(|m| m.cpp_name == method.cpp_name && !m.destructor) {
+                continue;
+            }
+            let overloads = self.methods.iter().filter(|m| m.cpp_name == method.cpp_name);
+            match method.destructor {
+                true => order.push(method),
+                false => order.extend(overloads.filter(|m| !m.destructor).rev()),
+            }
+        }
+        order
+    }
+
+    /// Slots of the vtable part of the class with the given ABI. Reordered overloads take the
+    /// slot indices of the methods they are swapped with.
+    fn vmt_slots(&self, msvc: bool) -> Vec<VmtSlot<'_>> {
+        let order = match msvc {
+            true => self.msvc_method_order(),
+            false => self.methods.iter().collect(),
+        };
+        let base_slots = match &self.base {
+            Some(base) => {
+                let base_data = &base.data_path;
+                quote! {
+                    ::core::mem::size_of::<<#base_data as ::bridgeless::Class>::Vmt>()
+                        / ::core::mem::size_of::<usize>()
+                }
+            }
+            None => quote!(0),
+        };
+
+        let mut slots = Vec::new();
+        // Index of the next slot, if it is known without evaluating constants
+        let mut next = Some(0);
+        let mut next_expr = quote!(0);
+        for (method, declared) in order.into_iter().zip(&self.methods) {
+            let gap_len = match declared.offset {
+                None => None,
+                Some(SlotOffset::Index(index)) => match next {
+                    Some(next) if index > next => Some(quote!(#index - #next)),
+                    Some(_) => None,
+                    None => Some(quote!(::bridgeless::internal::gap_len(#index, #next_expr))),
+                },
+                Some(SlotOffset::Bytes(bytes, span)) => Some(quote_spanned! {span=>
+                    ::bridgeless::internal::gap_len(
+                        ::bridgeless::internal::byte_offset_slot(#bytes, #base_slots),
+                        #next_expr,
+                    )
+                }),
+            };
+            // Named gaps are declared even when empty
+            let gap_len = gap_len.or_else(|| declared.gap.as_ref().map(|_| quote!(0)));
+            if let Some(len) = gap_len {
+                let gap_count = slots.iter().filter(|s| matches!(s, VmtSlot::Gap(..))).count();
+                let ident =
+                    declared.gap.clone().unwrap_or_else(|| format_ident!("_gap_{}", gap_count));
+                slots.push(VmtSlot::Gap(ident, len));
+            }
+            slots.push(VmtSlot::Fn(method));
+
+            let (start, start_expr) = match declared.offset {
+                None => (next, next_expr),
+                Some(SlotOffset::Index(index)) => (Some(index), quote!(#index)),
+                Some(SlotOffset::Bytes(bytes, span)) => (
+                    None,
+                    quote_spanned!(span=> ::bridgeless::internal::byte_offset_slot(#bytes, #base_slots)),
+                ),
+            };
+            // The number of slots of destructors depends on the target
+            (next, next_expr) = match method.destructor {
+                true => (None, quote!(#start_expr + ::bridgeless::Destructor::SLOTS)),
+                false => (start.map(|start| start + 1), quote!(#start_expr + 1)),
+            };
+        }
+        slots
+    }
+
+    /// Maps the slots of the vtable part of the class, along with whether they are mapped for the
+    /// MSVC ABI. If overloads are ordered differently or slot types differ with the MSVC ABI, both
+    /// ABIs are mapped and gated on the target environment.
+    fn map_vmt_slots(
+        &self,
+        f: impl Fn(&VmtSlot, bool) -> pm2::TokenStream,
+    ) -> Vec<pm2::TokenStream> {
+        let reordered = self
+            .msvc_method_order()
+            .into_iter()
+            .zip(&self.methods)
+            .any(|(a, b)| !std::ptr::eq(a, b));
+        let sret = self.methods.iter().any(|m| m.sret != Sret::No);
+
+        if !reordered && !sret {
+            return self.vmt_slots(false).iter().map(|slot| f(slot, false)).collect();
+        }
+        let itanium = self.vmt_slots(false).into_iter().map(|slot| {
+            let tokens = f(&slot, false);
+            quote!(#[cfg(not(target_env = "msvc"))] #tokens)
+        });
+        let msvc = self.vmt_slots(true).into_iter().map(|slot| {
+            let tokens = f(&slot, true);
+            quote!(#[cfg(target_env = "msvc")] #tokens)
+        });
+        itanium.chain(msvc).collect()
+    }
 }
 
+/// Attribute proc macro declaring a C++ class with single inheritance from a trait listing its
+/// virtual methods.
+///
+/// The trait must have the same name and visibility as the class's data struct, which must be
+/// `#[repr(C)]`.
+/// The base class, if any, is declared using its meta module as a supertrait. Methods can be
+/// placed at a specific vtable index using `#[offset(n)]`, counted from the first slot of the
+/// class, or at a byte offset from the start of the vtable as reported by reverse engineering
+/// tools using `#[offset(bytes = 0x48)]`. The slots skipped this way become a public gap field of
+/// `bridgeless::OpaqueFn`s in the vtable struct, which can be named with
+/// `#[offset(n, gap = name)]`.
+///
+/// Overloaded C++ methods are declared with distinct Rust names and the same
+/// `#[cpp_name = "name"]`. With the MSVC ABI, overloads are grouped at the slot of the first one
+/// in reverse declaration order, and the vtable is laid out accordingly.
+///
+/// The vtable struct has associated constants with the index of the slot of each method in the
+/// main vtable and its C++ name, e.g. `ShapeVmt::AREA_SLOT` and `ShapeVmt::AREA_NAME`. Its
+/// `typed` and `typed_mut` methods view its slots as function pointers taking `this` as a
+/// `bridgeless::CRef` (or `CRefMut`) to the class instead of a `u8` reference.
+///
+/// Methods with a body are the class's own implementations, while methods without one are pure
+/// virtual from the point of view of Rust. Derived classes override them using [`class_impl`].
+///
+/// Non-virtual methods are declared without a body and bound to a function of the process using
+/// one of the following attributes. They do not take a vtable slot, and their address is resolved
+/// on first call:
+/// - `#[rva(0x1234)]`: address relative to the base of the main executable module;
+/// - `#[symbol("_ZNK5Shape4nameEv")]`: exported symbol name;
+/// - `#[address(source)]`: any `bridgeless::address::AddressSource`, such as a closure.
+///
+/// Methods returning a C++ class by value must be marked `#[sret]`, as C++ member functions do not
+/// return them like C functions with the MSVC ABI: the class is always written through a hidden
+/// pointer passed after `this`. Classes which are not trivially copyable in C++ are also returned
+/// through a hidden pointer with the Itanium ABI, passed before `this`, which requires
+/// `#[sret(non_trivial)]`.
+///
+/// Methods taking C variadic arguments are declared with a trailing `_: ...` and cannot have a
+/// body, as defining variadic functions is unstable. Instead of calling them, their method returns
+/// a `bridgeless::Variadic` binding the function pointer to the instance. Rust classes cannot
+/// implement them either: their slot is left null, and calling them on a Rust instance panics.
+///
+/// A virtual destructor is declared with `#[destructor] fn destructor(&mut self);`. It takes the
+/// destructor's slots of the target's C++ ABI (`bridgeless::Destructor`) and is called when a `Cls`
+/// is dropped. Classes instantiated from Rust get a destructor which drops their layout, so it
+/// cannot have a body.
+///
+/// Each class also gets a `pin_init` function returning a `bridgeless::Init`, to construct
+/// instances at their final address with `bridgeless::Cls::emplace`.
+///
+/// C++ constructors are declared without a receiver nor a body, marked `#[constructor]` and bound
+/// to an address like non-virtual methods. Each one becomes a function of the data struct returning
+/// a `bridgeless::Init` which runs the constructor, and can be used as the base initializer passed
+/// to the `pin_init` of a Rust class deriving from it. With the Itanium ABI, bind the base object
+/// constructor (`C2`). The vtable pointer it writes is then replaced by the derived one.
+///
+/// The destructor of a Rust class only drops its layout and never calls the C++ destructor of its
+/// bases, so classes built by a constructor must have a trivial destructor: their C++ resources
+/// would otherwise leak. Declaring `#[constructor]` in a class with a virtual destructor is an
+/// error, but non-virtual destructors are not known to the bindings.
+///
+/// A panic in the Rust implementation of a method cannot unwind through its C++ caller. What the
+/// thunks of the methods declared by a class do instead is set with `#[class(panic = "...")]`:
+/// - `"abort"` (the default): abort the process, naming the method which panicked;
+/// - `"catch"`: catch the panic and return the default value of the return type, which requires the
+///   `std` feature of `bridgeless`. All the methods must then return a type implementing `Default`,
+///   which excludes references, raw pointers, `NonNull`, `CRef` and `CRefMut`;
+/// - `"unwind"`: unwind into the caller as a foreign exception, using the `C-unwind` ABI for the
+///   functions of the vtable.
+///
+/// The `"unwind"` policy also lets C++ exceptions thrown by the C++ implementations of the methods
+/// unwind into their Rust callers, which can catch them with `bridgeless::exception::try_call`.
+///
+/// Generic classes are monomorphized per set of generic arguments, just like C++ class templates.
+/// Their non-virtual methods and constructors cannot be bound to an address, which is resolved
+/// once for all the instantiations.
 #[proc_macro_error]
 #[proc_macro_attribute]
 pub fn class(attr: TokenStream, item: TokenStream) -> TokenStream {
-    if !attr.is_empty() {
-        abort_call_site!("class macro does not take any arguments");
-    }
-
-    let class = ClassInfo::new(parse_macro_input!(item));
+    let panic = PanicPolicy::from_args(attr);
+    let class = ClassInfo::new(parse_macro_input!(item), panic);
 
     let mut stream = pm2::TokenStream::new();
     stream.extend(generate_meta(&class));
+    stream.extend(generate_impl_trait(&class));
+    stream.extend(generate_vmt(&class));
+    stream.extend(generate_layout(&class));
+    stream.extend(generate_class(&class));
+    stream.extend(generate_vmt_gen(&class));
+    stream.extend(generate_init(&class));
 
     stream.into()
 }
 
 fn generate_meta(class: &ClassInfo) -> pm2::TokenStream {
     let vis = &class.vis;
-    let name = &class.name;
     let generics = &class.generics;
     let unbounded_generics = &class.unbounded_generics;
+    let unbounded_params = unbounded_generics.params.iter();
     let name_with_args = &class.name_with_args;
+    let args = class.args();
+    let thunk_gen_ident = class.suffixed("ThunkGen");
 
-    let meta_ident = Ident::new(&(class.name.to_string() + "_Meta"), pm2::Span::call_site());
+    let meta_ident = class.suffixed("_Meta");
 
-    let inherit_bounds = class.bases.iter().map(|base| &base.inherit_trait_path);
-    let base_vmt_parts = class.bases.iter().map(|base| &base.vmt_parts_trait_path);
+    let inherit_bounds = class.base.iter().map(|base| &base.inherit_trait_path);
+    let base_vmt_parts = class.base.iter().map(|base| &base.vmt_parts_trait_path);
 
     let vmt_part_where_bounds = quote! {
         ::bridgeless::internal::VmtPartGen<#name_with_args>
-                #(+ ::bridgeless::internal::VmtPartGen<#base_vmt_parts>)*
+                #(+ #base_vmt_parts)*
     };
 
     let generic_params = &generics.params;
     let generic_predicates = generics.where_clause.as_ref().map(|w| &w.predicates);
 
-    return quote! {
+    quote! {
         #[allow(non_snake_case)]
         #vis mod #meta_ident {
-            use super::#name;
+            use super::*;
             pub type Cls #unbounded_generics = #name_with_args;
+            pub type Data #unbounded_generics = #name_with_args;
+            #[doc(hidden)]
+            pub type ThunkGen<#(#unbounded_params,)* _bridgeless_C, Ofs> =
+                #thunk_gen_ident<#(#args,)* _bridgeless_C, Ofs>;
             pub trait InheritTrait #generics: #(#inherit_bounds)+* {}
             pub trait HasVmtParts #generics: #vmt_part_where_bounds {}
-            impl<_bridgeless_T, #generic_params> HasVmtParts #unbounded_generics for _bridgeless_T where
+            impl<_bridgeless_T, #generic_params> HasVmtParts<#(#args),*> for _bridgeless_T where
                 _bridgeless_T: #vmt_part_where_bounds, #generic_predicates {}
         }
+    }
+}
+
+fn generate_impl_trait(class: &ClassInfo) -> pm2::TokenStream {
+    let vis = &class.vis;
+    let ty = &class.name_with_args;
+    let params = class.params();
+    let args = class.args();
+    let predicates: Vec<_> = class.predicates().collect();
+    let impl_trait = class.suffixed("_Impl");
+    let combined_vmt = class.suffixed("CombinedVmt");
+    let part_index = Index::from(class.base.is_some() as usize);
+
+    let methods = class.methods.iter().filter(|method| !method.destructor).map(|method| {
+        let attrs = &method.fun.attrs;
+        let ident = method.ident();
+        let overrides = method.overrides_ident();
+        let (ptr_cast, this) = match method.receiver_mutability {
+            Some(_) => (quote!(self as *mut Self as *mut u8), quote!(&mut *_bridgeless_ptr)),
+            None => (quote!(self as *const Self as *const u8), quote!(&*_bridgeless_ptr)),
+        };
+        let (sig, call) = match method.variadic {
+            true => (
+                method.variadic_sig(),
+                quote!(::bridgeless::Variadic { this: #this, fun: _bridgeless_fn }),
+            ),
+            false => (method.named_sig(), method.call(&this)),
+        };
+        // Slots of variadic methods are left empty in the vtables of Rust classes
+        let unwrap = match method.variadic {
+            true => {
+                let msg = format!("Rust classes cannot implement the variadic method {ident}");
+                quote!(expect(#msg))
+            }
+            false => quote!(unwrap_unchecked()),
+        };
+
+        quote! {
+            #[doc(hidden)]
+            #[allow(non_upper_case_globals)]
+            const #overrides: bool = false;
+
+            #(#attrs)*
+            #sig {
+                let _bridgeless_ofs = <<Self as ::bridgeless::internal::ClassWrapper>::ClsType
+                    as ::bridgeless::Class>::base_offset::<#ty>()
+                    .expect("Unreachable code ran");
+                unsafe {
+                    let _bridgeless_ptr = (#ptr_cast).add(_bridgeless_ofs);
+                    let _bridgeless_fn = (*(_bridgeless_ptr as *const &'static #combined_vmt<#(#args),*>))
+                        .#part_index
+                        .#ident
+                        .#unwrap;
+                    #call
+                }
+            }
+        }
+    });
+
+    let non_virtual = class.non_virtual.iter().map(|method| {
+        let attrs = &method.fun.attrs;
+        let source = &method.address;
+        let name = format!("{}::{}", class.name, method.ident());
+        let resolve = quote!(_bridgeless_ADDRESS.get_or_resolve(#source, #name));
+        let fn_decl = match method.sret {
+            Sret::No => {
+                let bare_fn = method.bare_fn(false);
+                quote!(let _bridgeless_fn: #bare_fn = ::core::mem::transmute(#resolve);)
+            }
+            _ => {
+                let (itanium, msvc) = (method.bare_fn(false), method.bare_fn(true));
+                quote! {
+                    #[cfg(not(target_env = "msvc"))]
+                    let _bridgeless_fn: #itanium = ::core::mem::transmute(#resolve);
+                    #[cfg(target_env = "msvc")]
+                    let _bridgeless_fn: #msvc = ::core::mem::transmute(#resolve);
+                }
+            }
+        };
+        let (ptr_cast, this) = match method.receiver_mutability {
+            Some(_) => (
+                quote!(self as *mut Self as *mut u8),
+                quote!(&mut *_bridgeless_ptr),
+            ),
+            None => (
+                quote!(self as *const Self as *const u8),
+                quote!(&*_bridgeless_ptr),
+            ),
+        };
+        let (sig, call) = match method.variadic {
+            true => (
+                method.variadic_sig(),
+                quote!(::bridgeless::Variadic { this: #this, fun: _bridgeless_fn }),
+            ),
+            false => (method.named_sig(), method.call(&this)),
+        };
+
+        quote! {
+            #(#attrs)*
+            #sig {
+                static _bridgeless_ADDRESS: ::bridgeless::address::LazyAddress =
+                    ::bridgeless::address::LazyAddress::new();
+
+                let _bridgeless_ofs = <<Self as ::bridgeless::internal::ClassWrapper>::ClsType
+                    as ::bridgeless::Class>::base_offset::<#ty>()
+                    .expect("Unreachable code ran");
+                unsafe {
+                    let _bridgeless_ptr = (#ptr_cast).add(_bridgeless_ofs);
+                    #fn_decl
+                    #call
+                }
+            }
+        }
+    });
+
+    let own_impls = class.methods.iter().filter(|method| !method.variadic).filter_map(|method| {
+        let block = method.fun.default.as_ref()?;
+        let overrides = method.overrides_ident();
+        let sig = &method.fun.sig;
+        Some(quote! {
+            const #overrides: bool = true;
+            #sig #block
+        })
+    });
+
+    let params: Vec<_> = params.collect();
+    quote! {
+        #[allow(non_camel_case_types)]
+        #vis trait #impl_trait<#(#params),*>: 'static + ::bridgeless::internal::ClassWrapper
+        where #(#predicates,)*
+        {
+            #(#methods)*
+            #(#non_virtual)*
+        }
+
+        impl<#(#params),*> #impl_trait<#(#args),*> for ::bridgeless::Impl<#ty>
+        where #(#predicates,)*
+        {
+            #(#own_impls)*
+        }
+
+        impl<#(#params,)* _bridgeless_C: ::bridgeless::SubclassOf<#ty>> #impl_trait<#(#args),*>
+            for ::bridgeless::DynCls<_bridgeless_C>
+        where #(#predicates,)*
+        {}
+
+        impl<#(#params,)* _bridgeless_C: ::bridgeless::SubclassOf<#ty>> #impl_trait<#(#args),*>
+            for ::bridgeless::Cls<_bridgeless_C>
+        where #(#predicates,)*
+        {}
+    }
+}
+
+fn generate_vmt(class: &ClassInfo) -> pm2::TokenStream {
+    let vis = &class.vis;
+    let name = &class.name;
+    let params: Vec<_> = class.params().collect();
+    let args = class.args();
+    let predicates: Vec<_> = class.predicates().collect();
+    let vmt = class.suffixed("Vmt");
+    let vmt_typed = class.suffixed("VmtTyped");
+    let combined_vmt = class.suffixed("CombinedVmt");
+    let (marker_decl, marker_init) = class.marker_field();
+    let ty = &class.name_with_args;
+
+    let field_decls = class.map_vmt_slots(|slot, msvc| match slot {
+        VmtSlot::Fn(method) => {
+            let ident = method.ident();
+            let slot_type = method.slot_type(msvc);
+            quote!(pub #ident: #slot_type)
+        }
+        VmtSlot::Gap(ident, len) => quote!(pub #ident: [::bridgeless::OpaqueFn; #len]),
+    });
+    let typed_field_decls = class.map_vmt_slots(|slot, msvc| match slot {
+        VmtSlot::Fn(method) if method.destructor => {
+            let ident = method.ident();
+            quote!(pub #ident: ::bridgeless::Destructor)
+        }
+        VmtSlot::Fn(method) => {
+            let ident = method.ident();
+            let typed_fn = method.typed_bare_fn(msvc, ty);
+            quote!(pub #ident: ::core::option::Option<#typed_fn>)
+        }
+        VmtSlot::Gap(ident, len) => quote!(pub #ident: [::bridgeless::OpaqueFn; #len]),
+    });
+    let base_slots = match &class.base {
+        None => quote!(0),
+        Some(base) => {
+            let base_data = &base.data_path;
+            quote!(<<#base_data as ::bridgeless::Class>::Vmt as ::bridgeless::VmtSlots>::SLOT_COUNT)
+        }
     };
+    // `offset_of` gives the slots of both ABIs
+    let vmt_slots = class.vmt_slots(false);
+    let slot_consts = vmt_slots.iter().map(|slot| match slot {
+        VmtSlot::Fn(method) => {
+            let ident = method.ident();
+            let upper = ident.unraw().to_string().to_uppercase();
+            let slot_const = format_ident!("{upper}_SLOT");
+            let name_const = format_ident!("{upper}_NAME");
+            let slot_doc = format!(
+                "Index of the (first) slot of `{}` in the main vtable of the class.",
+                ident.unraw()
+            );
+            let name_doc = format!("Name of `{}` in C++.", ident.unraw());
+            let cpp_name = &method.cpp_name;
+            quote! {
+                #[doc = #slot_doc]
+                pub const #slot_const: usize = #base_slots
+                    + ::core::mem::offset_of!(Self, #ident) / ::core::mem::size_of::<usize>();
+                #[doc = #name_doc]
+                pub const #name_const: &'static str = #cpp_name;
+            }
+        }
+        VmtSlot::Gap(..) => quote!(),
+    });
+    let field_defaults = class.map_vmt_slots(|slot, _| match slot {
+        VmtSlot::Fn(method) if method.destructor => {
+            let ident = method.ident();
+            quote!(#ident: ::bridgeless::Destructor::NONE)
+        }
+        VmtSlot::Fn(method) => {
+            let ident = method.ident();
+            quote!(#ident: ::core::option::Option::None)
+        }
+        VmtSlot::Gap(ident, len) => quote!(#ident: [::bridgeless::OpaqueFn::NONE; #len]),
+    });
+    let debug_fields = class.map_vmt_slots(|slot, _| match slot {
+        VmtSlot::Fn(method) if method.destructor => {
+            let ident = method.ident();
+            let name = ident.to_string();
+            quote!(debug.field(#name, &self.#ident);)
+        }
+        VmtSlot::Fn(method) => {
+            let ident = method.ident();
+            let name = ident.to_string();
+            quote!(debug.field(#name, &::bridgeless::internal::DebugSlot::of(&self.#ident));)
+        }
+        VmtSlot::Gap(ident, _) => {
+            let name = ident.to_string();
+            quote!(debug.field(#name, &self.#ident);)
+        }
+    });
+    let vmt_name = vmt.to_string();
+    let combined_vmt_name = combined_vmt.to_string();
+    let asserts = class.methods.iter().filter(|method| !method.variadic).map(|method| {
+        let ident = method.ident();
+        let msg = format!("Can't generate vtable for {name}: missing impl for {ident}");
+        quote!(self.#ident.expect(#msg);)
+    });
+    let implemented = class.methods.iter().filter(|method| !method.variadic).map(|method| {
+        let ident = method.ident();
+        quote!(&& self.#ident.is_some())
+    });
+
+    let base_vmt = class.base.iter().map(|base| {
+        let base_data = &base.data_path;
+        quote!(pub <#base_data as ::bridgeless::Class>::Vmt,)
+    });
+    let (debug_base, debug_base_predicate) = match &class.base {
+        None => (quote!(), quote!()),
+        Some(base) => {
+            let base_data = &base.data_path;
+            (
+                quote!(.field(&self.0)),
+                quote!(<#base_data as ::bridgeless::Class>::Vmt: ::core::fmt::Debug,),
+            )
+        }
+    };
+    let own_vmt_index = Index::from(class.base.is_some() as usize);
+
+    quote! {
+        #[repr(C)]
+        #vis struct #vmt<#(#params),*> where #(#predicates,)* {
+            #(#field_decls,)*
+            #marker_decl
+        }
+
+        impl<#(#params),*> ::core::clone::Clone for #vmt<#(#args),*> where #(#predicates,)* {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+        impl<#(#params),*> ::core::marker::Copy for #vmt<#(#args),*> where #(#predicates,)* {}
+
+        impl<#(#params),*> ::core::fmt::Debug for #vmt<#(#args),*> where #(#predicates,)* {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                let mut debug = f.debug_struct(#vmt_name);
+                #(#debug_fields)*
+                debug.finish()
+            }
+        }
+
+        /// View of the slots of the vtable with function pointers taking `this` as a `CRef` to
+        /// the class.
+        #[repr(C)]
+        #vis struct #vmt_typed<#(#params),*> where #(#predicates,)* {
+            #(#typed_field_decls,)*
+            #marker_decl
+        }
+
+        impl<#(#params),*> ::core::clone::Clone for #vmt_typed<#(#args),*> where #(#predicates,)* {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+        impl<#(#params),*> ::core::marker::Copy for #vmt_typed<#(#args),*> where #(#predicates,)* {}
+
+        impl<#(#params),*> #vmt<#(#args),*> where #(#predicates,)* {
+            #(#slot_consts)*
+
+            /// Returns the slots of the vtable with their typed signatures.
+            pub fn typed(&self) -> &#vmt_typed<#(#args),*> {
+                // SAFETY: `CRef` has the same ABI as the `this` pointer of the slots
+                unsafe { &*(self as *const Self as *const #vmt_typed<#(#args),*>) }
+            }
+
+            /// Returns the slots of the vtable with their typed signatures, which can be set to
+            /// functions taking `this` as a `CRef` to the class.
+            pub fn typed_mut(&mut self) -> &mut #vmt_typed<#(#args),*> {
+                // SAFETY: `CRef` has the same ABI as the `this` pointer of the slots
+                unsafe { &mut *(self as *mut Self as *mut #vmt_typed<#(#args),*>) }
+            }
+
+            pub const fn default() -> Self {
+                Self {
+                    #(#field_defaults,)*
+                    #marker_init
+                }
+            }
+
+            pub const fn assert_implemented(&self) {
+                #(#asserts)*
+            }
+
+            pub const fn is_implemented(&self) -> bool {
+                true #(#implemented)*
+            }
+        }
+
+        #[repr(C)]
+        #vis struct #combined_vmt<#(#params),*>(#(#base_vmt)* pub #vmt<#(#args),*>)
+        where #(#predicates,)*;
+
+        impl<#(#params),*> ::core::clone::Clone for #combined_vmt<#(#args),*> where #(#predicates,)* {
+            fn clone(&self) -> Self {
+                *self
+            }
+        }
+        impl<#(#params),*> ::core::marker::Copy for #combined_vmt<#(#args),*> where #(#predicates,)* {}
+
+        unsafe impl<#(#params),*> ::bridgeless::VmtSlots for #combined_vmt<#(#args),*>
+        where #(#predicates,)* {}
+
+        impl<#(#params),*> ::core::fmt::Debug for #combined_vmt<#(#args),*>
+        where #(#predicates,)* #debug_base_predicate
+        {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_tuple(#combined_vmt_name) #debug_base .field(&self.#own_vmt_index).finish()
+            }
+        }
+    }
+}
+
+fn generate_layout(class: &ClassInfo) -> pm2::TokenStream {
+    let vis = &class.vis;
+    let ty = &class.name_with_args;
+    let params: Vec<_> = class.params().collect();
+    let args = class.args();
+    let predicates: Vec<_> = class.predicates().collect();
+    let layout = class.suffixed("Layout");
+
+    let layout_name = layout.to_string();
+    let (debug_base, debug_base_predicate) = match &class.base {
+        None => (quote!(), quote!()),
+        Some(base) => {
+            let base_data = &base.data_path;
+            (
+                quote!(.field("base", &self.0)),
+                quote!(<#base_data as ::bridgeless::Class>::Layout<VPtr>: ::core::fmt::Debug,),
+            )
+        }
+    };
+
+    let (first_field, replace_first, vtable, vtable_mut) = match &class.base {
+        None => (
+            quote!(VPtr),
+            quote!(new_vptr),
+            quote!(self.0),
+            quote!(&mut self.0),
+        ),
+        Some(base) => {
+            let base_data = &base.data_path;
+            (
+                quote!(<#base_data as ::bridgeless::Class>::Layout<VPtr>),
+                quote!(self.0.replace_vptr(new_vptr)),
+                quote!(self.0.vtable()),
+                quote!(unsafe { self.0.vtable_mut() }),
+            )
+        }
+    };
+
+    quote! {
+        #[repr(C)]
+        #vis struct #layout<#(#params,)* VPtr: 'static + Copy>(pub #first_field, pub #ty)
+        where #(#predicates,)*;
+
+        impl<#(#params,)* VPtr: 'static + Copy> #layout<#(#args,)* VPtr> where #(#predicates,)* {
+            pub fn replace_vptr<_bridgeless_V: 'static + Copy>(
+                self,
+                new_vptr: _bridgeless_V,
+            ) -> #layout<#(#args,)* _bridgeless_V> {
+                #layout(#replace_first, self.1)
+            }
+        }
+
+        impl<#(#params,)* VPtr: 'static + Copy> ::bridgeless::ClassLayout<VPtr>
+            for #layout<#(#args,)* VPtr>
+        where #(#predicates,)*
+        {
+            type Data = #ty;
+
+            fn data(&self) -> &Self::Data {
+                &self.1
+            }
+            fn data_mut(&mut self) -> &mut Self::Data {
+                &mut self.1
+            }
+            fn vtable(&self) -> VPtr {
+                #vtable
+            }
+            unsafe fn vtable_mut(&mut self) -> &mut VPtr {
+                #vtable_mut
+            }
+        }
+
+        // The vtable pointer is shown by the `Debug` implementations of the class wrappers
+        impl<#(#params,)* VPtr: 'static + Copy> ::core::fmt::Debug for #layout<#(#args,)* VPtr>
+        where
+            #(#predicates,)*
+            <Self as ::bridgeless::ClassLayout<VPtr>>::Data: ::core::fmt::Debug,
+            #debug_base_predicate
+        {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.debug_struct(#layout_name) #debug_base .field("data", &self.1).finish()
+            }
+        }
+    }
+}
+
+fn generate_class(class: &ClassInfo) -> pm2::TokenStream {
+    let ty = &class.name_with_args;
+    let params: Vec<_> = class.params().collect();
+    let args = class.args();
+    let predicates: Vec<_> = class.predicates().collect();
+    let meta = class.suffixed("_Meta");
+    let combined_vmt = class.suffixed("CombinedVmt");
+    let vmt = class.suffixed("Vmt");
+    let layout = class.suffixed("Layout");
+
+    let base_offset = match &class.base {
+        None => quote!(::core::option::Option::None),
+        Some(base) => {
+            let base_data = &base.data_path;
+            quote!(<#base_data as ::bridgeless::Class>::base_offset::<_bridgeless_C>())
+        }
+    };
+
+    let part_index = Index::from(class.base.is_some() as usize);
+    let destructor = match (class.methods.iter().find(|m| m.destructor), &class.base) {
+        (Some(method), _) => {
+            let ident = method.ident();
+            quote! {
+                let destructor = vmt.#part_index.#ident;
+                destructor.is_some().then_some(destructor)
+            }
+        }
+        (None, Some(base)) => {
+            let base_data = &base.data_path;
+            quote!(<#base_data as ::bridgeless::Class>::destructor(&vmt.0))
+        }
+        (None, None) => quote!({
+            let _ = vmt;
+            ::core::option::Option::None
+        }),
+    };
+
+    quote! {
+        unsafe impl<#(#params),*> ::bridgeless::Class for #ty where #(#predicates,)* {
+            type _InheritTrait = dyn #meta::InheritTrait<#(#args),*>;
+
+            type VmtPart = #vmt<#(#args),*>;
+            type Vmt = #combined_vmt<#(#args),*>;
+            type VmtPtr = &'static #combined_vmt<#(#args),*>;
+            type Layout<VPtr: 'static + Copy> = #layout<#(#args,)* VPtr>;
+
+            #[inline(always)]
+            fn base_offset<_bridgeless_C: ::bridgeless::Class>() -> ::core::option::Option<usize> {
+                if ::core::any::TypeId::of::<_bridgeless_C>() == ::core::any::TypeId::of::<Self>() {
+                    ::core::option::Option::Some(0)
+                }
+                else {
+                    #base_offset
+                }
+            }
+
+            #[inline(always)]
+            fn destructor(
+                vmt: <Self as ::bridgeless::Class>::VmtPtr,
+            ) -> ::core::option::Option<::bridgeless::Destructor> {
+                #destructor
+            }
+        }
+
+        unsafe impl<#(#params,)* _bridgeless_C: ::bridgeless::Class>
+            ::bridgeless::internal::SubclassOf<#ty>
+            for ::bridgeless::internal::SubclassOfWrapper<_bridgeless_C>
+        where
+            <_bridgeless_C as ::bridgeless::Class>::_InheritTrait: #meta::InheritTrait<#(#args),*>,
+            #(#predicates,)*
+        {}
+    }
+}
+
+fn generate_vmt_gen(class: &ClassInfo) -> pm2::TokenStream {
+    let vis = &class.vis;
+    let ty = &class.name_with_args;
+    let params: Vec<_> = class.params().collect();
+    let args = class.args();
+    let type_args: Vec<_> = class.generics.type_params().map(|t| &t.ident).collect();
+    let predicates: Vec<_> = class.predicates().collect();
+    let meta = class.suffixed("_Meta");
+    let impl_trait = class.suffixed("_Impl");
+    let vmt = class.suffixed("Vmt");
+    let combined_vmt = class.suffixed("CombinedVmt");
+    let thunk_gen = class.suffixed("ThunkGen");
+    let fallback_gen = format_ident!("FallbackGen{}", class.name);
+    let vmt_instance = class.suffixed("VmtInstance");
+    let override_vmt_instance = class.suffixed("OverrideVmtInstance");
+    let (_, marker_init) = class.marker_field();
+
+    // Constants have no unique address, so the vtable of non-generic classes is stored in a
+    // static for instances to be recognized by the registry. Statics can't be generic nor fail to
+    // evaluate, hence the probe resolving to `None` through `NoVmt` when the class can't be
+    // instantiated, or if some of its methods are not implemented.
+    let (vmt_static, vmt_instance_value) = match class.generics.params.is_empty() {
+        true => {
+            let vmt_static = class.suffixed("_VMT");
+            let vmt_probe = class.suffixed("VmtProbe");
+            let items = quote! {
+                #[doc(hidden)]
+                #vis struct #vmt_probe;
+
+                impl #vmt_probe
+                where
+                    for<'_bridgeless_a> ::bridgeless::internal::Deferred<'_bridgeless_a, #ty>:
+                        #meta::HasVmtParts,
+                {
+                    const VMT: ::core::option::Option<#combined_vmt> = <#ty>::try_make_vmt::<
+                        ::bridgeless::internal::ConstUsizeValue<0>,
+                        ::bridgeless::internal::Deferred<'static, #ty>,
+                    >();
+                }
+
+                impl ::bridgeless::internal::NoVmt<#combined_vmt> for #vmt_probe {}
+
+                #[doc(hidden)]
+                #[allow(non_upper_case_globals)]
+                #vis static #vmt_static: ::core::option::Option<#combined_vmt> = {
+                    use ::bridgeless::internal::NoVmt as _;
+                    #vmt_probe::VMT
+                };
+            };
+            let value = quote! {
+                match &#vmt_static {
+                    ::core::option::Option::Some(vmt) => vmt,
+                    // Fails with the name of the missing method
+                    ::core::option::Option::None => &<#ty>::make_vmt::<
+                        ::bridgeless::internal::ConstUsizeValue<0>,
+                        _bridgeless_G,
+                    >(),
+                }
+            };
+            (items, value)
+        }
+        false => {
+            let value = quote! {
+                &<#ty>::make_vmt::<::bridgeless::internal::ConstUsizeValue<0>, _bridgeless_G>()
+            };
+            (quote!(), value)
+        }
+    };
+
+    let fallback_fields = class.map_vmt_slots(|slot, _| match slot {
+        VmtSlot::Fn(method) if method.destructor => {
+            let ident = method.ident();
+            quote!(#ident: _bridgeless_o.#ident.or(_bridgeless_f.#ident))
+        }
+        VmtSlot::Fn(method) => {
+            let ident = method.ident();
+            quote! {
+                #ident: match _bridgeless_o.#ident {
+                    ::core::option::Option::Some(fun) => ::core::option::Option::Some(fun),
+                    ::core::option::Option::None => _bridgeless_f.#ident,
+                }
+            }
+        }
+        VmtSlot::Gap(ident, _) => quote!(#ident: _bridgeless_o.#ident),
+    });
+
+    // Variadic methods cannot be implemented in Rust, so they don't have thunks
+    let thunks = class.methods.iter().filter(|m| !m.destructor && !m.variadic).map(|method| {
+        let ident = method.ident();
+        let lifetimes = &method.explicit_sig.lifetimes;
+        let lt = &method.explicit_sig.receiver;
+        let mutability = &method.receiver_mutability;
+        let arg_names = &method.arg_names;
+        let arg_types = &method.explicit_sig.inputs;
+        let output = &method.explicit_sig.output;
+        let (ptr_cast, from_thin_ptr, derived) = match mutability {
+            Some(_) => (
+                quote!(_bridgeless_this as *mut u8),
+                quote!(from_thin_ptr_mut),
+                quote!(&mut *),
+            ),
+            None => (
+                quote!(_bridgeless_this as *const u8),
+                quote!(from_thin_ptr),
+                quote!(&*),
+            ),
+        };
+
+        let abi = &method.abi;
+        let call = quote! {
+            <::bridgeless::Impl<_bridgeless_C> as #impl_trait<#(#args),*>>::#ident(
+                _bridgeless_derived,
+                #(#arg_names),*
+            )
+        };
+        let name = format!("{}::{}", class.name, ident);
+        let call = match class.panic {
+            PanicPolicy::Abort => quote! {{
+                let _bridgeless_guard = ::bridgeless::internal::AbortOnUnwind(#name);
+                let _bridgeless_value = #call;
+                ::core::mem::forget(_bridgeless_guard);
+                _bridgeless_value
+            }},
+            PanicPolicy::Catch => {
+                quote!(::bridgeless::internal::catch_unwind!(move || #call))
+            }
+            PanicPolicy::Unwind => call,
+        };
+
+        let thunk = |msvc: bool| {
+            let this = quote!(_bridgeless_this: &#lt #mutability u8);
+            let (hidden_args, output, write_ret) = match &method.explicit_sig.output {
+                ReturnType::Type(_, ty) if method.indirect_return(msvc) => {
+                    let ret = quote!(_bridgeless_ret: *mut #ty);
+                    let hidden_args = match msvc {
+                        true => quote!(#this, #ret),
+                        false => quote!(#ret, #this),
+                    };
+                    let write_ret = quote!(_bridgeless_ret.write(_bridgeless_value); _bridgeless_ret);
+                    (hidden_args, quote!(-> *mut #ty), write_ret)
+                }
+                _ => (this, quote!(#output), quote!(_bridgeless_value)),
+            };
+
+            quote! {
+                unsafe extern #abi fn #ident<
+                    #(#lifetimes,)*
+                    #(#params,)*
+                    _bridgeless_C: ::bridgeless::Class,
+                    Ofs: ::bridgeless::internal::HasConst<usize>,
+                >(
+                    #hidden_args,
+                    #(#arg_names: #arg_types),*
+                ) #output
+                where
+                    ::bridgeless::Impl<_bridgeless_C>: #impl_trait<#(#args),*>,
+                    #(#predicates,)*
+                {
+                    unsafe {
+                        let _bridgeless_ptr = (#ptr_cast).sub(Ofs::VALUE);
+                        let _bridgeless_derived = #derived <::bridgeless::Impl<_bridgeless_C>
+                            as ::bridgeless::internal::FromThinPtr>::#from_thin_ptr(_bridgeless_ptr);
+                        let _bridgeless_value = #call;
+                        #write_ret
+                    }
+                }
+            }
+        };
+
+        match method.sret {
+            Sret::No => thunk(false),
+            _ => {
+                let (itanium, msvc) = (thunk(false), thunk(true));
+                quote! {
+                    #[cfg(not(target_env = "msvc"))]
+                    #itanium
+                    #[cfg(target_env = "msvc")]
+                    #msvc
+                }
+            }
+        }
+    });
+
+    let thunk_fields = class.map_vmt_slots(|slot, _| match slot {
+        // Rust instances are destroyed by dropping their layout
+        VmtSlot::Fn(method) if method.destructor => {
+            let ident = method.ident();
+            quote!(#ident: ::bridgeless::internal::rust_destructor::<_bridgeless_C, Ofs>())
+        }
+        VmtSlot::Fn(method) if method.variadic => {
+            let ident = method.ident();
+            quote!(#ident: ::core::option::Option::None)
+        }
+        VmtSlot::Fn(method) => {
+            let ident = method.ident();
+            let overrides = method.overrides_ident();
+            quote! {
+                #ident: if <::bridgeless::Impl<_bridgeless_C> as #impl_trait<#(#args),*>>::#overrides {
+                    ::core::option::Option::Some(#ident::<#(#args,)* _bridgeless_C, Ofs>)
+                }
+                else {
+                    ::core::option::Option::None
+                }
+            }
+        }
+        VmtSlot::Gap(ident, len) => quote!(#ident: [::bridgeless::OpaqueFn::NONE; #len]),
+    });
+
+    // C++ destructors are kept when overriding the methods of the vtable of C++ instances
+    let keep_destructors = class.methods.iter().filter(|m| m.destructor).map(|method| {
+        let ident = method.ident();
+        quote! {
+            if !RUST_DESTRUCTORS {
+                vmt.#ident = ::bridgeless::Destructor::NONE;
+            }
+        }
+    });
+
+    let (make_vmt_bounds, base_vmt, base_try_vmt, base_override_vmt) = match &class.base {
+        None => Default::default(),
+        Some(base) => {
+            let base_data = &base.data_path;
+            let base_vmt_parts = &base.vmt_parts_trait_path;
+            (
+                quote! {
+                    for<'_bridgeless_a> ::bridgeless::internal::Deferred<'_bridgeless_a, #ty>:
+                        #base_vmt_parts,
+                },
+                quote! {
+                    <#base_data>::make_vmt::<
+                        ::bridgeless::internal::AddConst<Ofs, 0>,
+                        ::bridgeless::internal::FallbackVmtGen<
+                            _bridgeless_G,
+                            ::bridgeless::internal::Deferred<'static, #ty>,
+                        >,
+                    >(),
+                },
+                quote! {
+                    match <#base_data>::try_make_vmt::<
+                        ::bridgeless::internal::AddConst<Ofs, 0>,
+                        ::bridgeless::internal::FallbackVmtGen<
+                            _bridgeless_G,
+                            ::bridgeless::internal::Deferred<'static, #ty>,
+                        >,
+                    >() {
+                        ::core::option::Option::Some(base) => base,
+                        ::core::option::Option::None => return ::core::option::Option::None,
+                    },
+                },
+                quote! {
+                    <#base_data>::make_override_vmt::<
+                        ::bridgeless::internal::AddConst<Ofs, 0>,
+                        ::bridgeless::internal::FallbackVmtGen<
+                            _bridgeless_G,
+                            ::bridgeless::internal::Deferred<'static, #ty>,
+                        >,
+                        RUST_DESTRUCTORS,
+                    >(),
+                },
+            )
+        }
+    };
+
+    quote! {
+        #[doc(hidden)]
+        #vis struct #fallback_gen<#(#params,)* Ofs, _bridgeless_O, _bridgeless_F>(
+            ::core::marker::PhantomData<fn() -> (#(#type_args,)* Ofs, _bridgeless_O, _bridgeless_F)>,
+        )
+        where #(#predicates,)*;
+
+        impl<
+            #(#params,)*
+            Ofs: ::bridgeless::internal::HasConst<usize>,
+            _bridgeless_O: ::bridgeless::internal::VmtPartGen<#ty>,
+            _bridgeless_F: ::bridgeless::internal::VmtPartGen<#ty>,
+        > ::bridgeless::internal::HasConst<#vmt<#(#args),*>>
+            for #fallback_gen<#(#args,)* Ofs, _bridgeless_O, _bridgeless_F>
+        where #(#predicates,)*
+        {
+            const VALUE: #vmt<#(#args),*> = {
+                let _bridgeless_o = <_bridgeless_O::ForOffset<Ofs>
+                    as ::bridgeless::internal::HasConst<#vmt<#(#args),*>>>::VALUE;
+                let _bridgeless_f = <_bridgeless_F::ForOffset<Ofs>
+                    as ::bridgeless::internal::HasConst<#vmt<#(#args),*>>>::VALUE;
+                #vmt {
+                    #(#fallback_fields,)*
+                    #marker_init
+                }
+            };
+        }
+
+        unsafe impl<
+            #(#params,)*
+            _bridgeless_O: ::bridgeless::internal::VmtPartGen<#ty>,
+            _bridgeless_F: ::bridgeless::internal::VmtPartGen<#ty>,
+        > ::bridgeless::internal::VmtPartGen<#ty>
+            for ::bridgeless::internal::FallbackVmtGen<_bridgeless_O, _bridgeless_F>
+        where #(#predicates,)*
+        {
+            type ForOffset<Ofs: ::bridgeless::internal::HasConst<usize>> =
+                #fallback_gen<#(#args,)* Ofs, _bridgeless_O, _bridgeless_F>;
+        }
+
+        #[doc(hidden)]
+        #vis struct #thunk_gen<#(#params,)* _bridgeless_C, Ofs>(
+            ::core::marker::PhantomData<fn() -> (#(#type_args,)* _bridgeless_C, Ofs)>,
+        )
+        where #(#predicates,)*;
+
+        impl<
+            #(#params,)*
+            _bridgeless_C: ::bridgeless::Class,
+            Ofs: ::bridgeless::internal::HasConst<usize>,
+        > ::bridgeless::internal::HasConst<#vmt<#(#args),*>>
+            for #thunk_gen<#(#args,)* _bridgeless_C, Ofs>
+        where
+            ::bridgeless::Impl<_bridgeless_C>: #impl_trait<#(#args),*>,
+            #(#predicates,)*
+        {
+            const VALUE: #vmt<#(#args),*> = {
+                #(#thunks)*
+
+                #vmt {
+                    #(#thunk_fields,)*
+                    #marker_init
+                }
+            };
+        }
+
+        unsafe impl<#(#params),*> ::bridgeless::internal::VmtPartGen<#ty> for #ty
+        where #(#predicates,)*
+        {
+            type ForOffset<Ofs: ::bridgeless::internal::HasConst<usize>> =
+                #thunk_gen<#(#args,)* #ty, Ofs>;
+        }
+
+        impl<#(#params),*> #ty where #(#predicates,)* {
+            pub const fn make_vmt<
+                Ofs: ::bridgeless::internal::HasConst<usize>,
+                _bridgeless_G: #meta::HasVmtParts<#(#args),*>,
+            >() -> #combined_vmt<#(#args),*>
+            where #make_vmt_bounds
+            {
+                let vmt = <<::bridgeless::internal::FallbackVmtGen<_bridgeless_G, #ty>
+                    as ::bridgeless::internal::VmtPartGen<#ty>>::ForOffset<Ofs>
+                    as ::bridgeless::internal::HasConst<#vmt<#(#args),*>>>::VALUE;
+                vmt.assert_implemented();
+                #combined_vmt(#base_vmt vmt)
+            }
+
+            /// Like `make_vmt`, but returns `None` instead of failing if a method of the class or
+            /// of its bases is not implemented.
+            pub const fn try_make_vmt<
+                Ofs: ::bridgeless::internal::HasConst<usize>,
+                _bridgeless_G: #meta::HasVmtParts<#(#args),*>,
+            >() -> ::core::option::Option<#combined_vmt<#(#args),*>>
+            where #make_vmt_bounds
+            {
+                let vmt = <<::bridgeless::internal::FallbackVmtGen<_bridgeless_G, #ty>
+                    as ::bridgeless::internal::VmtPartGen<#ty>>::ForOffset<Ofs>
+                    as ::bridgeless::internal::HasConst<#vmt<#(#args),*>>>::VALUE;
+                if !vmt.is_implemented() {
+                    return ::core::option::Option::None;
+                }
+                ::core::option::Option::Some(#combined_vmt(#base_try_vmt vmt))
+            }
+
+            /// Like `make_vmt`, but leaves the slots of methods which are not implemented in Rust
+            /// empty, as well as those of destructors unless `RUST_DESTRUCTORS` is true.
+            pub const fn make_override_vmt<
+                Ofs: ::bridgeless::internal::HasConst<usize>,
+                _bridgeless_G: #meta::HasVmtParts<#(#args),*>,
+                const RUST_DESTRUCTORS: bool,
+            >() -> #combined_vmt<#(#args),*>
+            where #make_vmt_bounds
+            {
+                #[allow(unused_mut)]
+                let mut vmt = <<::bridgeless::internal::FallbackVmtGen<_bridgeless_G, #ty>
+                    as ::bridgeless::internal::VmtPartGen<#ty>>::ForOffset<Ofs>
+                    as ::bridgeless::internal::HasConst<#vmt<#(#args),*>>>::VALUE;
+                #(#keep_destructors)*
+                #combined_vmt(#base_override_vmt vmt)
+            }
+        }
+
+        #[doc(hidden)]
+        #vis struct #vmt_instance<#(#params,)* _bridgeless_G>(
+            ::core::marker::PhantomData<fn() -> (#(#type_args,)* _bridgeless_G)>,
+        )
+        where #(#predicates,)*;
+
+        impl<#(#params,)* _bridgeless_G: #meta::HasVmtParts<#(#args),*>>
+            ::bridgeless::internal::HasConst<&'static #combined_vmt<#(#args),*>>
+            for #vmt_instance<#(#args,)* _bridgeless_G>
+        where #make_vmt_bounds #(#predicates,)*
+        {
+            const VALUE: &'static #combined_vmt<#(#args),*> = #vmt_instance_value;
+        }
+
+        #vmt_static
+
+        #[doc(hidden)]
+        #vis struct #override_vmt_instance<#(#params,)* _bridgeless_G, const RUST_DESTRUCTORS: bool>(
+            ::core::marker::PhantomData<fn() -> (#(#type_args,)* _bridgeless_G)>,
+        )
+        where #(#predicates,)*;
+
+        impl<
+            #(#params,)*
+            _bridgeless_G: #meta::HasVmtParts<#(#args),*>,
+            const RUST_DESTRUCTORS: bool,
+        > ::bridgeless::internal::HasConst<#combined_vmt<#(#args),*>>
+            for #override_vmt_instance<#(#args,)* _bridgeless_G, RUST_DESTRUCTORS>
+        where #make_vmt_bounds #(#predicates,)*
+        {
+            const VALUE: #combined_vmt<#(#args),*> = <#ty>::make_override_vmt::<
+                ::bridgeless::internal::ConstUsizeValue<0>,
+                _bridgeless_G,
+                RUST_DESTRUCTORS,
+            >();
+        }
+
+        unsafe impl<#(#params),*> ::bridgeless::ConcreteClass for #ty
+        where
+            for<'_bridgeless_a> ::bridgeless::internal::Deferred<'_bridgeless_a, #ty>:
+                #meta::HasVmtParts<#(#args),*>,
+            #(#predicates,)*
+        {
+            type VmtInstance = #vmt_instance<
+                #(#args,)*
+                ::bridgeless::internal::Deferred<'static, #ty>,
+            >;
+
+            type OverrideVmtInstance<const RUST_DESTRUCTORS: bool> = #override_vmt_instance<
+                #(#args,)*
+                ::bridgeless::internal::Deferred<'static, #ty>,
+                RUST_DESTRUCTORS,
+            >;
+
+            fn with_vtable(
+                layout: <Self as ::bridgeless::Class>::Layout<()>,
+                vmt: <Self as ::bridgeless::Class>::VmtPtr,
+            ) -> <Self as ::bridgeless::Class>::Layout<<Self as ::bridgeless::Class>::VmtPtr> {
+                layout.replace_vptr(vmt)
+            }
+        }
+    }
+}
+
+fn generate_init(class: &ClassInfo) -> pm2::TokenStream {
+    let ty = &class.name_with_args;
+    let params: Vec<_> = class.params().collect();
+    let args = class.args();
+    let predicates: Vec<_> = class.predicates().collect();
+    let layout = class.suffixed("Layout");
+
+    let (base_param, base_init) = match &class.base {
+        None => Default::default(),
+        Some(base) => {
+            let base_data = &base.data_path;
+            (
+                quote!(base: impl ::bridgeless::Init<#base_data>,),
+                // The base is the first field of the layout
+                quote!(::bridgeless::Init::init(base, this);),
+            )
+        }
+    };
+
+    let constructors = class.constructors.iter().map(|ctor| {
+        let attrs = &ctor.fun.attrs;
+        let ident = &ctor.fun.sig.ident;
+        let arg_names = &ctor.arg_names;
+        let arg_types = ctor.fun.sig.inputs.iter().filter_map(|arg| match arg {
+            FnArg::Typed(pt) => Some(&pt.ty),
+            FnArg::Receiver(_) => None,
+        });
+        let arg_decls = arg_names.iter().zip(arg_types.clone()).map(|(name, ty)| quote!(#name: #ty));
+        let source = &ctor.address;
+        let name = format!("{}::{}", class.name, ident);
+        let abi = class.panic.abi();
+        quote! {
+            #(#attrs)*
+            pub fn #ident(#(#arg_decls),*) -> impl ::bridgeless::Init<#ty> {
+                unsafe {
+                    ::bridgeless::init::InitFn::new(move |this: ::core::ptr::NonNull<u8>| {
+                        static _bridgeless_ADDRESS: ::bridgeless::address::LazyAddress =
+                            ::bridgeless::address::LazyAddress::new();
+
+                        let _bridgeless_fn: unsafe extern #abi fn(*mut u8, #(#arg_types),*) =
+                            ::core::mem::transmute(_bridgeless_ADDRESS.get_or_resolve(#source, #name));
+                        (_bridgeless_fn)(this.as_ptr(), #(#arg_names),*)
+                    })
+                }
+            }
+        }
+    });
+
+    quote! {
+        impl<#(#params),*> #ty where #(#predicates,)* {
+            #(#constructors)*
+
+            /// Returns an initializer constructing the class at its final address with
+            /// `bridgeless::Cls::emplace`, which initializes the base class with `base` and then
+            /// the class's data with the value returned by `init`.
+            pub fn pin_init(
+                #base_param
+                init: impl ::core::ops::FnOnce(::bridgeless::Uninit<'_, #ty>) -> #ty,
+            ) -> impl ::bridgeless::Init<#ty> {
+                unsafe {
+                    ::bridgeless::init::InitFn::new(move |this: ::core::ptr::NonNull<u8>| {
+                        #base_init
+                        let layout = this.as_ptr()
+                            as *mut #layout<#(#args,)* <#ty as ::bridgeless::Class>::VmtPtr>;
+                        let data = init(::bridgeless::Uninit::new(this));
+                        ::core::ptr::addr_of_mut!((*layout).1).write(data);
+                    })
+                }
+            }
+        }
+    }
+}
+
+/// Rewrites a `#[covariant]` override returning a pointer to a subclass into one with the
+/// signature of the base method, which upcasts the value returned by the original body.
+///
+/// The body is moved into a local trait so that `self`, `Self` and `return` keep their meaning.
+fn covariant_override(fun: &mut ImplItemFn, base: &Path, generics: &Generics, self_ty: &Type) {
+    let ret = match &fun.sig.output {
+        ReturnType::Type(_, ty) => ty.as_ref().clone(),
+        ReturnType::Default => abort!(
+            fun.sig,
+            "covariant overrides must return a pointer to a subclass of the base class"
+        ),
+    };
+
+    let mut inner = fun.clone();
+    let ident = &fun.sig.ident;
+    let mut arg_names = Vec::new();
+    for (i, arg) in fun.sig.inputs.iter_mut().enumerate() {
+        match arg {
+            FnArg::Receiver(_) => arg_names.push(quote!(self)),
+            FnArg::Typed(pt) => {
+                let name = match pt.pat.as_ref() {
+                    Pat::Ident(PatIdent { ident, .. }) => ident.clone(),
+                    _ => format_ident!("arg{i}"),
+                };
+                *pt.pat = parse_quote!(#name);
+                arg_names.push(quote!(#name));
+            }
+        }
+    }
+    let trait_sig = fun.sig.clone();
+    let covariant = quote_spanned!(ret.span()=> <#ret as ::bridgeless::CovariantReturn<#base>>);
+    fun.sig.output = parse_quote!(-> #covariant::Base);
+    fun.attrs.retain(|attr| attr.path().is_ident("doc"));
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    inner.vis = Visibility::Inherited;
+    fun.block = parse_quote!({
+        #[allow(non_camel_case_types)]
+        trait _bridgeless_Covariant #impl_generics #where_clause {
+            #trait_sig;
+        }
+        impl #impl_generics _bridgeless_Covariant #ty_generics for #self_ty #where_clause {
+            #inner
+        }
+        #covariant::upcast_return(
+            <Self as _bridgeless_Covariant #ty_generics>::#ident(#(#arg_names),*)
+        )
+    });
+}
+
+/// Attribute proc macro placed on an implementation of the `_Impl` trait of a base class for
+/// `Impl<C>`, where `C` is a class derived from it. Methods of the implementation override the
+/// ones of the base class in the vtables of `C`.
+///
+/// A class can only be instantiated or derived from in Rust if such an implementation (possibly
+/// empty) is present for every one of its base classes:
+///
+/// ```ignore
+/// #[class_impl]
+/// impl Shape_Impl for Impl<Circle> {
+///     fn area(&self) -> i64 {
+///         3 * self.radius * self.radius
+///     }
+/// }
+/// ```
+///
+/// An override marked with `#[covariant]` may return a pointer to a subclass of what the base
+/// method returns (`CRef`, `CBox`, raw `Cls` pointers or an `Option` of them), like a C++ override
+/// returning `Derived*` in place of `Base*`. The pointer is upcast when called through the vtable:
+///
+/// ```ignore
+/// #[class_impl]
+/// impl Shape_Impl for Impl<Circle> {
+///     #[covariant]
+///     fn clone(&self) -> *mut Cls<Circle> {
+///         Box::into_raw(Box::new(Circle::new(self.radius)))
+///     }
+/// }
+/// ```
+#[proc_macro_error]
+#[proc_macro_attribute]
+pub fn class_impl(attr: TokenStream, item: TokenStream) -> TokenStream {
+    if !attr.is_empty() {
+        abort_call_site!("class_impl macro does not take any arguments");
+    }
+
+    let mut item_impl: ItemImpl = parse_macro_input!(item);
+
+    let trait_path = match &item_impl.trait_ {
+        Some((None, path, _)) => path.clone(),
+        _ => abort!(
+            item_impl.self_ty,
+            "class_impl must be used on an implementation of a class's `_Impl` trait"
+        ),
+    };
+
+    let derived = match item_impl.self_ty.as_ref() {
+        Type::Path(TypePath { qself: None, path }) => {
+            path.segments
+                .last()
+                .and_then(|seg| match (&seg.arguments, seg.ident == "Impl") {
+                    (PathArguments::AngleBracketed(args), true) if args.args.len() == 1 => {
+                        match args.args.first() {
+                            Some(GenericArgument::Type(ty)) => Some(ty.clone()),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                })
+        }
+        _ => None,
+    }
+    .unwrap_or_else(|| abort!(item_impl.self_ty, "class_impl must be used on `Impl<C>`"));
+
+    let mut meta_path = trait_path;
+    let last = meta_path.segments.last_mut().unwrap();
+    let class_name = match last.ident.to_string().strip_suffix("_Impl") {
+        Some(name) if !name.is_empty() => name.to_owned(),
+        _ => abort!(last.ident, "expected the `_Impl` trait of a class"),
+    };
+    last.ident = Ident::new(&format!("{class_name}_Meta"), last.ident.span());
+    let args = std::mem::take(&mut last.arguments);
+
+    if let Type::Path(TypePath { qself: None, path }) = &derived {
+        if path.segments.last().is_some_and(|seg| seg.ident == class_name) {
+            abort!(
+                derived,
+                "the implementations of a class's own virtual methods must be provided in its \
+                 #[class] declaration"
+            );
+        }
+    }
+
+    let data_path = helpers::append_path(&meta_path, "Data", &args);
+    let (generics, self_ty) = (item_impl.generics.clone(), item_impl.self_ty.clone());
+    for item in &mut item_impl.items {
+        if let ImplItem::Fn(fun) = item {
+            let len = fun.attrs.len();
+            fun.attrs.retain(|attr| !attr.path().is_ident("covariant"));
+            if fun.attrs.len() != len {
+                covariant_override(fun, &data_path, &generics, &self_ty);
+            }
+        }
+    }
+
+    let overrides: Vec<ImplItem> = item_impl
+        .items
+        .iter()
+        .filter_map(|item| match item {
+            ImplItem::Fn(fun) => {
+                let overrides = format_ident!("__OVERRIDES_{}", fun.sig.ident);
+                Some(parse_quote!(const #overrides: bool = true;))
+            }
+            _ => None,
+        })
+        .collect();
+    item_impl.items.extend(overrides);
+
+    let mut thunk_gen_args = match args {
+        PathArguments::AngleBracketed(args) => args.args,
+        _ => Default::default(),
+    };
+    thunk_gen_args.push(parse_quote!(#derived));
+    thunk_gen_args.push(parse_quote!(Ofs));
+    let thunk_gen_path = helpers::append_path(
+        &meta_path,
+        "ThunkGen",
+        &PathArguments::AngleBracketed(parse_quote!(<#thunk_gen_args>)),
+    );
+
+    let (impl_generics, _, where_clause) = item_impl.generics.split_for_impl();
+
+    quote! {
+        #item_impl
+
+        unsafe impl #impl_generics ::bridgeless::internal::VmtPartGen<#data_path> for #derived
+        #where_clause
+        {
+            type ForOffset<Ofs: ::bridgeless::internal::HasConst<usize>> = #thunk_gen_path;
+        }
+    }
+    .into()
 }
 
 // fn check_restrictions(trait_def: &ItemTrait) {
@@ -0,0 +1,151 @@
+//! Lifetime handling for virtual function signatures.
+//!
+//! Vtable slots are higher-ranked function pointers, for which the lifetime elision rules of
+//! methods don't apply. Signatures are thus rewritten with every elided lifetime made explicit
+//! before being turned into function pointer types and thunks.
+
+use std::collections::HashSet;
+
+use proc_macro2::Span;
+use proc_macro_error::emit_error;
+use syn::{visit::Visit, visit_mut::VisitMut, *};
+
+/// Signature of a virtual function where all lifetimes are named.
+pub struct ExplicitSig {
+    /// Lifetime parameters, including the ones introduced for elided lifetimes.
+    pub lifetimes: Vec<LifetimeParam>,
+    pub receiver: Lifetime,
+    pub inputs: Vec<Type>,
+    pub output: ReturnType,
+}
+
+enum Replacement<'a> {
+    /// Each elided lifetime becomes a new lifetime parameter.
+    Fresh(&'a mut Vec<Lifetime>),
+    /// Each elided lifetime becomes the given lifetime.
+    Fixed(&'a Lifetime),
+}
+
+struct ElidedLifetimes<'a>(Replacement<'a>);
+
+impl ElidedLifetimes<'_> {
+    fn next(&mut self) -> Lifetime {
+        match &mut self.0 {
+            Replacement::Fresh(fresh) => {
+                let lt = Lifetime::new(&format!("'_bridgeless_{}", fresh.len()), Span::call_site());
+                fresh.push(lt.clone());
+                lt
+            }
+            Replacement::Fixed(lt) => (*lt).clone(),
+        }
+    }
+}
+
+impl VisitMut for ElidedLifetimes<'_> {
+    fn visit_type_reference_mut(&mut self, r: &mut TypeReference) {
+        if r.lifetime.is_none() {
+            r.lifetime = Some(self.next());
+        }
+        visit_mut::visit_type_reference_mut(self, r);
+    }
+
+    fn visit_lifetime_mut(&mut self, lt: &mut Lifetime) {
+        if lt.ident == "_" {
+            *lt = self.next();
+        }
+    }
+
+    // Elided lifetimes of nested function types are bound by them
+    fn visit_type_bare_fn_mut(&mut self, _: &mut TypeBareFn) {}
+    fn visit_parenthesized_generic_arguments_mut(&mut self, _: &mut ParenthesizedGenericArguments) {
+    }
+}
+
+#[derive(Default)]
+struct UsedLifetimes<'a>(Vec<&'a Lifetime>);
+
+impl<'a> Visit<'a> for UsedLifetimes<'a> {
+    fn visit_lifetime(&mut self, lt: &'a Lifetime) {
+        self.0.push(lt);
+    }
+
+    fn visit_type_bare_fn(&mut self, _: &'a TypeBareFn) {}
+    fn visit_parenthesized_generic_arguments(&mut self, _: &'a ParenthesizedGenericArguments) {}
+}
+
+/// Makes the lifetimes of a virtual function signature explicit according to the elision rules
+/// of methods with a reference receiver, emitting errors for lifetimes which can't be
+/// expressed in a higher-ranked function pointer type.
+pub fn explicit_sig(sig: &Signature) -> ExplicitSig {
+    for param in sig.generics.lifetimes() {
+        if !param.bounds.is_empty() {
+            emit_error!(
+                param,
+                "lifetime bounds are not supported on virtual functions"
+            );
+        }
+    }
+    if let Some(where_clause) = &sig.generics.where_clause {
+        emit_error!(
+            where_clause,
+            "where clauses are not supported on virtual functions"
+        );
+    }
+
+    let mut fresh = Vec::new();
+    let receiver = match sig.receiver().and_then(|r| r.lifetime()) {
+        Some(lt) if lt.ident != "_" => lt.clone(),
+        _ => {
+            let lt = Lifetime::new("'_bridgeless_self", Span::call_site());
+            fresh.push(lt.clone());
+            lt
+        }
+    };
+
+    let mut inputs: Vec<Type> = sig
+        .inputs
+        .iter()
+        .filter_map(|arg| match arg {
+            FnArg::Typed(pt) => Some(pt.ty.as_ref().clone()),
+            FnArg::Receiver(_) => None,
+        })
+        .collect();
+    let mut visitor = ElidedLifetimes(Replacement::Fresh(&mut fresh));
+    inputs.iter_mut().for_each(|ty| visitor.visit_type_mut(ty));
+
+    let mut output = sig.output.clone();
+    ElidedLifetimes(Replacement::Fixed(&receiver)).visit_return_type_mut(&mut output);
+
+    // Lifetimes only used by the return type are unbounded, as the caller can choose them freely
+    let mut used = UsedLifetimes::default();
+    inputs.iter().for_each(|ty| used.visit_type(ty));
+    let used: HashSet<_> =
+        used.0.into_iter().map(|lt| &lt.ident).chain([&receiver.ident]).collect();
+    let declared: HashSet<_> = sig.generics.lifetimes().map(|p| &p.lifetime.ident).collect();
+
+    let mut output_lifetimes = UsedLifetimes::default();
+    output_lifetimes.visit_return_type(&sig.output);
+    for lt in output_lifetimes.0 {
+        if declared.contains(&lt.ident) && !used.contains(&lt.ident) {
+            emit_error!(
+                lt,
+                "lifetime `{}` of the return type must be used by the receiver or an argument",
+                lt
+            );
+        }
+    }
+
+    let lifetimes = sig
+        .generics
+        .lifetimes()
+        .cloned()
+        .chain(fresh.into_iter().map(LifetimeParam::new))
+        .collect();
+
+    ExplicitSig {
+        lifetimes,
+        receiver,
+        inputs,
+        output,
+    }
+}
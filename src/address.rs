@@ -0,0 +1,91 @@
+//! Resolution of the addresses of non-virtual methods bound in a class declaration with the
+//! `#[rva]`, `#[symbol]` or `#[address]` attributes.
+//!
+//! [`Rva`] and [`Symbol`] look up the modules of the process, and are only available on Windows
+//! and Unix platforms.
+
+#[cfg(any(windows, unix))]
+use core::ffi::CStr;
+use core::{
+    ptr::{null_mut, NonNull},
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+#[cfg(any(windows, unix))]
+use crate::module::{find_symbol, Module};
+
+/// Source from which the address of a function can be resolved at runtime.
+///
+/// This is implemented by closures returning an optional address, so custom lookups can be
+/// passed to `#[address]`.
+pub trait AddressSource {
+    /// Resolves the address, returning [`None`] if it could not be found.
+    fn resolve(self) -> Option<NonNull<()>>;
+}
+
+/// Address relative to the base of the main executable module.
+#[cfg(any(windows, unix))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rva(pub usize);
+
+#[cfg(any(windows, unix))]
+impl AddressSource for Rva {
+    fn resolve(self) -> Option<NonNull<()>> {
+        NonNull::new(Module::main().base().wrapping_add(self.0) as *mut ())
+    }
+}
+
+/// Address of an exported symbol, looked up with [`find_symbol`].
+#[cfg(any(windows, unix))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Symbol<'a>(pub &'a CStr);
+
+#[cfg(any(windows, unix))]
+impl AddressSource for Symbol<'_> {
+    fn resolve(self) -> Option<NonNull<()>> {
+        find_symbol(self.0)
+    }
+}
+
+impl<F: FnOnce() -> Option<NonNull<()>>> AddressSource for F {
+    fn resolve(self) -> Option<NonNull<()>> {
+        self()
+    }
+}
+
+/// Address which is resolved on first use, then cached.
+#[derive(Debug)]
+pub struct LazyAddress(AtomicPtr<()>);
+
+impl LazyAddress {
+    pub const fn new() -> Self {
+        Self(AtomicPtr::new(null_mut()))
+    }
+
+    /// Returns the cached address, resolving it from `source` if this is the first call.
+    ///
+    /// Concurrent first calls may resolve the address more than once.
+    ///
+    /// # Panics
+    /// If the address could not be resolved. `name` is the name of the function, which is
+    /// included in the panic message.
+    #[inline]
+    pub fn get_or_resolve(&self, source: impl AddressSource, name: &str) -> NonNull<()> {
+        match NonNull::new(self.0.load(Ordering::Acquire)) {
+            Some(address) => address,
+            None => {
+                let address = source
+                    .resolve()
+                    .unwrap_or_else(|| panic!("failed to resolve the address of {name}"));
+                self.0.store(address.as_ptr(), Ordering::Release);
+                address
+            }
+        }
+    }
+}
+
+impl Default for LazyAddress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
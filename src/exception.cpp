@@ -0,0 +1,48 @@
+// Shim running a Rust closure inside a C++ `try` block, for `exception::try_call`.
+//
+// `BRIDGELESS_TRY_CALL` is defined by the build script to a name unique to the version of the
+// crate, so that several versions can be linked in the same program.
+
+#include <exception>
+#include <typeinfo>
+
+#if defined(__GLIBCXX__) || defined(_LIBCPP_VERSION)
+#include <cxxabi.h>
+#endif
+
+typedef void (*bridgeless_call)(void* ctx);
+typedef void (*bridgeless_on_catch)(void* ctx, const char* type_name, const char* what);
+
+// Kinds of unwinding out of `f`, set by `f` in `*unwind` before it unwinds. Must match
+// `exception.rs`.
+enum {
+    UNWIND_EXCEPTION = 0,
+    UNWIND_PANIC = 1,
+    // Either a C++ exception or a Rust panic, which is not a C++ exception whose type can be read
+    UNWIND_UNKNOWN = 2,
+};
+
+// Calls `f(ctx)`, returning 0 if it returned. If a C++ exception unwound out of it, calls
+// `on_catch` while the exception is alive and returns 1. Rust panics are rethrown.
+extern "C" int BRIDGELESS_TRY_CALL(bridgeless_call f, void* ctx, const int* unwind,
+                                   bridgeless_on_catch on_catch) {
+    try {
+        f(ctx);
+        return 0;
+    } catch (const std::exception& e) {
+        on_catch(ctx, typeid(e).name(), e.what());
+    } catch (...) {
+        if (*unwind == UNWIND_PANIC) throw;
+
+        const char* type_name = nullptr;
+#if defined(__GLIBCXX__) || defined(_LIBCPP_VERSION)
+        if (*unwind == UNWIND_EXCEPTION) {
+            if (const std::type_info* type = abi::__cxa_current_exception_type()) {
+                type_name = type->name();
+            }
+        }
+#endif
+        on_catch(ctx, type_name, nullptr);
+    }
+    return 1;
+}
@@ -0,0 +1,153 @@
+//! Catching C++ exceptions thrown by C++ functions called from Rust.
+//!
+//! C++ exceptions can only unwind into Rust through functions using the `C-unwind` ABI, such as
+//! the methods of classes declared with `#[class(panic = "unwind")]`. Rust itself cannot catch
+//! them, so [`try_call`] runs a closure inside a C++ `try` block instead.
+//!
+//! The `try` block is a small C++ shim compiled by the build script with the system C++ compiler,
+//! which also links the C++ runtime (e.g. `libstdc++`).
+
+use core::{
+    ffi::{c_char, c_int, c_void, CStr},
+    fmt,
+    ptr::addr_of,
+};
+use std::string::{String, ToString};
+
+/// C++ exception caught by [`try_call`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ForeignException {
+    type_name: String,
+    what: Option<String>,
+}
+
+impl ForeignException {
+    /// Name of the type of the exception, as returned by `std::type_info::name`. Its format is
+    /// implementation-defined: GCC and Clang return the mangled name, and MSVC a demangled one.
+    ///
+    /// Empty if the type of an exception not derived from `std::exception` cannot be told, as with
+    /// MSVC.
+    pub fn type_name(&self) -> &str {
+        &self.type_name
+    }
+
+    /// Message of the exception, if it is derived from `std::exception`.
+    pub fn what(&self) -> Option<&str> {
+        self.what.as_deref()
+    }
+}
+
+impl fmt::Display for ForeignException {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "C++ exception of type `{}`", self.type_name)?;
+        match &self.what {
+            Some(what) => write!(f, ": {what}"),
+            None => Ok(()),
+        }
+    }
+}
+
+impl std::error::Error for ForeignException {}
+
+/// Calls `f`, catching the C++ exceptions which unwind out of it.
+///
+/// Rust panics raised by `f` are propagated unchanged, except if the thread is already panicking,
+/// e.g. when `try_call` is used in a `Drop` implementation during unwinding: they can't be told
+/// apart from C++ exceptions then, and are caught as exceptions of unknown type.
+pub fn try_call<R, F: FnOnce() -> R>(f: F) -> Result<R, ForeignException> {
+    let mut call = Call {
+        f: Some(f),
+        result: None,
+        unwind: UNWIND_EXCEPTION,
+        exception: None,
+    };
+    let ctx = &mut call as *mut Call<R, F>;
+    let thrown = unsafe {
+        bridgeless_try_call(
+            call_once::<R, F>,
+            ctx as *mut c_void,
+            addr_of!((*ctx).unwind),
+            on_catch::<R, F>,
+        )
+    };
+    match thrown {
+        0 => Ok(call.result.unwrap()),
+        _ => Err(call.exception.unwrap()),
+    }
+}
+
+/// Kinds of unwinding out of `f`, as told to the shim. Must match `exception.cpp`.
+const UNWIND_EXCEPTION: c_int = 0;
+const UNWIND_PANIC: c_int = 1;
+const UNWIND_UNKNOWN: c_int = 2;
+
+struct Call<R, F> {
+    f: Option<F>,
+    result: Option<R>,
+    /// Set when `f` unwinds, for the shim to rethrow Rust panics.
+    unwind: c_int,
+    exception: Option<ForeignException>,
+}
+
+unsafe extern "C-unwind" fn call_once<R, F: FnOnce() -> R>(call: *mut c_void) {
+    /// Dropped only if `f` unwinds. C++ exceptions unwind through Rust frames without panicking,
+    /// so a panic started by `f` is one which was not already running when it was called.
+    struct PanicGuard<'a> {
+        unwind: &'a mut c_int,
+        was_panicking: bool,
+    }
+
+    impl Drop for PanicGuard<'_> {
+        fn drop(&mut self) {
+            *self.unwind = match (self.was_panicking, std::thread::panicking()) {
+                (true, _) => UNWIND_UNKNOWN,
+                (false, true) => UNWIND_PANIC,
+                (false, false) => UNWIND_EXCEPTION,
+            };
+        }
+    }
+
+    let call = unsafe { &mut *(call as *mut Call<R, F>) };
+    let f = call.f.take().unwrap();
+    let guard = PanicGuard {
+        unwind: &mut call.unwind,
+        was_panicking: std::thread::panicking(),
+    };
+    let result = f();
+    core::mem::forget(guard);
+    call.result = Some(result);
+}
+
+unsafe extern "C" fn on_catch<R, F>(
+    call: *mut c_void,
+    type_name: *const c_char,
+    what: *const c_char,
+) {
+    let call = unsafe { &mut *(call as *mut Call<R, F>) };
+    let to_string = |s: *const c_char| unsafe { CStr::from_ptr(s) }.to_string_lossy();
+
+    // Names of types with internal linkage are prefixed with '*' by GCC
+    let type_name = match type_name.is_null() {
+        true => String::new(),
+        false => to_string(type_name).trim_start_matches('*').to_string(),
+    };
+    let what = (!what.is_null()).then(|| to_string(what).into_owned());
+    call.exception = Some(ForeignException { type_name, what });
+}
+
+extern "C-unwind" {
+    /// Shim in `exception.cpp`, calling `f(ctx)` in a `try` block. Returns 0 if `f` returned, and
+    /// 1 if it threw a C++ exception, which was passed to `on_catch`. Rust panics are rethrown if
+    /// `*unwind` is [`UNWIND_PANIC`].
+    #[link_name = env!("BRIDGELESS_TRY_CALL")]
+    fn bridgeless_try_call(
+        f: unsafe extern "C-unwind" fn(ctx: *mut c_void),
+        ctx: *mut c_void,
+        unwind: *const c_int,
+        on_catch: unsafe extern "C" fn(
+            ctx: *mut c_void,
+            type_name: *const c_char,
+            what: *const c_char,
+        ),
+    ) -> c_int;
+}
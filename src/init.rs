@@ -0,0 +1,100 @@
+//! In-place construction of class instances.
+//!
+//! C++ objects are often address-sensitive: constructors register `this` with other subsystems
+//! or link the object into intrusive lists. Creating a [`Cls`] by value with [`Cls::new`] and
+//! moving it around would leave such registrations dangling.
+//!
+//! Instead, each class declaration provides a `pin_init` function returning an [`Init`], which
+//! initializes the data of the class and of its bases (base first) directly at the final address
+//! of the instance. It is then constructed with [`Cls::emplace`]:
+//!
+//! ```ignore
+//! let mut slot = MaybeUninit::uninit();
+//! let circle = unsafe {
+//!     Cls::emplace(
+//!         &mut slot,
+//!         Circle::pin_init(
+//!             Shape::pin_init(|this| Shape { id: register_shape(this.this()) }),
+//!             |_| Circle { radius: 2 },
+//!         ),
+//!     )
+//! };
+//! ```
+
+use core::{any::TypeId, marker::PhantomData, pin::Pin, ptr::NonNull};
+
+use crate::{base_offset, Class, ClassLayout, Cls, SubclassOf};
+
+/// In-place initializer of the data of the class `C` and of its bases, returned by the
+/// `pin_init` function generated for each class.
+///
+/// # Safety
+/// [`Init::init`] must fully initialize the data of `C` and of all of its bases.
+pub unsafe trait Init<C: Class> {
+    /// Initializes the data of `C` and of its bases in the layout of `C` at `this`.
+    ///
+    /// # Safety
+    /// `this` must be valid for writes of `C::Layout<C::VmtPtr>` and properly aligned. Its vtable
+    /// pointer must already be set.
+    unsafe fn init(self, this: NonNull<u8>);
+}
+
+/// Instance of `C` (or of a class derived from it) under construction, whose bases are
+/// initialized but whose own data is not.
+pub struct Uninit<'a, C: Class>(NonNull<u8>, PhantomData<&'a mut Cls<C>>);
+
+impl<C: Class> Uninit<'_, C> {
+    #[doc(hidden)]
+    #[inline(always)]
+    pub unsafe fn new(this: NonNull<u8>) -> Self {
+        Self(this, PhantomData)
+    }
+
+    /// Returns the final address of the instance, the C++ `this` pointer.
+    ///
+    /// The instance must not be accessed through it until it is fully constructed.
+    #[inline(always)]
+    pub fn this(&self) -> NonNull<Cls<C>> {
+        self.0.cast()
+    }
+
+    /// Returns the data of the base class `B` of `C`, which has already been initialized.
+    ///
+    /// # Panics
+    /// If `B` is `C` itself, as its data is being initialized.
+    #[inline]
+    pub fn base<B: Class>(&mut self) -> Pin<&mut B>
+    where
+        C: SubclassOf<B>,
+    {
+        assert!(
+            TypeId::of::<B>() != TypeId::of::<C>(),
+            "the data of the class under construction is not initialized yet"
+        );
+        unsafe {
+            let base = self.0.as_ptr().add(base_offset::<B, C>());
+            let layout = &mut *(base as *mut B::Layout<B::VmtPtr>);
+            Pin::new_unchecked(layout.data_mut())
+        }
+    }
+}
+
+/// [`Init`] implemented by a closure which initializes the data of `C` and its bases.
+#[doc(hidden)]
+pub struct InitFn<C, F>(F, PhantomData<fn() -> C>);
+
+impl<C: Class, F: FnOnce(NonNull<u8>)> InitFn<C, F> {
+    /// # Safety
+    /// `fun` must uphold the invariants of [`Init::init`].
+    #[inline(always)]
+    pub unsafe fn new(fun: F) -> Self {
+        Self(fun, PhantomData)
+    }
+}
+
+unsafe impl<C: Class, F: FnOnce(NonNull<u8>)> Init<C> for InitFn<C, F> {
+    #[inline(always)]
+    unsafe fn init(self, this: NonNull<u8>) {
+        (self.0)(this)
+    }
+}
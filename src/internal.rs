@@ -1,6 +1,6 @@
-use core::{marker::PhantomData, ops::Add};
+use core::{fmt, marker::PhantomData};
 
-use crate::Class;
+use crate::{Class, Destructor};
 
 /// Trait implemented by types that are wrappers around a class layout,
 /// such as `Cls`, `DynCls`, and `Impl`.
@@ -11,7 +11,11 @@ pub trait ClassWrapper {
 
 /// Converts a thin type erased pointer to a (potentially fat) typed pointer.
 pub trait FromThinPtr {
+    /// # Safety
+    /// `ptr` must point to the start of a valid class layout.
     unsafe fn from_thin_ptr(ptr: *const u8) -> *const Self;
+    /// # Safety
+    /// `ptr` must point to the start of a valid class layout.
     unsafe fn from_thin_ptr_mut(ptr: *mut u8) -> *mut Self;
 }
 
@@ -22,9 +26,13 @@ pub trait FromThinPtr {
 // internal::SubclassOf<dyn Base> for internal::SubclassOfWrapper<T>. Then the blanket impl
 // implements SubclassOf<dyn Base> for T.
 
+/// # Safety
+/// Must only be implemented by the class declaration macro.
+#[allow(clippy::needless_maybe_sized)]
 pub unsafe trait SubclassOf<B: Class + ?Sized>: 'static {}
 
 pub struct SubclassOfWrapper<T: ?Sized>(T);
+#[allow(clippy::needless_maybe_sized)]
 unsafe impl<B: Class + ?Sized, D: Class + ?Sized> crate::SubclassOf<B> for D where
     SubclassOfWrapper<D>: SubclassOf<B>
 {
@@ -40,6 +48,12 @@ pub trait HasConst<T> {
     const VALUE: T;
 }
 
+/// Fallback of the probe selecting the vtable stored in the static of a non-generic class, for
+/// classes which are not concrete.
+pub trait NoVmt<T> {
+    const VMT: Option<T> = None;
+}
+
 pub struct ConstUsizeValue<const N: usize>;
 impl<const N: usize> HasConst<usize> for ConstUsizeValue<N> {
     const VALUE: usize = N;
@@ -66,6 +80,17 @@ pub unsafe trait VmtPartGen<C: Class> {
     type ForOffset<Ofs: HasConst<usize>>: HasConst<C::VmtPart>;
 }
 
+/// Wrapper forwarding the [`VmtPartGen`] implementations of `T`.
+///
+/// Generated code bounds on `for<'a> Deferred<'a, C>: VmtPartGen<B>` instead of `C: VmtPartGen<B>`
+/// so that impls for classes which can't be instantiated are discarded instead of rejected as
+/// having trivially false bounds.
+pub struct Deferred<'a, T>(PhantomData<&'a T>);
+
+unsafe impl<C: Class, T: VmtPartGen<C>> VmtPartGen<C> for Deferred<'_, T> {
+    type ForOffset<Ofs: HasConst<usize>> = T::ForOffset<Ofs>;
+}
+
 /// Given a list of the size of the virtual function tables of all base classes,
 /// checks if the order is compatible with the C++ ABI. If not, will panic to prevent
 /// compilation and let the user know.
@@ -84,3 +109,138 @@ pub const fn assert_base_ordering(size_of_vmts: &[usize]) {
         i += 1;
     }
 }
+
+/// Returns the index of a vtable slot in the part of a class's vtable, from its offset `bytes`
+/// from the start of the vtable whose first `base_slots` slots belong to base classes.
+// `usize::is_multiple_of` requires Rust 1.87
+#[allow(clippy::manual_is_multiple_of)]
+pub const fn byte_offset_slot(bytes: usize, base_slots: usize) -> usize {
+    let ptr = size_of::<usize>();
+    assert!(
+        bytes % ptr == 0,
+        "vtable byte offset must be a multiple of the size of a pointer"
+    );
+    assert!(
+        bytes / ptr >= base_slots,
+        "vtable byte offset must be past the slots of base classes"
+    );
+    bytes / ptr - base_slots
+}
+
+/// Returns the number of slots of a gap ending at the slot `end`, starting at the slot `start`.
+pub const fn gap_len(end: usize, start: usize) -> usize {
+    assert!(end >= start, "offset must be strictly increasing");
+    end - start
+}
+
+/// Destructor of a class whose vtable is generated from Rust, which drops the layout of `C` found
+/// `Ofs` bytes before `this`.
+unsafe extern "C" fn drop_layout<C: Class, Ofs: HasConst<usize>>(this: *mut u8) {
+    unsafe { core::ptr::drop_in_place(this.sub(Ofs::VALUE) as *mut C::Layout<C::VmtPtr>) }
+}
+
+#[cfg(target_env = "msvc")]
+unsafe extern "C" fn scalar_deleting<C: Class, Ofs: HasConst<usize>>(
+    this: *mut u8,
+    _flags: u32,
+) -> *mut u8 {
+    unsafe { drop_layout::<C, Ofs>(this) };
+    this
+}
+
+/// Vtable entries of the destructor of `C` for the part of its vtable at offset `Ofs`.
+///
+/// The memory of Rust instances is not owned by C++, so the deleting destructor only drops the
+/// instance without freeing it. The C++ destructors of its bases are not called, which is why
+/// `#[constructor]` bases must have a trivial destructor.
+pub const fn rust_destructor<C: Class, Ofs: HasConst<usize>>() -> Destructor {
+    Destructor {
+        #[cfg(not(target_env = "msvc"))]
+        complete: Some(drop_layout::<C, Ofs>),
+        #[cfg(not(target_env = "msvc"))]
+        deleting: Some(drop_layout::<C, Ofs>),
+        #[cfg(target_env = "msvc")]
+        scalar_deleting: Some(scalar_deleting::<C, Ofs>),
+    }
+}
+
+/// Guard held by the thunks of classes with the `abort` panic policy while the Rust
+/// implementation of a method runs. Panicking again when dropped during unwinding aborts the
+/// process, naming the method the panic came from.
+///
+/// The guard must be forgotten once the implementation returns.
+pub struct AbortOnUnwind(pub &'static str);
+
+impl Drop for AbortOnUnwind {
+    fn drop(&mut self) {
+        panic!(
+            "panic in `{}` called from C++ cannot unwind, aborting",
+            self.0
+        );
+    }
+}
+
+/// Debug formatting of the function bound to a vtable slot: its address, followed by its
+/// location in the loaded modules with the `std` feature on Windows and Unix.
+#[derive(Clone, Copy)]
+pub struct DebugSlot(pub *const ());
+
+impl DebugSlot {
+    /// Reads the function pointer of a slot of type `Option<F>`.
+    #[inline(always)]
+    pub fn of<F: Copy>(slot: &Option<F>) -> Self {
+        const {
+            assert!(
+                size_of::<Option<F>>() == size_of::<*const ()>(),
+                "slots must be function pointers"
+            )
+        };
+        Self(unsafe { core::mem::transmute_copy(slot) })
+    }
+}
+
+impl fmt::Debug for DebugSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.0.is_null() {
+            return f.write_str("None");
+        }
+        write!(f, "{:p}", self.0)?;
+        #[cfg(all(feature = "std", any(windows, unix)))]
+        if let Some(location) = crate::module::locate(self.0) {
+            write!(f, " in {location}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Runs the Rust implementation of a method of a class with the `catch` panic policy, returning
+/// the default value of its return type if it panics.
+#[cfg(feature = "std")]
+#[inline]
+pub fn catch_unwind_or_default<R: Default>(f: impl FnOnce() -> R) -> R {
+    std::panic::catch_unwind(std::panic::AssertUnwindSafe(f)).unwrap_or_default()
+}
+
+/// Calls [`catch_unwind_or_default`] from the thunks of classes with the `catch` panic policy, or
+/// fails to compile without the `std` feature, which the macros can't check themselves.
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bridgeless_catch_unwind {
+    ($f:expr) => {
+        $crate::internal::catch_unwind_or_default($f)
+    };
+}
+
+#[cfg(not(feature = "std"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __bridgeless_catch_unwind {
+    ($f:expr) => {
+        ::core::compile_error!(
+            "the \"catch\" panic policy requires the `std` feature of bridgeless"
+        )
+    };
+}
+
+pub use __bridgeless_catch_unwind as catch_unwind;
@@ -1,16 +1,39 @@
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
 use core::{
+    fmt,
     marker::PhantomData,
+    mem::{ManuallyDrop, MaybeUninit},
     ops::{Deref, DerefMut},
+    pin::Pin,
     ptr::NonNull,
 };
 
-pub use bridgeless_proc_macros::class;
+pub use bridgeless_proc_macros::{class, class_impl};
 
+pub mod address;
+#[cfg(all(feature = "std", any(windows, unix)))]
+pub mod exception;
+pub mod init;
 pub mod internal;
-
+// Modules looking up the images loaded in the process, which need an OS loader
+#[cfg(any(windows, unix))]
+pub mod module;
+pub mod overrides;
+pub mod registry;
+#[cfg(any(windows, unix))]
+pub mod rtti;
+#[cfg(any(windows, unix))]
+pub mod scan;
+pub mod stl;
+pub mod weak;
+
+pub use init::{Init, Uninit};
 use internal::FromThinPtr;
+pub use weak::CWeak;
 
 /// Trait implemented on types that match a class's memory layout.
 pub trait ClassLayout<VPtr: 'static + Copy>: 'static {
@@ -55,7 +78,7 @@ pub unsafe trait Class: 'static + Sized {
     type VmtPart: 'static + Copy;
 
     /// Type of the main vtable (the one at offset 0) of the class.
-    type Vmt: 'static + Copy;
+    type Vmt: VmtSlots;
 
     /// Type of the vtable pointer included in the layout struct.
     ///
@@ -70,6 +93,295 @@ pub unsafe trait Class: 'static + Sized {
     /// currently possible without specialization. Instead, it is implemented such that it can
     /// be inlined to a constant when optimizations are applied.
     fn base_offset<C: Class>() -> Option<usize>;
+
+    /// Returns the virtual destructor in the vtable `vmt`, if the class or one of its bases
+    /// declares one.
+    fn destructor(vmt: Self::VmtPtr) -> Option<Destructor>;
+}
+
+/// Trait implemented by classes whose vtable can be generated entirely from Rust implementations,
+/// i.e. every virtual method of the class and its bases has an implementation.
+///
+/// This is implemented by the class declaration macro for classes where the `#[class_impl]`
+/// blocks of all base classes are present. Missing method implementations are reported as a
+/// compile-time error when an instance is first created with [`Cls::new`].
+///
+/// # SAFETY
+/// **This trait should not be implemented manually**.
+pub unsafe trait ConcreteClass: Class {
+    /// Type providing the vtable generated from the class's Rust implementations.
+    ///
+    /// This is not an associated const of the trait as those are evaluated eagerly, even when no
+    /// instance of the class is ever created.
+    type VmtInstance: internal::HasConst<Self::VmtPtr>;
+
+    /// Type providing the main vtable of the class where only the slots of the methods
+    /// implemented in Rust are set, used to override the methods of C++ vtables with
+    /// [`overrides::register`] and [`overrides::register_derived`]. The slots of destructors
+    /// are only set if `RUST_DESTRUCTORS` is true.
+    type OverrideVmtInstance<const RUST_DESTRUCTORS: bool>: internal::HasConst<Self::Vmt>;
+
+    /// Moves `layout` into a layout using the vtable `vmt`.
+    fn with_vtable(layout: Self::Layout<()>, vmt: Self::VmtPtr) -> Self::Layout<Self::VmtPtr>;
+}
+
+/// Vtable entries of a virtual destructor, declared with `#[destructor]` in a class declaration.
+///
+/// With the Itanium C++ ABI, these are the complete object destructor followed by the deleting
+/// destructor, taking two slots. With the MSVC ABI, this is the scalar deleting destructor, which
+/// takes a single slot.
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct Destructor {
+    #[cfg(not(target_env = "msvc"))]
+    pub complete: Option<unsafe extern "C" fn(this: *mut u8)>,
+    #[cfg(not(target_env = "msvc"))]
+    pub deleting: Option<unsafe extern "C" fn(this: *mut u8)>,
+    #[cfg(target_env = "msvc")]
+    pub scalar_deleting: Option<unsafe extern "C" fn(this: *mut u8, flags: u32) -> *mut u8>,
+}
+
+impl Destructor {
+    /// Number of vtable slots taken by a virtual destructor.
+    pub const SLOTS: usize = size_of::<Self>() / size_of::<usize>();
+
+    /// Unbound destructor slots.
+    pub const NONE: Self = Self {
+        #[cfg(not(target_env = "msvc"))]
+        complete: None,
+        #[cfg(not(target_env = "msvc"))]
+        deleting: None,
+        #[cfg(target_env = "msvc")]
+        scalar_deleting: None,
+    };
+
+    /// Returns true if the destructor slots are bound.
+    #[inline(always)]
+    pub const fn is_some(&self) -> bool {
+        #[cfg(not(target_env = "msvc"))]
+        return self.complete.is_some() && self.deleting.is_some();
+        #[cfg(target_env = "msvc")]
+        return self.scalar_deleting.is_some();
+    }
+
+    /// Returns `self` if it is bound, and `fallback` otherwise.
+    #[doc(hidden)]
+    pub const fn or(self, fallback: Self) -> Self {
+        match self.is_some() {
+            true => self,
+            false => fallback,
+        }
+    }
+
+    /// Panics with `msg` if the destructor slots are not bound.
+    #[doc(hidden)]
+    pub const fn expect(self, msg: &str) -> Self {
+        if !self.is_some() {
+            panic!("{}", msg);
+        }
+        self
+    }
+
+    /// Runs the destructor of the object at `this` without freeing its memory, like an explicit
+    /// call to `this->~C()` in C++.
+    ///
+    /// # Safety
+    /// `this` must point to a valid instance of a class using this destructor, which must not be
+    /// used afterwards.
+    #[inline]
+    pub unsafe fn destruct(self, this: *mut u8) {
+        #[cfg(not(target_env = "msvc"))]
+        (self.complete.expect("unbound destructor"))(this);
+        #[cfg(target_env = "msvc")]
+        (self.scalar_deleting.expect("unbound destructor"))(this, 0);
+    }
+
+    /// Runs the destructor of the object at `this` and frees its memory, like `delete this` in C++.
+    ///
+    /// # Safety
+    /// `this` must point to a valid instance of a class using this destructor which was allocated
+    /// with C++'s `new`, and must not be used afterwards.
+    #[inline]
+    pub unsafe fn delete(self, this: *mut u8) {
+        #[cfg(not(target_env = "msvc"))]
+        (self.deleting.expect("unbound destructor"))(this);
+        #[cfg(target_env = "msvc")]
+        (self.scalar_deleting.expect("unbound destructor"))(this, 1);
+    }
+}
+
+impl fmt::Debug for Destructor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut debug = f.debug_struct("Destructor");
+        #[cfg(not(target_env = "msvc"))]
+        debug
+            .field("complete", &internal::DebugSlot::of(&self.complete))
+            .field("deleting", &internal::DebugSlot::of(&self.deleting));
+        #[cfg(target_env = "msvc")]
+        debug.field(
+            "scalar_deleting",
+            &internal::DebugSlot::of(&self.scalar_deleting),
+        );
+        debug.finish()
+    }
+}
+
+/// Vtable slot in a gap left by `#[offset]` in a class declaration, whose signature is unknown.
+///
+/// Its function can be read from a C++ vtable and forwarded to another one, but must be cast to
+/// its actual signature to be called.
+#[repr(transparent)]
+#[derive(Clone, Copy)]
+pub struct OpaqueFn(Option<unsafe extern "C" fn()>);
+
+impl fmt::Debug for OpaqueFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        internal::DebugSlot(self.as_ptr()).fmt(f)
+    }
+}
+
+impl OpaqueFn {
+    /// Unbound slot.
+    pub const NONE: Self = Self(None);
+
+    /// Creates a slot bound to the function at `fun`, or an unbound one if it is null.
+    ///
+    /// # Safety
+    /// `fun` must be null or point to a function.
+    #[inline(always)]
+    pub const unsafe fn from_ptr(fun: *const ()) -> Self {
+        unsafe { core::mem::transmute::<*const (), Self>(fun) }
+    }
+
+    /// Returns the address of the function of the slot, or null if it is unbound.
+    #[inline(always)]
+    pub fn as_ptr(self) -> *const () {
+        self.0.map_or(core::ptr::null(), |fun| fun as *const ())
+    }
+
+    /// Returns true if the slot is bound.
+    #[inline(always)]
+    pub const fn is_some(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Casts the function of the slot to the function pointer type `F`.
+    ///
+    /// # Safety
+    /// `F` must be a function pointer type matching the signature of the function.
+    #[inline(always)]
+    pub unsafe fn cast<F: Copy>(self) -> Option<F> {
+        const {
+            assert!(
+                size_of::<F>() == size_of::<usize>(),
+                "slots can only be cast to function pointers"
+            )
+        };
+        self.0.map(|fun| unsafe { core::mem::transmute_copy(&fun) })
+    }
+}
+
+/// Raw access to the slots of a vtable by index, including the slots the class declaration does
+/// not name.
+///
+/// Implemented by the `CombinedVmt` structs generated for classes.
+///
+/// # Safety
+/// `Self` must be made of pointer-sized function pointers only.
+pub unsafe trait VmtSlots: 'static + Copy {
+    /// Number of slots of the vtable declared for the class.
+    const SLOT_COUNT: usize = size_of::<Self>() / size_of::<usize>();
+
+    /// Returns the number of slots of the vtable declared for the class.
+    #[inline(always)]
+    fn slot_count(&self) -> usize {
+        Self::SLOT_COUNT
+    }
+
+    /// Returns the address of the function in the slot `index`, or null if it is unbound.
+    ///
+    /// # Panics
+    /// If `index` is not below [`Self::SLOT_COUNT`].
+    #[inline]
+    fn slot(&self, index: usize) -> *const () {
+        assert!(
+            index < Self::SLOT_COUNT,
+            "slot index {index} out of the bounds of the vtable ({} slots)",
+            Self::SLOT_COUNT
+        );
+        unsafe { (self as *const Self as *const *const ()).add(index).read() }
+    }
+}
+
+unsafe impl VmtSlots for () {}
+
+/// Function pointer types which can be called from a vtable slot with [`DynCls::call_slot`].
+///
+/// Implemented for `unsafe extern "C"` and `unsafe extern "C-unwind"` function pointers taking
+/// the `this` pointer as a `*mut u8`, followed by up to 8 arguments.
+///
+/// # Safety
+/// `Self` must be a function pointer type whose first parameter is the `this` pointer.
+pub unsafe trait SlotFn: Copy {
+    /// Tuple of the arguments following `this`.
+    type Args;
+    /// Return type of the function.
+    type Output;
+
+    /// Calls the function at `fun`.
+    ///
+    /// # Safety
+    /// `fun` must point to a function with the signature `Self`, and `this` and `args` must be
+    /// valid arguments for it.
+    unsafe fn call(fun: *const (), this: *mut u8, args: Self::Args) -> Self::Output;
+}
+
+macro_rules! impl_slot_fn {
+    ($($arg:ident),*) => {
+        impl_slot_fn!(@abi "C"; $($arg),*);
+        impl_slot_fn!(@abi "C-unwind"; $($arg),*);
+    };
+    (@abi $abi:literal; $($arg:ident),*) => {
+        unsafe impl<R, $($arg),*> SlotFn for unsafe extern $abi fn(*mut u8 $(, $arg)*) -> R {
+            type Args = ($($arg,)*);
+            type Output = R;
+
+            #[inline(always)]
+            #[allow(non_snake_case)]
+            unsafe fn call(fun: *const (), this: *mut u8, ($($arg,)*): Self::Args) -> R {
+                let fun = unsafe { core::mem::transmute::<*const (), Self>(fun) };
+                unsafe { fun(this $(, $arg)*) }
+            }
+        }
+    };
+}
+
+impl_slot_fn!();
+impl_slot_fn!(A1);
+impl_slot_fn!(A1, A2);
+impl_slot_fn!(A1, A2, A3);
+impl_slot_fn!(A1, A2, A3, A4);
+impl_slot_fn!(A1, A2, A3, A4, A5);
+impl_slot_fn!(A1, A2, A3, A4, A5, A6);
+impl_slot_fn!(A1, A2, A3, A4, A5, A6, A7);
+impl_slot_fn!(A1, A2, A3, A4, A5, A6, A7, A8);
+
+/// Variadic method bound to an instance, returned by the method generated for a virtual or
+/// non-virtual method declared with a trailing `...`.
+///
+/// Defining C variadic functions is unstable in Rust, so such methods cannot be implemented in
+/// Rust. They are called through the bound function pointer instead:
+///
+/// ```ignore
+/// let log = logger.log();
+/// unsafe { (log.fun)(log.this, c"%s: %d".as_ptr(), name.as_ptr(), 42) };
+/// ```
+#[derive(Clone, Copy)]
+pub struct Variadic<This, F> {
+    /// `this` pointer to pass to the function, the base subobject of the declaring class.
+    pub this: This,
+    /// Function pointer of the method.
+    pub fun: F,
 }
 
 /// Custom marker trait signifying that a given [`Class`] is an (inclusive) subclass of `B`.
@@ -96,8 +408,17 @@ pub fn base_offset<B: Class, C: SubclassOf<B>>() -> usize {
 /// [`Cls`] can [`DerefMut`] into the [`Class`] type. It also implements [`AsRef`] and
 /// [`AsMut`] for all base classes of `C`, so these methods may be used to access base data.
 /// Virtual methods of base classes can be called without needing to do this.
+///
+/// # Destruction
+/// When the class or one of its bases declares a virtual destructor, dropping a [`Cls`] calls the
+/// destructor of the most-derived class through the vtable, which is responsible for dropping the
+/// class data. Otherwise, the layout is dropped in place. Wrap the instance in a [`ManuallyDrop`]
+/// to skip destruction, e.g. when ownership of the object is handed over to C++.
+///
+/// An instance in C++-owned memory must not be moved out of it (e.g. with [`core::mem::replace`]),
+/// as both copies would then be destructed.
 #[repr(C)]
-pub struct Cls<C: Class>(C::Layout<C::VmtPtr>);
+pub struct Cls<C: Class>(ManuallyDrop<C::Layout<C::VmtPtr>>);
 
 impl<C: Class> Cls<C> {
     /// Upcast to a base type. The equivalent of `static_cast<B& const>(self)` in C++.
@@ -152,13 +473,92 @@ impl<C: Class> Cls<C> {
         &mut self.0
     }
 
-    /// Consumes `self`, returning the class instance's inner layout.
+    /// Consumes `self`, returning the class instance's inner layout. The destructor of the class
+    /// is not called.
+    ///
     /// # Safety
     /// Mutable access to the class layout is unsafe. In particular, it lets you overwrite
     /// the object's vtable, which is *very* dangerous!
     #[inline(always)]
     pub unsafe fn into_layout(self) -> C::Layout<C::VmtPtr> {
-        self.0
+        let this = ManuallyDrop::new(self);
+        unsafe { core::ptr::read(&*this.0) }
+    }
+
+    /// Creates an instance of the class from a layout without vtable pointer, using the vtable
+    /// generated from the class's Rust implementations.
+    #[inline(always)]
+    pub fn new(layout: C::Layout<()>) -> Self
+    where
+        C: ConcreteClass,
+    {
+        Self(ManuallyDrop::new(C::with_vtable(
+            layout,
+            <C::VmtInstance as internal::HasConst<C::VmtPtr>>::VALUE,
+        )))
+    }
+
+    /// Constructs an instance of the class in place in `slot`, using the vtable generated from
+    /// the class's Rust implementations. The data of the class and its bases is initialized at
+    /// its final address by `init`, base classes first.
+    ///
+    /// The vtable pointer is set before `init` runs, so virtual methods must not be called
+    /// during initialization. It is set again once `init` returns, as C++ constructors of base
+    /// classes (see `#[constructor]` in [`class`]) overwrite it with their own vtable, just like
+    /// the constructor of a derived C++ class would.
+    ///
+    /// # Safety
+    /// The instance must be dropped (e.g. with [`core::ptr::drop_in_place`]) before the memory of
+    /// `slot` is reused or invalidated, as its address may have been registered during
+    /// initialization.
+    pub unsafe fn emplace(slot: &mut MaybeUninit<Self>, init: impl Init<C>) -> Pin<&mut Self>
+    where
+        C: ConcreteClass,
+    {
+        unsafe {
+            Self::emplace_with_vtable(
+                slot,
+                init,
+                <C::VmtInstance as internal::HasConst<C::VmtPtr>>::VALUE,
+            )
+        }
+    }
+
+    /// Creates an instance of the class from a layout without vtable pointer, using the vtable
+    /// `vmt`, e.g. one built with [`overrides::register_derived`] for classes deriving from C++
+    /// classes whose vtable is only known at runtime.
+    ///
+    /// # Safety
+    /// `vmt` must be a vtable of `C` suitable for instances created from Rust.
+    #[inline(always)]
+    pub unsafe fn with_vtable(layout: C::Layout<()>, vmt: C::VmtPtr) -> Self
+    where
+        C: ConcreteClass,
+    {
+        Self(ManuallyDrop::new(C::with_vtable(layout, vmt)))
+    }
+
+    /// Constructs an instance of the class in place in `slot` like [`Cls::emplace`], using the
+    /// vtable `vmt` like [`Cls::with_vtable`].
+    ///
+    /// # Safety
+    /// The requirements of both [`Cls::emplace`] and [`Cls::with_vtable`] apply.
+    pub unsafe fn emplace_with_vtable(
+        slot: &mut MaybeUninit<Self>,
+        init: impl Init<C>,
+        vmt: C::VmtPtr,
+    ) -> Pin<&mut Self>
+    where
+        C: ConcreteClass,
+    {
+        let this = slot.as_mut_ptr();
+        unsafe {
+            // The vtable pointer is the first field of every layout
+            (this as *mut C::VmtPtr).write(vmt);
+            init.init(NonNull::new_unchecked(this as *mut u8));
+            (this as *mut C::VmtPtr).write(vmt);
+            Pin::new_unchecked(&mut *this)
+        }
     }
 
     /// Creates an instance of the class given a fully populated layout.
@@ -168,7 +568,49 @@ impl<C: Class> Cls<C> {
     /// for the classes involved.
     #[inline(always)]
     pub unsafe fn from_layout(layout: C::Layout<C::VmtPtr>) -> Self {
-        Self(layout)
+        Self(ManuallyDrop::new(layout))
+    }
+}
+
+/// Formats the instance of `C` with the layout `layout` for the `Debug` implementations of the
+/// class wrappers, with its address, its main vtable and its data.
+fn debug_instance<C: Class>(
+    name: &str,
+    layout: &C::Layout<C::VmtPtr>,
+    f: &mut fmt::Formatter<'_>,
+) -> fmt::Result
+where
+    C::Layout<C::VmtPtr>: fmt::Debug,
+    C::Vmt: fmt::Debug,
+{
+    let mut debug = f.debug_struct(name);
+    debug
+        .field("class", &format_args!("{}", core::any::type_name::<C>()))
+        .field("address", &(layout as *const C::Layout<C::VmtPtr>));
+    if size_of::<C::VmtPtr>() == size_of::<*const ()>() {
+        // Only slots declared for `C` are shown, even if the vtable is the one of a derived class
+        let vmt: &'static C::Vmt = unsafe { core::mem::transmute_copy(&layout.vtable()) };
+        debug.field("vptr", &(vmt as *const C::Vmt)).field("vtable", vmt);
+    }
+    debug.field("layout", layout).finish()
+}
+
+impl<C: Class> fmt::Debug for Cls<C>
+where
+    C::Layout<C::VmtPtr>: fmt::Debug,
+    C::Vmt: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        debug_instance::<C>("Cls", &self.0, f)
+    }
+}
+
+impl<C: Class> Drop for Cls<C> {
+    fn drop(&mut self) {
+        match C::destructor(self.0.vtable()) {
+            Some(destructor) => unsafe { destructor.destruct(self as *mut Self as *mut u8) },
+            None => unsafe { ManuallyDrop::drop(&mut self.0) },
+        }
     }
 }
 
@@ -239,12 +681,12 @@ pub struct DynCls<C: Class>(C::Layout<C::VmtPtr>, [()]);
 impl<C: Class> FromThinPtr for DynCls<C> {
     #[inline(always)]
     unsafe fn from_thin_ptr(ptr: *const u8) -> *const Self {
-        core::slice::from_raw_parts(ptr, 0) as *const [u8] as *const Self
+        core::ptr::slice_from_raw_parts(ptr, 0) as *const Self
     }
 
     #[inline(always)]
     unsafe fn from_thin_ptr_mut(ptr: *mut u8) -> *mut Self {
-        core::slice::from_raw_parts_mut(ptr, 0) as *mut [u8] as *mut Self
+        core::ptr::slice_from_raw_parts_mut(ptr, 0) as *mut Self
     }
 }
 
@@ -305,12 +747,87 @@ impl<C: Class> DynCls<C> {
 
     /// Transforms `self` into a mutable reference to a concrete class.
     ///
+    /// See [`Self::try_as_concrete_mut`] for a checked alternative.
+    ///
     /// # SAFETY
     /// `self`'s concrete type must be `C`, and not a class derived from `C`.
     #[inline(always)]
     pub unsafe fn as_concrete_mut(&mut self) -> &mut Cls<C> {
         unsafe { &mut *(self as *mut _ as *mut _) }
     }
+
+    /// Returns true if the concrete type of `self` is `D`, i.e. if its vtable was registered for
+    /// `D` with [`registry::register_vtable`].
+    ///
+    /// `C` must be the primary base of `D` (at offset 0), since the vtable pointer of `D` can only
+    /// be read if it is shared with `C`. Returns false otherwise.
+    #[inline]
+    pub fn is_instance_of<D: SubclassOf<C>>(&self) -> bool {
+        if base_offset::<C, D>() != 0 {
+            return false;
+        }
+        let vtable = registry::vtable_address::<C>(self.0.vtable());
+        registry::is_registered_address::<D>(vtable)
+    }
+
+    /// Transforms `self` into a reference to the concrete class `D`, if it is an instance of `D`
+    /// as checked by [`Self::is_instance_of`].
+    #[inline]
+    pub fn try_as_concrete<D: SubclassOf<C>>(&self) -> Option<&Cls<D>> {
+        self.is_instance_of::<D>()
+            .then(|| unsafe { self.downcast::<D>().as_concrete() })
+    }
+
+    /// Transforms `self` into a mutable reference to the concrete class `D`, if it is an instance
+    /// of `D` as checked by [`Self::is_instance_of`].
+    ///
+    /// # SAFETY
+    /// `self`'s concrete type must be `D`, and not a class derived from `D`, which holds as long as
+    /// the vtables registered for `D` are only used by instances of `D`. The instance must also
+    /// not be moved out of the returned reference (e.g. with [`core::mem::swap`]) unless it can
+    /// be relocated bitwise, which most C++ classes can't.
+    #[inline]
+    pub unsafe fn try_as_concrete_mut<D: SubclassOf<C>>(&mut self) -> Option<&mut Cls<D>> {
+        match self.is_instance_of::<D>() {
+            true => Some(unsafe { self.downcast_mut::<D>().as_concrete_mut() }),
+            false => None,
+        }
+    }
+
+    /// Calls the function in the slot `INDEX` of the main vtable of `self` as a function of type
+    /// `F`, passing `self` as `this`. This gives access to the virtual methods the class
+    /// declaration does not name, e.g. in the gaps left by `#[offset]`.
+    ///
+    /// Fails to compile if `INDEX` is not below the number of slots declared for `C`.
+    ///
+    /// ```ignore
+    /// let area = unsafe { widget.call_slot::<unsafe extern "C" fn(*mut u8, i32) -> i64, 3>((2,)) };
+    /// ```
+    ///
+    /// # Safety
+    /// The slot must be bound to a function with the signature `F`, taking an instance of `C` as
+    /// `this`.
+    #[inline]
+    pub unsafe fn call_slot<F: SlotFn, const INDEX: usize>(&mut self, args: F::Args) -> F::Output {
+        const {
+            assert!(
+                INDEX < <C::Vmt as VmtSlots>::SLOT_COUNT,
+                "slot index out of the bounds of the vtable of the class"
+            )
+        };
+        let vtable = registry::vtable_address::<C>(self.0.vtable()) as *const *const ();
+        unsafe { F::call(vtable.add(INDEX).read(), self as *mut Self as *mut u8, args) }
+    }
+}
+
+impl<C: Class> fmt::Debug for DynCls<C>
+where
+    C::Layout<C::VmtPtr>: fmt::Debug,
+    C::Vmt: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        debug_instance::<C>("DynCls", &self.0, f)
+    }
 }
 
 impl<C: Class> internal::ClassWrapper for DynCls<C> {
@@ -390,11 +907,20 @@ pub type CRefMut<'a, C> = CRef<'a, C, Mut>;
 impl<'a, C: Class> Clone for CRef<'a, C> {
     #[inline(always)]
     fn clone(&self) -> Self {
-        Self(self.0, PhantomData)
+        *self
     }
 }
 impl<'a, C: Class> Copy for CRef<'a, C> {}
 
+impl<C: Class, A: Mutability> fmt::Debug for CRef<'_, C, A>
+where
+    DynCls<C>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
 // CRef `Deref` into a DynCls
 impl<'a, C: Class, A: Mutability> Deref for CRef<'a, C, A> {
     type Target = DynCls<C>;
@@ -477,8 +1003,25 @@ impl<'a, C: Class> From<CRefMut<'a, C>> for CRef<'a, C> {
     }
 }
 
+// Direct constructors, which unlike the `From` impls do not require `C: SubclassOf<C>` to be
+// provable (it is not for a generic `C`)
+impl<'a, C: Class> CRef<'a, C> {
+    /// Creates a [`CRef`] from a reference to a concrete instance of `C`.
+    #[inline(always)]
+    pub fn new(value: &'a Cls<C>) -> Self {
+        CRef(NonNull::from(value), PhantomData)
+    }
+}
+impl<'a, C: Class> CRefMut<'a, C> {
+    /// Creates a [`CRefMut`] from a mutable reference to a concrete instance of `C`.
+    #[inline(always)]
+    pub fn new_mut(value: &'a mut Cls<C>) -> Self {
+        CRef(NonNull::from(value), PhantomData)
+    }
+}
+
 // "Borrow" method on `CRefMut` to help using it as a normal mutable ref
-impl<'a, C: Class> CRefMut<'_, C> {
+impl<C: Class> CRefMut<'_, C> {
     /// "Borrows" this mutable reference, simulating the coercion of a &mut T to a &T.
     #[inline(always)]
     pub fn borrow(&self) -> CRef<'_, C> {
@@ -554,6 +1097,15 @@ impl<C: Class> DerefMut for CBox<C> {
     }
 }
 
+impl<C: Class, M: Mutability> fmt::Debug for CBox<C, M>
+where
+    DynCls<C>: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
 // AsRef/AsMut conversions from CBox to Cls and DynCls
 impl<B: Class, C: SubclassOf<B>, M: Mutability> AsRef<DynCls<B>> for CBox<C, M> {
     #[inline(always)]
@@ -574,6 +1126,75 @@ impl<B: Class, C: SubclassOf<B>> AsMut<DynCls<B>> for CBox<C> {
     }
 }
 
+/// Pointer types to instances of a subclass of `B` which can be returned by a `#[covariant]`
+/// override of a virtual method of `B`, which returns [`CovariantReturn::Base`].
+///
+/// This is the equivalent of a C++ override returning `Derived*` where the base method returns
+/// `Base*`. The returned pointer is upcast to `B` when called through the base vtable slot.
+///
+/// # SAFETY
+/// `Base` must be ABI-compatible with `Self`, and [`CovariantReturn::upcast_return`] must
+/// return a pointer to the `B` base of the same instance.
+pub unsafe trait CovariantReturn<B: Class> {
+    /// Return type of the base method.
+    type Base;
+
+    /// Upcasts the pointer to its `B` base.
+    fn upcast_return(self) -> Self::Base;
+}
+
+#[allow(private_bounds)]
+unsafe impl<'a, B: Class, D: SubclassOf<B>, A: Mutability> CovariantReturn<B> for CRef<'a, D, A> {
+    type Base = CRef<'a, B, A>;
+
+    #[inline(always)]
+    fn upcast_return(self) -> Self::Base {
+        let ptr = unsafe { self.0.cast::<u8>().add(base_offset::<B, D>()) };
+        CRef(ptr.cast(), PhantomData)
+    }
+}
+#[allow(private_bounds)]
+unsafe impl<B: Class, D: SubclassOf<B>, M: Mutability> CovariantReturn<B> for CBox<D, M> {
+    type Base = CBox<B, M>;
+
+    #[inline(always)]
+    fn upcast_return(self) -> Self::Base {
+        let ptr = unsafe { self.0.cast::<u8>().add(base_offset::<B, D>()) };
+        CBox(ptr.cast(), PhantomData)
+    }
+}
+unsafe impl<B: Class, D: SubclassOf<B>> CovariantReturn<B> for *const Cls<D> {
+    type Base = *const Cls<B>;
+
+    #[inline(always)]
+    fn upcast_return(self) -> Self::Base {
+        match self.is_null() {
+            true => core::ptr::null(),
+            false => unsafe { self.cast::<u8>().add(base_offset::<B, D>()).cast() },
+        }
+    }
+}
+unsafe impl<B: Class, D: SubclassOf<B>> CovariantReturn<B> for *mut Cls<D> {
+    type Base = *mut Cls<B>;
+
+    #[inline(always)]
+    fn upcast_return(self) -> Self::Base {
+        match self.is_null() {
+            true => core::ptr::null_mut(),
+            false => unsafe { self.cast::<u8>().add(base_offset::<B, D>()).cast() },
+        }
+    }
+}
+// Nullable pointers, relying on the `NonNull` option layout optimization
+unsafe impl<B: Class, T: CovariantReturn<B>> CovariantReturn<B> for Option<T> {
+    type Base = Option<T::Base>;
+
+    #[inline(always)]
+    fn upcast_return(self) -> Self::Base {
+        self.map(T::upcast_return)
+    }
+}
+
 /// Marker type used to provide virtual function implementations for a given type. Has the same
 /// layout as [`DynCls<C>`], which it can [`DerefMut`] into.
 #[repr(C)]
@@ -582,12 +1203,12 @@ pub struct Impl<C: Class>(DynCls<C>);
 impl<C: Class> FromThinPtr for Impl<C> {
     #[inline(always)]
     unsafe fn from_thin_ptr(ptr: *const u8) -> *const Self {
-        core::slice::from_raw_parts(ptr, 0) as *const [u8] as *const Self
+        core::ptr::slice_from_raw_parts(ptr, 0) as *const Self
     }
 
     #[inline(always)]
     unsafe fn from_thin_ptr_mut(ptr: *mut u8) -> *mut Self {
-        core::slice::from_raw_parts_mut(ptr, 0) as *mut [u8] as *mut Self
+        core::ptr::slice_from_raw_parts_mut(ptr, 0) as *mut Self
     }
 }
 
@@ -0,0 +1,516 @@
+//! Modules (executables and shared libraries) loaded in the current process.
+
+use core::{ffi::CStr, fmt, ptr::NonNull};
+
+/// A module (executable or shared library) loaded in the current process.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Module {
+    base: NonNull<u8>,
+}
+
+impl Module {
+    /// Returns the main executable module of the process.
+    pub fn main() -> Self {
+        Self {
+            base: sys::main_module_base(),
+        }
+    }
+
+    /// Returns the module whose image contains `address`, if any.
+    pub fn containing(address: *const ()) -> Option<Self> {
+        NonNull::new(sys::module_base_containing(address)).map(|base| Self { base })
+    }
+
+    /// Creates a module from the address at which its image is loaded.
+    ///
+    /// # Safety
+    /// `base` must point to the headers of an ELF or PE image mapped by the loader, which must
+    /// stay loaded for the rest of the program.
+    #[inline(always)]
+    pub const unsafe fn from_base(base: NonNull<u8>) -> Self {
+        Self { base }
+    }
+
+    /// Address at which the image of the module is loaded, which RVAs are relative to.
+    #[inline(always)]
+    pub fn base(self) -> *const u8 {
+        self.base.as_ptr()
+    }
+
+    /// Iterates over the memory sections of the module, parsed from the headers of its image.
+    ///
+    /// These are the loaded segments of an ELF image, or the sections of a PE image. Images of
+    /// other formats have no sections.
+    pub fn sections(self) -> Sections {
+        unsafe { Sections::parse(self.base()) }
+    }
+
+    /// Returns the section of the module containing `address`, if any.
+    pub fn section_containing(self, address: *const ()) -> Option<Section> {
+        self.sections().find(|section| section.contains(address))
+    }
+}
+
+/// A contiguous range of the memory of a loaded module.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Section {
+    address: NonNull<u8>,
+    len: usize,
+    readable: bool,
+    writable: bool,
+    executable: bool,
+}
+
+impl Section {
+    /// Address of the start of the section.
+    #[inline(always)]
+    pub fn address(self) -> *const u8 {
+        self.address.as_ptr()
+    }
+
+    /// Size of the section in memory, in bytes.
+    #[inline(always)]
+    pub fn len(self) -> usize {
+        self.len
+    }
+
+    #[inline(always)]
+    pub fn is_empty(self) -> bool {
+        self.len == 0
+    }
+
+    #[inline(always)]
+    pub fn is_readable(self) -> bool {
+        self.readable
+    }
+
+    #[inline(always)]
+    pub fn is_writable(self) -> bool {
+        self.writable
+    }
+
+    #[inline(always)]
+    pub fn is_executable(self) -> bool {
+        self.executable
+    }
+
+    /// Returns true if `address` is within the section.
+    pub fn contains(self, address: *const ()) -> bool {
+        (address as usize).wrapping_sub(self.address() as usize) < self.len
+    }
+
+    /// The memory of the section.
+    ///
+    /// # Safety
+    /// The section must be readable, and must not be written to while the slice is alive.
+    #[inline(always)]
+    pub unsafe fn bytes(self) -> &'static [u8] {
+        core::slice::from_raw_parts(self.address(), self.len)
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+enum ImageFormat {
+    Elf { load_bias: usize },
+    Pe,
+    Unknown,
+}
+
+/// Iterator over the sections of a [`Module`].
+#[derive(Clone, Debug)]
+pub struct Sections {
+    base: *const u8,
+    format: ImageFormat,
+    headers: *const u8,
+    header_size: usize,
+    remaining: usize,
+}
+
+#[inline(always)]
+unsafe fn read<T: Copy>(ptr: *const u8, offset: usize) -> T {
+    ptr.add(offset).cast::<T>().read_unaligned()
+}
+
+const PT_LOAD: u32 = 1;
+
+impl Sections {
+    /// Offsets of `p_type`, `p_flags`, `p_vaddr` and `p_memsz` in a program header.
+    #[cfg(target_pointer_width = "64")]
+    const PHDR_OFFSETS: [usize; 4] = [0, 4, 16, 40];
+    #[cfg(target_pointer_width = "32")]
+    const PHDR_OFFSETS: [usize; 4] = [0, 24, 8, 20];
+
+    unsafe fn parse(base: *const u8) -> Self {
+        let empty = Self {
+            base,
+            format: ImageFormat::Unknown,
+            headers: base,
+            header_size: 0,
+            remaining: 0,
+        };
+
+        match read::<[u8; 4]>(base, 0) {
+            [0x7f, b'E', b'L', b'F'] => {
+                // `e_phoff`, `e_phentsize` and `e_phnum`
+                let (phoff, phentsize, phnum) = match cfg!(target_pointer_width = "64") {
+                    true => (read::<u64>(base, 0x20) as usize, 0x36, 0x38),
+                    false => (read::<u32>(base, 0x1c) as usize, 0x2a, 0x2c),
+                };
+                let mut sections = Self {
+                    base,
+                    format: ImageFormat::Elf { load_bias: 0 },
+                    headers: base.add(phoff),
+                    header_size: read::<u16>(base, phentsize) as usize,
+                    remaining: read::<u16>(base, phnum) as usize,
+                };
+
+                // The image is loaded at the lowest virtual address of its segments
+                let [p_type, _, p_vaddr, _] = Self::PHDR_OFFSETS;
+                let min_vaddr = (0..sections.remaining)
+                    .map(|i| sections.headers.add(i * sections.header_size))
+                    .filter(|&phdr| read::<u32>(phdr, p_type) == PT_LOAD)
+                    .map(|phdr| read::<usize>(phdr, p_vaddr))
+                    .min()
+                    .unwrap_or(0);
+                sections.format = ImageFormat::Elf {
+                    load_bias: (base as usize).wrapping_sub(min_vaddr),
+                };
+                sections
+            }
+            [b'M', b'Z', ..] => {
+                let nt_headers = base.add(read::<u32>(base, 0x3c) as usize);
+                if read::<[u8; 4]>(nt_headers, 0) != *b"PE\0\0" {
+                    return empty;
+                }
+                let optional_header_size = read::<u16>(nt_headers, 20) as usize;
+                Self {
+                    base,
+                    format: ImageFormat::Pe,
+                    headers: nt_headers.add(24 + optional_header_size),
+                    header_size: 40,
+                    remaining: read::<u16>(nt_headers, 6) as usize,
+                }
+            }
+            _ => empty,
+        }
+    }
+}
+
+impl Iterator for Sections {
+    type Item = Section;
+
+    fn next(&mut self) -> Option<Section> {
+        while self.remaining > 0 {
+            let header = self.headers;
+            self.headers = self.headers.wrapping_add(self.header_size);
+            self.remaining -= 1;
+
+            let section = unsafe {
+                match self.format {
+                    ImageFormat::Elf { load_bias } => {
+                        let [p_type, p_flags, p_vaddr, p_memsz] = Self::PHDR_OFFSETS;
+                        if read::<u32>(header, p_type) != PT_LOAD {
+                            continue;
+                        }
+                        let flags = read::<u32>(header, p_flags);
+                        Section {
+                            address: NonNull::new_unchecked(
+                                load_bias.wrapping_add(read::<usize>(header, p_vaddr)) as *mut u8,
+                            ),
+                            len: read::<usize>(header, p_memsz),
+                            readable: flags & 4 != 0,
+                            writable: flags & 2 != 0,
+                            executable: flags & 1 != 0,
+                        }
+                    }
+                    ImageFormat::Pe => {
+                        let characteristics = read::<u32>(header, 36);
+                        Section {
+                            address: NonNull::new_unchecked(
+                                self.base.add(read::<u32>(header, 12) as usize) as *mut u8,
+                            ),
+                            len: read::<u32>(header, 8) as usize,
+                            readable: characteristics & 0x4000_0000 != 0,
+                            writable: characteristics & 0x8000_0000 != 0,
+                            executable: characteristics & 0x2000_0000 != 0,
+                        }
+                    }
+                    ImageFormat::Unknown => return None,
+                }
+            };
+            return Some(section);
+        }
+        None
+    }
+}
+
+/// Looks up the address of an exported symbol of the process by its (mangled) name.
+///
+/// On Windows, only the exports of the main executable are searched. Elsewhere, the symbol is
+/// searched in all modules loaded with global symbol visibility.
+pub fn find_symbol(name: &CStr) -> Option<NonNull<()>> {
+    NonNull::new(sys::find_symbol(name))
+}
+
+/// Location of an address in the image of a loaded module, found with [`locate`].
+///
+/// Its [`Display`](fmt::Display) implementation shows the file name of the module and the offset
+/// of the address in it, followed by the nearest symbol if one is known, e.g.
+/// `libgame.so+0x1f2e0 (_ZN5Enemy6attackEv+0x10)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Location {
+    /// Module containing the address.
+    pub module: Module,
+    /// Offset of the address from the base of the module.
+    pub offset: usize,
+    /// Path of the file the module was loaded from, if known.
+    pub path: Option<&'static CStr>,
+    /// Name and address of the nearest exported symbol at or before the address, if any.
+    pub symbol: Option<(&'static CStr, NonNull<()>)>,
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let utf8 = |name: &'static CStr| core::str::from_utf8(name.to_bytes()).unwrap_or("?");
+        match self.path.map(utf8) {
+            Some(path) => {
+                let file_name = path.rsplit(['/', '\\']).next().unwrap_or(path);
+                write!(f, "{file_name}+{:#x}", self.offset)?;
+            }
+            None => write!(f, "{:p}+{:#x}", self.module.base(), self.offset)?,
+        }
+        if let Some((name, address)) = self.symbol {
+            let offset = (self.module.base() as usize)
+                .wrapping_add(self.offset)
+                .wrapping_sub(address.as_ptr() as usize);
+            write!(f, " ({}+{offset:#x})", utf8(name))?;
+        }
+        Ok(())
+    }
+}
+
+/// Finds the module containing `address`, and the nearest exported symbol preceding it.
+///
+/// Paths and symbols are looked up with `dladdr` on Unix platforms, and are not available on
+/// Windows.
+pub fn locate(address: *const ()) -> Option<Location> {
+    let (base, path, symbol, symbol_address) = sys::locate(address);
+    let module = Module {
+        base: NonNull::new(base)?,
+    };
+    let to_cstr =
+        |s: *const core::ffi::c_char| (!s.is_null()).then(|| unsafe { CStr::from_ptr(s) });
+    Some(Location {
+        module,
+        offset: (address as usize).wrapping_sub(base as usize),
+        path: to_cstr(path).filter(|path| !path.is_empty()),
+        symbol: to_cstr(symbol).zip(NonNull::new(symbol_address)),
+    })
+}
+
+#[cfg(windows)]
+mod sys {
+    use core::{
+        ffi::{c_char, c_void, CStr},
+        ptr::{null, null_mut, NonNull},
+    };
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetModuleHandleW(name: *const u16) -> *mut c_void;
+        fn GetProcAddress(module: *mut c_void, name: *const c_char) -> *mut c_void;
+        fn GetModuleHandleExW(flags: u32, name: *const u16, module: *mut *mut c_void) -> i32;
+    }
+
+    const GET_MODULE_HANDLE_EX_FLAG_UNCHANGED_REFCOUNT: u32 = 2;
+    const GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS: u32 = 4;
+
+    pub fn main_module_base() -> NonNull<u8> {
+        NonNull::new(unsafe { GetModuleHandleW(null()) }.cast())
+            .expect("the main module should be loaded")
+    }
+
+    pub fn find_symbol(name: &CStr) -> *mut () {
+        unsafe { GetProcAddress(GetModuleHandleW(null()), name.as_ptr()) }.cast()
+    }
+
+    pub fn module_base_containing(address: *const ()) -> *mut u8 {
+        let mut module = null_mut();
+        let flags =
+            GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS | GET_MODULE_HANDLE_EX_FLAG_UNCHANGED_REFCOUNT;
+        unsafe { GetModuleHandleExW(flags, address.cast(), &mut module) };
+        module.cast()
+    }
+
+    pub fn locate(address: *const ()) -> (*mut u8, *const c_char, *const c_char, *mut ()) {
+        (module_base_containing(address), null(), null(), null_mut())
+    }
+}
+
+#[cfg(all(unix, target_vendor = "apple"))]
+mod sys {
+    use core::{
+        ffi::{c_char, c_int, c_void, CStr},
+        ptr::{null, null_mut, NonNull},
+    };
+
+    const RTLD_DEFAULT: *mut c_void = -2isize as *mut c_void;
+
+    /// `Dl_info`
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct DlInfo {
+        fname: *const c_char,
+        fbase: *mut c_void,
+        sname: *const c_char,
+        saddr: *mut c_void,
+    }
+
+    extern "C" {
+        fn _dyld_get_image_header(index: u32) -> *const c_void;
+        fn dlsym(handle: *mut c_void, name: *const c_char) -> *mut c_void;
+        fn dladdr(address: *const c_void, info: *mut DlInfo) -> c_int;
+    }
+
+    pub fn main_module_base() -> NonNull<u8> {
+        NonNull::new(unsafe { _dyld_get_image_header(0) } as *mut u8)
+            .expect("the main module should be loaded")
+    }
+
+    pub fn find_symbol(name: &CStr) -> *mut () {
+        unsafe { dlsym(RTLD_DEFAULT, name.as_ptr()) }.cast()
+    }
+
+    pub fn module_base_containing(address: *const ()) -> *mut u8 {
+        locate(address).0
+    }
+
+    pub fn locate(address: *const ()) -> (*mut u8, *const c_char, *const c_char, *mut ()) {
+        let mut info = DlInfo {
+            fname: core::ptr::null(),
+            fbase: core::ptr::null_mut(),
+            sname: core::ptr::null(),
+            saddr: core::ptr::null_mut(),
+        };
+        match unsafe { dladdr(address.cast(), &mut info) } {
+            0 => (null_mut(), null(), null(), null_mut()),
+            _ => (info.fbase.cast(), info.fname, info.sname, info.saddr.cast()),
+        }
+    }
+}
+
+#[cfg(all(unix, not(target_vendor = "apple")))]
+mod sys {
+    use core::{
+        ffi::{c_char, c_int, c_void, CStr},
+        ptr::{null, null_mut, NonNull},
+    };
+
+    const PT_LOAD: u32 = 1;
+
+    /// `Elf64_Phdr`
+    #[cfg(target_pointer_width = "64")]
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct ProgramHeader {
+        p_type: u32,
+        p_flags: u32,
+        p_offset: u64,
+        p_vaddr: u64,
+        p_paddr: u64,
+        p_filesz: u64,
+        p_memsz: u64,
+        p_align: u64,
+    }
+
+    /// `Elf32_Phdr`
+    #[cfg(target_pointer_width = "32")]
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct ProgramHeader {
+        p_type: u32,
+        p_offset: u32,
+        p_vaddr: u32,
+        p_paddr: u32,
+        p_filesz: u32,
+        p_memsz: u32,
+        p_flags: u32,
+        p_align: u32,
+    }
+
+    /// `Dl_info`
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct DlInfo {
+        fname: *const c_char,
+        fbase: *mut c_void,
+        sname: *const c_char,
+        saddr: *mut c_void,
+    }
+
+    /// Prefix of `struct dl_phdr_info`.
+    #[allow(dead_code)]
+    #[repr(C)]
+    struct DlPhdrInfo {
+        addr: usize,
+        name: *const c_char,
+        phdr: *const ProgramHeader,
+        phnum: u16,
+    }
+
+    extern "C" {
+        fn dl_iterate_phdr(
+            callback: unsafe extern "C" fn(*mut DlPhdrInfo, usize, *mut c_void) -> c_int,
+            data: *mut c_void,
+        ) -> c_int;
+        fn dlsym(handle: *mut c_void, name: *const c_char) -> *mut c_void;
+        fn dladdr(address: *const c_void, info: *mut DlInfo) -> c_int;
+    }
+
+    // The main program is always the first object visited by `dl_iterate_phdr`
+    unsafe extern "C" fn main_module_callback(
+        info: *mut DlPhdrInfo,
+        _size: usize,
+        data: *mut c_void,
+    ) -> c_int {
+        let info = &*info;
+        let headers = core::slice::from_raw_parts(info.phdr, info.phnum as usize);
+        let first_vaddr = headers
+            .iter()
+            .filter(|header| header.p_type == PT_LOAD)
+            .map(|header| header.p_vaddr as usize)
+            .min()
+            .unwrap_or(0);
+
+        *data.cast::<usize>() = info.addr.wrapping_add(first_vaddr);
+        1
+    }
+
+    pub fn main_module_base() -> NonNull<u8> {
+        let mut base = 0usize;
+        unsafe { dl_iterate_phdr(main_module_callback, (&mut base as *mut usize).cast()) };
+        NonNull::new(base as *mut u8).expect("the main module should be loaded")
+    }
+
+    pub fn find_symbol(name: &CStr) -> *mut () {
+        // `RTLD_DEFAULT`
+        unsafe { dlsym(null_mut(), name.as_ptr()) }.cast()
+    }
+
+    pub fn module_base_containing(address: *const ()) -> *mut u8 {
+        locate(address).0
+    }
+
+    pub fn locate(address: *const ()) -> (*mut u8, *const c_char, *const c_char, *mut ()) {
+        let mut info = DlInfo {
+            fname: core::ptr::null(),
+            fbase: core::ptr::null_mut(),
+            sname: core::ptr::null(),
+            saddr: core::ptr::null_mut(),
+        };
+        match unsafe { dladdr(address.cast(), &mut info) } {
+            0 => (null_mut(), null(), null(), null_mut()),
+            _ => (info.fbase.cast(), info.fname, info.sname, info.saddr.cast()),
+        }
+    }
+}
@@ -0,0 +1,327 @@
+//! Overriding the virtual methods of C++ classes at runtime, for instances created by C++.
+//!
+//! A Rust class `C` deriving from a C++ class `B` without adding data can override some of the
+//! methods of `B`. [`register`] builds a copy of the vtable of `B` found at runtime in which
+//! these methods are replaced by the Rust implementations of `C`. The methods `C` does not
+//! override, the destructor and the RTTI of the class are kept.
+//!
+//! Instances of `B` created by C++ are then turned into instances of `C` by [`apply`], which
+//! replaces their vtable pointer. It is meant to be called from a hook run on each new instance,
+//! e.g. a detour of the constructor of `B` (after calling the original) or an allocation
+//! callback. [`on_construct`] can be used directly as such a C callback.
+//!
+//! ```ignore
+//! #[repr(C)]
+//! pub struct Berserker;
+//!
+//! #[class]
+//! pub trait Berserker: Enemy_Meta {}
+//!
+//! #[class_impl]
+//! impl Enemy_Impl for Impl<Berserker> {
+//!     fn attack(&self) -> i64 {
+//!         self.upcast::<Enemy>().strength * 2
+//!     }
+//! }
+//!
+//! unsafe { overrides::register::<Berserker, Enemy>(enemy_vmt)? };
+//! // In the constructor hook:
+//! overrides::apply(enemy);
+//! ```
+//!
+//! Rust classes deriving from C++ classes whose vtable is only known at runtime can be
+//! instantiated from Rust in the same way: [`register_derived`] builds their vtable from the one
+//! of their C++ base, to be passed to [`Cls::with_vtable`](crate::Cls::with_vtable).
+//!
+//! Registration is thread-safe, and each vtable is built once. A class registered with both
+//! functions gets a distinct vtable from each.
+
+use core::{
+    any::TypeId,
+    fmt, hint,
+    mem::size_of,
+    ptr::{null_mut, NonNull},
+    sync::atomic::{AtomicBool, AtomicPtr, AtomicUsize, Ordering},
+};
+
+use crate::{
+    internal::HasConst, registry::vtable_address, Class, ClassLayout, ConcreteClass, DynCls,
+    SubclassOf,
+};
+
+/// Maximum number of classes whose overrides can be registered.
+pub const CAPACITY: usize = 64;
+
+/// Number of pointer-sized words available to store the vtables built by [`register`].
+pub const POOL_WORDS: usize = 4096;
+
+/// Number of words before the address a vtable pointer points to: the RTTI complete object
+/// locator with MSVC, the offset to top and the type info otherwise.
+#[cfg(target_env = "msvc")]
+const PREFIX_WORDS: usize = 1;
+#[cfg(not(target_env = "msvc"))]
+const PREFIX_WORDS: usize = 2;
+
+/// Error returned by [`register`] when there is no space left to register more overrides.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OverridesFull;
+
+impl fmt::Display for OverridesFull {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "no space left to register vtable overrides ({CAPACITY} classes, {POOL_WORDS} words)"
+        )
+    }
+}
+
+/// Registered overrides. `class` points to a function returning the [`TypeId`] of the class,
+/// `base` is the vtable that was copied and `vtable` the one built from it. `derived` is true
+/// for the vtables built by [`register_derived`].
+struct Entry {
+    class: AtomicPtr<()>,
+    derived: AtomicBool,
+    base: AtomicPtr<()>,
+    vtable: AtomicPtr<()>,
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY: Entry = Entry {
+    class: AtomicPtr::new(null_mut()),
+    derived: AtomicBool::new(false),
+    base: AtomicPtr::new(null_mut()),
+    vtable: AtomicPtr::new(null_mut()),
+};
+
+#[allow(clippy::declare_interior_mutable_const)]
+const ZERO: AtomicUsize = AtomicUsize::new(0);
+
+static ENTRIES: [Entry; CAPACITY] = [EMPTY; CAPACITY];
+static POOL: [AtomicUsize; POOL_WORDS] = [ZERO; POOL_WORDS];
+
+/// Number of published entries. Entries and the words of their vtables are written before
+/// being published, and never modified afterwards.
+static LEN: AtomicUsize = AtomicUsize::new(0);
+/// Number of words of the pool in use, only accessed while holding [`LOCK`].
+static POOL_LEN: AtomicUsize = AtomicUsize::new(0);
+/// Serializes registrations, so that a vtable is never built twice.
+static LOCK: AtomicBool = AtomicBool::new(false);
+
+fn type_id<C: Class>() -> TypeId {
+    TypeId::of::<C>()
+}
+
+fn published() -> &'static [Entry] {
+    &ENTRIES[..LEN.load(Ordering::Acquire)]
+}
+
+impl Entry {
+    fn is<C: Class>(&self, derived: bool) -> bool {
+        if self.derived.load(Ordering::Relaxed) != derived {
+            return false;
+        }
+        // SAFETY: `class` is only ever set to a `type_id::<C>` function pointer
+        let class: fn() -> TypeId =
+            unsafe { core::mem::transmute(self.class.load(Ordering::Relaxed)) };
+        class() == TypeId::of::<C>()
+    }
+}
+
+struct LockGuard;
+
+impl LockGuard {
+    fn lock() -> Self {
+        while LOCK
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            hint::spin_loop();
+        }
+        LockGuard
+    }
+}
+
+impl Drop for LockGuard {
+    fn drop(&mut self) {
+        LOCK.store(false, Ordering::Release);
+    }
+}
+
+/// Returns the vtable built by [`register`] for `C`, if any.
+pub fn vtable<C: Class>() -> Option<C::VmtPtr> {
+    find_class::<C>(false)
+}
+
+/// Returns the vtable built by [`register_derived`] for `C`, if any.
+pub fn derived_vtable<C: Class>() -> Option<C::VmtPtr> {
+    find_class::<C>(true)
+}
+
+fn find_class<C: Class>(derived: bool) -> Option<C::VmtPtr> {
+    let entry = published().iter().find(|entry| entry.is::<C>(derived))?;
+    let vtable = entry.vtable.load(Ordering::Relaxed);
+    Some(unsafe { core::mem::transmute_copy(&vtable) })
+}
+
+/// Builds the vtable of `C` from `base_vmt`, the vtable of instances of the C++ class `B`, by
+/// replacing the slots of the methods implemented in Rust by `C` and its Rust bases.
+///
+/// The slots of `C` beyond the vtable of `B`, which belong to virtual methods declared by
+/// classes between `B` and `C`, are entirely taken from the Rust implementations.
+///
+/// Only the first registration of `C` builds a vtable: later calls return it. It is distinct
+/// from the vtable built by [`register_derived`] for `C`, if any.
+///
+/// The vtable built has the slots declared for `C` only: slots of the C++ vtable past the last
+/// one declared by `C` and its bases are not copied.
+///
+/// # Panics
+/// If a method declared by a class between `B` and `C` is not implemented in Rust.
+///
+/// # Safety
+/// `base_vmt` must be the main vtable of instances whose concrete class is exactly `B`, and
+/// `B` must not have a virtual base. The vtables of `C` must be used by [`apply`] only.
+///
+/// Every slot that C++ code can call on the instances must be declared by `C` or its bases, up
+/// to the last one. Undeclared slots in between can be left to gaps with `#[offset]`, whose
+/// functions are copied from `base_vmt`. Calling a slot past the end of the vtable reads
+/// unrelated memory.
+pub unsafe fn register<C, B>(base_vmt: B::VmtPtr) -> Result<C::VmtPtr, OverridesFull>
+where
+    C: ConcreteClass + SubclassOf<B>,
+    B: Class,
+{
+    const {
+        assert!(
+            size_of::<C::Layout<C::VmtPtr>>() == size_of::<B::Layout<B::VmtPtr>>(),
+            "classes overriding C++ vtables at runtime cannot add data to their base"
+        )
+    };
+    let overrides = <C::OverrideVmtInstance<false> as HasConst<C::Vmt>>::VALUE;
+    unsafe { build::<C, B>(base_vmt, overrides, false) }
+}
+
+/// Builds the vtable of instances of `C` created from Rust, for a class `C` deriving from a C++
+/// class `B` whose vtable `base_vmt` is only known at runtime (e.g. found with
+/// [`rtti::find_class_vmt`](crate::rtti::find_class_vmt)).
+///
+/// Like with [`register`], the slots of the methods of `B` which are not implemented in Rust are
+/// taken from `base_vmt`, and only the first registration of `C` builds a vtable, which is
+/// distinct from the one built by [`register`] for `C`, if any. Instances are
+/// then created with [`Cls::with_vtable`](crate::Cls::with_vtable) or
+/// [`Cls::emplace_with_vtable`](crate::Cls::emplace_with_vtable). Unlike [`register`], `C` can
+/// add data to `B`, and its destructor drops the instance like the vtables generated by
+/// [`Cls::new`](crate::Cls::new): the destructor of `B` is not called, so `B` must have a trivial
+/// destructor if its data is built by a C++ constructor.
+///
+/// The RTTI of the vtable is the one of `B`.
+///
+/// # Panics
+/// If a method declared by a class between `B` and `C` is not implemented in Rust.
+///
+/// # Safety
+/// `base_vmt` must be the main vtable of a C++ class whose layout is the one of `B`, without a
+/// virtual base. Like with [`register`], every slot that C++ code can call on the instances must
+/// be declared by `C` or its bases, using gaps for the ones that are not named.
+pub unsafe fn register_derived<C, B>(base_vmt: B::VmtPtr) -> Result<C::VmtPtr, OverridesFull>
+where
+    C: ConcreteClass + SubclassOf<B>,
+    B: Class,
+{
+    let overrides = <C::OverrideVmtInstance<true> as HasConst<C::Vmt>>::VALUE;
+    unsafe { build::<C, B>(base_vmt, overrides, true) }
+}
+
+/// Builds and registers the vtable of `C` from `base_vmt` and the slots set in `overrides`. The
+/// vtable is used by [`apply`] unless `derived` is true.
+unsafe fn build<C: Class, B: Class>(
+    base_vmt: B::VmtPtr,
+    overrides: C::Vmt,
+    derived: bool,
+) -> Result<C::VmtPtr, OverridesFull> {
+    let _guard = LockGuard::lock();
+    if let Some(vmt) = find_class::<C>(derived) {
+        return Ok(vmt);
+    }
+
+    let len = LEN.load(Ordering::Relaxed);
+    let entry = ENTRIES.get(len).ok_or(OverridesFull)?;
+    let base_words = PREFIX_WORDS + size_of::<B::Vmt>() / size_of::<usize>();
+    let words = PREFIX_WORDS + size_of::<C::Vmt>() / size_of::<usize>();
+    let start = POOL_LEN.load(Ordering::Relaxed);
+    let vtable = POOL.get(start..start + words).ok_or(OverridesFull)?;
+
+    // SAFETY: Class vtables are made of pointer-sized function pointers
+    let overrides = unsafe {
+        core::slice::from_raw_parts(
+            &overrides as *const C::Vmt as *const usize,
+            words - PREFIX_WORDS,
+        )
+    };
+    let base = vtable_address::<B>(base_vmt) as *const usize;
+    for (i, word) in vtable.iter().enumerate() {
+        let value = match overrides.get(i.wrapping_sub(PREFIX_WORDS)) {
+            Some(&fun) if fun != 0 => fun,
+            Some(_) => {
+                assert!(
+                    i < base_words,
+                    "missing Rust implementation for slot {}",
+                    i - PREFIX_WORDS
+                );
+                unsafe { base.sub(PREFIX_WORDS).add(i).read() }
+            }
+            // RTTI of the C++ class
+            None => unsafe { base.sub(PREFIX_WORDS).add(i).read() },
+        };
+        word.store(value, Ordering::Relaxed);
+    }
+
+    let vtable = vtable[PREFIX_WORDS..].as_ptr() as *mut ();
+    entry.class.store(type_id::<C> as fn() -> TypeId as *mut (), Ordering::Relaxed);
+    entry.derived.store(derived, Ordering::Relaxed);
+    entry.base.store(base as *mut (), Ordering::Relaxed);
+    entry.vtable.store(vtable, Ordering::Relaxed);
+    POOL_LEN.store(start + words, Ordering::Relaxed);
+    LEN.store(len + 1, Ordering::Release);
+
+    Ok(unsafe { core::mem::transmute_copy(&vtable) })
+}
+
+/// Replaces the vtable of `instance` by the one built by [`register`] from it, if any. Returns
+/// whether it was replaced.
+///
+/// Only instances using a vtable passed to [`register`] as their main vtable are modified, so
+/// this can be called on every instance of a class hierarchy. If several classes override the
+/// same vtable, the first one registered is used.
+pub fn apply<B: Class>(instance: &mut DynCls<B>) -> bool {
+    match find(vtable_address::<B>(instance.0.vtable())) {
+        Some(vtable) => {
+            unsafe { *instance.0.vtable_mut() = core::mem::transmute_copy(&vtable) };
+            true
+        }
+        None => false,
+    }
+}
+
+/// Callback replacing the vtable of a newly constructed C++ object like [`apply`].
+///
+/// # Safety
+/// `this` must be null or point to a valid C++ object with a vtable, which is not accessed
+/// concurrently.
+pub unsafe extern "C" fn on_construct(this: *mut u8) {
+    let vptr = this as *mut *mut ();
+    if let Some(vtable) = NonNull::new(vptr).and_then(|vptr| find(unsafe { vptr.read() })) {
+        unsafe { vptr.write(vtable) };
+    }
+}
+
+/// Returns the vtable built by [`register`] from `base`.
+fn find(base: *mut ()) -> Option<*mut ()> {
+    if base.is_null() {
+        return None;
+    }
+    let entry = published().iter().find(|entry| {
+        !entry.derived.load(Ordering::Relaxed) && entry.base.load(Ordering::Relaxed) == base
+    })?;
+    Some(entry.vtable.load(Ordering::Relaxed))
+}
@@ -0,0 +1,114 @@
+//! Registry of the known vtables of classes, used to check the concrete type of an instance at
+//! runtime.
+//!
+//! Unlike C++'s `dynamic_cast`, this does not rely on RTTI: a class's vtables must be registered
+//! with [`register_vtable`] before instances of the class can be recognized. They usually come
+//! from a Rust instance created with [`Cls::new`](crate::Cls::new), or from a lookup in a loaded
+//! module, e.g. with [`rtti::find_class_vmt`](crate::rtti::find_class_vmt).
+//!
+//! The vtable generated for a non-generic Rust class is stored in a static, so all its instances
+//! created with [`Cls::new`](crate::Cls::new) share the same vtable pointer. Statics can't be
+//! generic, so the vtable of a generic class is a constant instead and is not guaranteed to have
+//! a unique address: instances created in different crates may not be recognized.
+
+use core::{
+    any::TypeId,
+    fmt,
+    ptr::null_mut,
+    sync::atomic::{AtomicPtr, Ordering},
+};
+
+use crate::Class;
+
+/// Maximum number of vtables which can be registered.
+pub const CAPACITY: usize = 256;
+
+/// Error returned by [`register_vtable`] when the registry is full.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegistryFull;
+
+impl fmt::Display for RegistryFull {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the vtable registry is full ({CAPACITY} entries)")
+    }
+}
+
+/// Registered vtable. `class` is claimed first and points to a function returning the
+/// [`TypeId`] of the class; `vtable` is published once `class` is set.
+struct Entry {
+    class: AtomicPtr<()>,
+    vtable: AtomicPtr<()>,
+}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const EMPTY: Entry = Entry {
+    class: AtomicPtr::new(null_mut()),
+    vtable: AtomicPtr::new(null_mut()),
+};
+
+static REGISTRY: [Entry; CAPACITY] = [EMPTY; CAPACITY];
+
+fn type_id<C: Class>() -> TypeId {
+    TypeId::of::<C>()
+}
+
+impl Entry {
+    fn is<C: Class>(&self, vtable: *mut ()) -> bool {
+        if self.vtable.load(Ordering::Acquire) != vtable {
+            return false;
+        }
+        let class = self.class.load(Ordering::Relaxed);
+        // SAFETY: `class` is only ever set to a `type_id::<C>` function pointer
+        let class: fn() -> TypeId = unsafe { core::mem::transmute(class) };
+        class() == TypeId::of::<C>()
+    }
+}
+
+/// Returns the address of the vtable pointed to by `vmt`.
+pub(crate) fn vtable_address<C: Class>(vmt: C::VmtPtr) -> *mut () {
+    const {
+        assert!(
+            size_of::<C::VmtPtr>() == size_of::<*mut ()>(),
+            "the class must have a vtable"
+        )
+    };
+    unsafe { core::mem::transmute_copy(&vmt) }
+}
+
+/// Registers `vmt` as a vtable of instances whose concrete class is exactly `C`.
+///
+/// Registering the same vtable for `C` more than once has no effect.
+///
+/// # Safety
+/// Any object using `vmt` as its main vtable must be a valid instance of `C`, and not of a class
+/// derived from `C`.
+pub unsafe fn register_vtable<C: Class>(vmt: C::VmtPtr) -> Result<(), RegistryFull> {
+    let vtable = vtable_address::<C>(vmt);
+    if is_registered::<C>(vmt) {
+        return Ok(());
+    }
+    let class = type_id::<C> as fn() -> TypeId as *mut ();
+    let entry = REGISTRY
+        .iter()
+        .find(|entry| {
+            entry
+                .class
+                .compare_exchange(null_mut(), class, Ordering::Relaxed, Ordering::Relaxed)
+                .is_ok()
+        })
+        .ok_or(RegistryFull)?;
+    entry.vtable.store(vtable, Ordering::Release);
+    Ok(())
+}
+
+/// Returns true if `vmt` was registered as a vtable of `C` with [`register_vtable`].
+pub fn is_registered<C: Class>(vmt: C::VmtPtr) -> bool {
+    is_registered_address::<C>(vtable_address::<C>(vmt))
+}
+
+pub(crate) fn is_registered_address<C: Class>(vtable: *mut ()) -> bool {
+    REGISTRY
+        .iter()
+        .take_while(|entry| !entry.class.load(Ordering::Relaxed).is_null())
+        .any(|entry| entry.is::<C>(vtable))
+}
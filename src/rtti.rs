@@ -0,0 +1,355 @@
+//! Lookup of the vtables of C++ classes by name, using the run-time type information (RTTI)
+//! stored in the memory of a loaded [`Module`].
+//!
+//! Both the Itanium C++ ABI (GCC, Clang) and MSVC RTTI layouts are supported, regardless of the
+//! image format of the module. Only the primary vtable of a class is found.
+
+use core::{fmt, mem::size_of, ptr::NonNull};
+
+use crate::{
+    module::{Module, Section},
+    Class,
+};
+
+/// Maximum length of a mangled class name.
+pub const MAX_NAME_LEN: usize = 256;
+
+/// Error returned by RTTI vtable lookups.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RttiError {
+    /// The demangled name is not a (possibly namespaced) identifier, or its mangled form is
+    /// longer than [`MAX_NAME_LEN`].
+    InvalidName,
+    /// No vtable of a class with the given name was found in the module.
+    NotFound,
+    /// A vtable was found, but the slot at the given index does not point to executable code.
+    ///
+    /// This happens when the class declares more virtual methods than the C++ class has.
+    InvalidSlot(usize),
+}
+
+impl fmt::Display for RttiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidName => f.write_str("invalid class name"),
+            Self::NotFound => f.write_str("vtable not found"),
+            Self::InvalidSlot(slot) => {
+                write!(
+                    f,
+                    "slot {slot} of the vtable does not point to executable code"
+                )
+            }
+        }
+    }
+}
+
+/// Mangled class name, stored inline.
+struct MangledName {
+    buf: [u8; MAX_NAME_LEN],
+    len: usize,
+}
+
+impl MangledName {
+    fn new() -> Self {
+        Self {
+            buf: [0; MAX_NAME_LEN],
+            len: 0,
+        }
+    }
+
+    fn push(&mut self, bytes: &[u8]) -> Result<(), RttiError> {
+        let end = self.len + bytes.len();
+        self.buf
+            .get_mut(self.len..end)
+            .ok_or(RttiError::InvalidName)?
+            .copy_from_slice(bytes);
+        self.len = end;
+        Ok(())
+    }
+
+    fn push_len(&mut self, len: usize) -> Result<(), RttiError> {
+        let mut digits = [0u8; 20];
+        let mut start = digits.len();
+        let mut len = len;
+        loop {
+            start -= 1;
+            digits[start] = b'0' + (len % 10) as u8;
+            len /= 10;
+            if len == 0 {
+                break;
+            }
+        }
+        self.push(&digits[start..])
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        &self.buf[..self.len]
+    }
+}
+
+/// Checks that `name` is a (possibly namespaced) identifier.
+fn check_name(name: &str) -> Result<(), RttiError> {
+    let is_ident = |part: &str| {
+        part.bytes().next().is_some_and(|c| !c.is_ascii_digit())
+            && part.bytes().all(|c| c.is_ascii_alphanumeric() || c == b'_')
+    };
+    match name.split("::").all(is_ident) {
+        true => Ok(()),
+        false => Err(RttiError::InvalidName),
+    }
+}
+
+/// Itanium C++ ABI name of a class type, e.g. `N4game6PlayerE` for `game::Player`.
+fn itanium_name(name: &str) -> Result<MangledName, RttiError> {
+    check_name(name)?;
+    let nested = name.contains("::");
+
+    let mut mangled = MangledName::new();
+    if nested {
+        mangled.push(b"N")?;
+    }
+    for part in name.split("::") {
+        mangled.push_len(part.len())?;
+        mangled.push(part.as_bytes())?;
+    }
+    if nested {
+        mangled.push(b"E")?;
+    }
+    Ok(mangled)
+}
+
+/// MSVC type descriptor name of a class or struct, e.g. `.?AVPlayer@game@@` for `class
+/// game::Player`.
+fn msvc_name(name: &str, is_struct: bool) -> Result<MangledName, RttiError> {
+    check_name(name)?;
+    let mut mangled = MangledName::new();
+    mangled.push(if is_struct { b".?AU" } else { b".?AV" })?;
+    for part in name.rsplit("::") {
+        mangled.push(part.as_bytes())?;
+        mangled.push(b"@")?;
+    }
+    mangled.push(b"@")?;
+    Ok(mangled)
+}
+
+const PTR_SIZE: usize = size_of::<usize>();
+
+fn readable_sections(module: Module) -> impl Iterator<Item = Section> {
+    module.sections().filter(|section| section.is_readable())
+}
+
+/// Iterates over the addresses of the pointer-aligned words of the readable sections of `module`
+/// which are equal to `value`.
+unsafe fn find_words(module: Module, value: usize) -> impl Iterator<Item = *const u8> {
+    readable_sections(module).flat_map(move |section| {
+        let start = section.address().align_offset(PTR_SIZE).min(section.len());
+        let words = core::slice::from_raw_parts(
+            section.address().add(start).cast::<usize>(),
+            (section.len() - start) / PTR_SIZE,
+        );
+        words
+            .iter()
+            .filter(move |&&word| word == value)
+            .map(|word| word as *const usize as *const u8)
+    })
+}
+
+/// Iterates over the addresses of the NUL-terminated strings `name` in the readable sections of
+/// `module`.
+unsafe fn find_strings(module: Module, name: &[u8]) -> impl Iterator<Item = *const u8> + '_ {
+    readable_sections(module).flat_map(move |section| {
+        let bytes = section.bytes();
+        bytes
+            .windows(name.len() + 1)
+            .enumerate()
+            .filter(move |(_, window)| window[..name.len()] == *name && window[name.len()] == 0)
+            .map(move |(i, _)| bytes[i..].as_ptr())
+    })
+}
+
+#[inline(always)]
+unsafe fn read_word(address: *const u8, offset: isize) -> usize {
+    address.offset(offset * PTR_SIZE as isize).cast::<usize>().read_unaligned()
+}
+
+/// Itanium C++ ABI: `typeinfo name` string -> `typeinfo` -> primary vtable.
+unsafe fn itanium_vtables(module: Module, name: &[u8]) -> impl Iterator<Item = NonNull<u8>> + '_ {
+    find_strings(module, name)
+        // The name is the second field of the `std::type_info` object
+        .flat_map(move |name| find_words(module, name as usize))
+        .map(|name_field| name_field.wrapping_sub(PTR_SIZE))
+        // The vtable is preceded by the `typeinfo` pointer and the offset to top, which is 0 in a
+        // primary vtable
+        .flat_map(move |type_info| find_words(module, type_info as usize))
+        .filter(|&type_info_field| read_word(type_info_field, -1) == 0)
+        .map(|type_info_field| NonNull::new_unchecked(type_info_field.add(PTR_SIZE) as *mut u8))
+}
+
+/// Returns true if `col` points to a complete object locator of a primary vtable for the type
+/// descriptor at `type_descriptor`.
+unsafe fn is_primary_locator(module: Module, col: *const u8, type_descriptor: *const u8) -> bool {
+    let field = |i: usize| col.add(4 * i).cast::<u32>().read_unaligned();
+    let rva = |ptr: *const u8| (ptr as usize).wrapping_sub(module.base() as usize) as u32;
+
+    // `signature`, `offset`, `cdOffset`, `pTypeDescriptor`, ... and on 64-bit, `pSelf`
+    match cfg!(target_pointer_width = "64") {
+        true => {
+            field(0) == 1
+                && field(1) == 0
+                && field(3) == rva(type_descriptor)
+                && field(5) == rva(col)
+        }
+        false => field(0) == 0 && field(1) == 0 && field(3) as usize == type_descriptor as usize,
+    }
+}
+
+/// MSVC: `TypeDescriptor` -> `RTTICompleteObjectLocator` -> vtable.
+unsafe fn msvc_vtables(module: Module, name: &[u8]) -> impl Iterator<Item = NonNull<u8>> + '_ {
+    find_strings(module, name)
+        // The name is preceded by the `type_info` vtable pointer and a reserved pointer
+        .map(|name| name.wrapping_sub(2 * PTR_SIZE))
+        .flat_map(move |type_descriptor| {
+            // `pTypeDescriptor` is the 4th field of the locator, and an RVA on 64-bit
+            let value = match cfg!(target_pointer_width = "64") {
+                true => (type_descriptor as usize).wrapping_sub(module.base() as usize) as u32,
+                false => type_descriptor as usize as u32,
+            };
+            readable_sections(module).flat_map(move |section| {
+                let bytes = section.bytes();
+                let start = section.address().align_offset(4).min(bytes.len());
+                bytes[start..]
+                    .chunks_exact(4)
+                    .filter(move |field| {
+                        u32::from_ne_bytes([field[0], field[1], field[2], field[3]]) == value
+                    })
+                    .map(|field| field.as_ptr().wrapping_sub(12))
+                    .filter(move |&col| is_primary_locator(module, col, type_descriptor))
+            })
+        })
+        // The locator is stored right before the first slot of the vtable
+        .flat_map(move |col| find_words(module, col as usize))
+        .map(|col_field| NonNull::new_unchecked(col_field.add(PTR_SIZE) as *mut u8))
+}
+
+/// Iterates over the candidate vtables of the class with the mangled name `name`, which is either
+/// an Itanium C++ ABI type name (e.g. `N4game6PlayerE`) or a MSVC type descriptor name (e.g.
+/// `.?AVPlayer@game@@`).
+unsafe fn vtables(module: Module, name: &[u8]) -> impl Iterator<Item = NonNull<u8>> + '_ {
+    let msvc = name.starts_with(b".?A");
+    let itanium = (!msvc).then(|| itanium_vtables(module, name));
+    let msvc = msvc.then(|| msvc_vtables(module, name));
+    itanium.into_iter().flatten().chain(msvc.into_iter().flatten())
+}
+
+/// Calls `f` with the mangled names of the class `name` in each ABI, returning the first result.
+fn with_mangled_names<T>(
+    name: &str,
+    mut f: impl FnMut(&[u8]) -> Option<T>,
+) -> Result<Option<T>, RttiError> {
+    let names = [
+        itanium_name(name)?,
+        msvc_name(name, false)?,
+        msvc_name(name, true)?,
+    ];
+    Ok(names.iter().find_map(|mangled| f(mangled.as_bytes())))
+}
+
+/// Finds the primary vtable of the C++ class with the mangled name `name` in `module`.
+///
+/// `name` is either an Itanium C++ ABI type name (e.g. `N4game6PlayerE`) or a MSVC type
+/// descriptor name (e.g. `.?AVPlayer@game@@`).
+///
+/// # Safety
+/// The readable sections of the module must not be written to while they are scanned.
+pub unsafe fn find_vtable_mangled(module: Module, name: &[u8]) -> Option<NonNull<u8>> {
+    vtables(module, name).next()
+}
+
+/// Finds the primary vtable of the C++ class with the demangled name `name` (e.g. `game::Player`)
+/// in `module`.
+///
+/// Names of class template instances are not supported and must be looked up with
+/// [`find_vtable_mangled`].
+///
+/// # Safety
+/// The readable sections of the module must not be written to while they are scanned.
+pub unsafe fn find_vtable(module: Module, name: &str) -> Result<NonNull<u8>, RttiError> {
+    with_mangled_names(name, |mangled| find_vtable_mangled(module, mangled))?
+        .ok_or(RttiError::NotFound)
+}
+
+/// Checks that each of the slots of `C::Vmt` in the vtable at `vtable` points to executable code.
+fn validate<C: Class>(module: Module, vtable: NonNull<u8>) -> Result<(), RttiError> {
+    let slots = size_of::<C::Vmt>() / PTR_SIZE;
+    let section = module
+        .section_containing(vtable.as_ptr() as *const ())
+        .ok_or(RttiError::InvalidSlot(0))?;
+
+    for slot in 0..slots {
+        let address = vtable.as_ptr().wrapping_add(slot * PTR_SIZE);
+        if !section.contains(address.wrapping_add(PTR_SIZE - 1) as *const ()) {
+            return Err(RttiError::InvalidSlot(slot));
+        }
+        let function = unsafe { read_word(address, 0) } as *const ();
+        let is_code = Module::containing(function)
+            .and_then(|module| module.section_containing(function))
+            .is_some_and(|section| section.is_executable());
+        if !is_code {
+            return Err(RttiError::InvalidSlot(slot));
+        }
+    }
+    Ok(())
+}
+
+unsafe fn vmt_ptr<C: Class>(vtable: NonNull<u8>) -> C::VmtPtr {
+    const {
+        assert!(
+            size_of::<C::VmtPtr>() == PTR_SIZE,
+            "the class must have a vtable"
+        )
+    };
+    core::mem::transmute_copy(&vtable)
+}
+
+/// Finds the primary vtable of the C++ class with the mangled name `name` in `module`, and
+/// returns it as a vtable pointer of `C` after checking that every slot of `C::Vmt` points to
+/// executable code.
+///
+/// # Safety
+/// The readable sections of the module must not be written to while they are scanned, and the
+/// vtable must be compatible with `C::Vmt`.
+pub unsafe fn find_class_vmt_mangled<C: Class>(
+    module: Module,
+    name: &[u8],
+) -> Result<C::VmtPtr, RttiError> {
+    let mut error = RttiError::NotFound;
+    for vtable in vtables(module, name) {
+        match validate::<C>(module, vtable) {
+            Ok(()) => return Ok(vmt_ptr::<C>(vtable)),
+            Err(e) => error = e,
+        }
+    }
+    Err(error)
+}
+
+/// Finds the primary vtable of the C++ class with the demangled name `name` in `module`, and
+/// returns it as a vtable pointer of `C` after checking that every slot of `C::Vmt` points to
+/// executable code.
+///
+/// # Safety
+/// The readable sections of the module must not be written to while they are scanned, and the
+/// vtable must be compatible with `C::Vmt`.
+pub unsafe fn find_class_vmt<C: Class>(module: Module, name: &str) -> Result<C::VmtPtr, RttiError> {
+    let mut error = RttiError::NotFound;
+    let found = with_mangled_names(name, |mangled| {
+        match find_class_vmt_mangled::<C>(module, mangled) {
+            Ok(vmt) => Some(vmt),
+            Err(RttiError::NotFound) => None,
+            Err(e) => {
+                error = e;
+                None
+            }
+        }
+    })?;
+    found.ok_or(error)
+}
@@ -0,0 +1,270 @@
+//! Signature ("array of bytes") scanning, to find vtables and functions in the memory of a loaded
+//! [`Module`] at runtime.
+//!
+//! ```ignore
+//! const GET_PLAYER: Pattern = Pattern::new("48 8B 05 ?? ?? ?? ?? 48 85 C0 74 ?? 48 8B 40 08");
+//!
+//! let mov = unsafe { Module::main().find_pattern(&GET_PLAYER) }.unwrap();
+//! let player_vmt = unsafe { scan::vmt::<Player>(scan::resolve_relative(mov, 3, 7)) };
+//! ```
+
+use core::{fmt, ptr::NonNull};
+
+use crate::{
+    module::{Module, Section},
+    Class,
+};
+
+/// Maximum length of a [`Pattern`], in bytes.
+pub const MAX_PATTERN_LEN: usize = 128;
+
+/// Error returned when parsing an invalid [`Pattern`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PatternError {
+    /// The pattern contains no bytes.
+    Empty,
+    /// The pattern is longer than [`MAX_PATTERN_LEN`] bytes.
+    TooLong,
+    /// The token starting at the given byte offset of the pattern string is not a valid byte.
+    InvalidByte(usize),
+}
+
+impl fmt::Display for PatternError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("empty pattern"),
+            Self::TooLong => write!(f, "pattern is longer than {MAX_PATTERN_LEN} bytes"),
+            Self::InvalidByte(offset) => write!(f, "invalid byte in pattern at offset {offset}"),
+        }
+    }
+}
+
+/// Byte pattern with wildcards, parsed from an IDA-style string such as `"E8 ?? ?? ?? ?? 48 8B"`.
+///
+/// `?` and `??` match any byte, while a `?` in place of a single hex digit (e.g. `4?`) matches any
+/// value of that nibble.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub struct Pattern {
+    bytes: [u8; MAX_PATTERN_LEN],
+    mask: [u8; MAX_PATTERN_LEN],
+    len: usize,
+}
+
+const fn hex_digit(c: u8) -> Option<(u8, u8)> {
+    match c {
+        b'0'..=b'9' => Some((c - b'0', 0xf)),
+        b'a'..=b'f' => Some((c - b'a' + 10, 0xf)),
+        b'A'..=b'F' => Some((c - b'A' + 10, 0xf)),
+        b'?' => Some((0, 0)),
+        _ => None,
+    }
+}
+
+impl Pattern {
+    /// Parses an IDA-style pattern string.
+    pub const fn parse(pattern: &str) -> Result<Self, PatternError> {
+        let s = pattern.as_bytes();
+        let mut parsed = Self {
+            bytes: [0; MAX_PATTERN_LEN],
+            mask: [0; MAX_PATTERN_LEN],
+            len: 0,
+        };
+
+        let mut i = 0;
+        while i < s.len() {
+            if s[i].is_ascii_whitespace() {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            while i < s.len() && !s[i].is_ascii_whitespace() {
+                i += 1;
+            }
+
+            let (byte, mask) = match (i - start, hex_digit(s[start])) {
+                (1, Some((_, 0))) => (0, 0),
+                (2, Some((high, high_mask))) => match hex_digit(s[start + 1]) {
+                    Some((low, low_mask)) => (high << 4 | low, high_mask << 4 | low_mask),
+                    None => return Err(PatternError::InvalidByte(start)),
+                },
+                _ => return Err(PatternError::InvalidByte(start)),
+            };
+            if parsed.len == MAX_PATTERN_LEN {
+                return Err(PatternError::TooLong);
+            }
+            parsed.bytes[parsed.len] = byte;
+            parsed.mask[parsed.len] = mask;
+            parsed.len += 1;
+        }
+
+        match parsed.len {
+            0 => Err(PatternError::Empty),
+            _ => Ok(parsed),
+        }
+    }
+
+    /// Parses an IDA-style pattern string. Meant to be used in constants.
+    ///
+    /// # Panics
+    /// If the pattern is invalid.
+    pub const fn new(pattern: &str) -> Self {
+        match Self::parse(pattern) {
+            Ok(parsed) => parsed,
+            Err(PatternError::Empty) => panic!("empty pattern"),
+            Err(PatternError::TooLong) => panic!("pattern is too long"),
+            Err(PatternError::InvalidByte(_)) => panic!("invalid byte in pattern"),
+        }
+    }
+
+    /// Creates a pattern matching exactly `bytes`.
+    ///
+    /// # Panics
+    /// If `bytes` is empty or longer than [`MAX_PATTERN_LEN`].
+    pub const fn from_bytes(bytes: &[u8]) -> Self {
+        assert!(!bytes.is_empty(), "empty pattern");
+        assert!(bytes.len() <= MAX_PATTERN_LEN, "pattern is too long");
+
+        let mut parsed = Self {
+            bytes: [0; MAX_PATTERN_LEN],
+            mask: [0xff; MAX_PATTERN_LEN],
+            len: bytes.len(),
+        };
+        let mut i = 0;
+        while i < bytes.len() {
+            parsed.bytes[i] = bytes[i];
+            i += 1;
+        }
+        parsed
+    }
+
+    /// Number of bytes matched by the pattern.
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Always false, as patterns cannot be empty.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns true if `bytes` starts with a match of the pattern.
+    #[inline]
+    pub fn matches(&self, bytes: &[u8]) -> bool {
+        bytes.len() >= self.len && (0..self.len).all(|i| bytes[i] & self.mask[i] == self.bytes[i])
+    }
+
+    /// Returns the offset of the first match of the pattern in `haystack`.
+    pub fn find(&self, haystack: &[u8]) -> Option<usize> {
+        self.find_iter(haystack).next()
+    }
+
+    /// Iterates over the offsets of all (possibly overlapping) matches of the pattern in
+    /// `haystack`.
+    pub fn find_iter<'a>(&'a self, haystack: &'a [u8]) -> impl Iterator<Item = usize> + 'a {
+        haystack
+            .windows(self.len)
+            .enumerate()
+            .filter(|(_, window)| self.matches(window))
+            .map(|(i, _)| i)
+    }
+}
+
+impl fmt::Debug for Pattern {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Pattern(\"")?;
+        for i in 0..self.len {
+            if i != 0 {
+                f.write_str(" ")?;
+            }
+            match self.mask[i] {
+                0 => f.write_str("??")?,
+                0xff => write!(f, "{:02X}", self.bytes[i])?,
+                0xf0 => write!(f, "{:X}?", self.bytes[i] >> 4)?,
+                _ => write!(f, "?{:X}", self.bytes[i] & 0xf)?,
+            }
+        }
+        f.write_str("\")")
+    }
+}
+
+impl Section {
+    /// Returns the address of the first match of `pattern` in the section.
+    ///
+    /// # Safety
+    /// The section must be readable, and must not be written to while it is scanned.
+    pub unsafe fn find_pattern(self, pattern: &Pattern) -> Option<NonNull<u8>> {
+        let offset = pattern.find(self.bytes())?;
+        Some(NonNull::new_unchecked(self.address().add(offset) as *mut u8))
+    }
+}
+
+impl Module {
+    /// Returns the address of the first match of `pattern` in the readable sections of the module.
+    ///
+    /// # Safety
+    /// The readable sections of the module must not be written to while they are scanned.
+    pub unsafe fn find_pattern(self, pattern: &Pattern) -> Option<NonNull<u8>> {
+        self.sections()
+            .filter(|section| section.is_readable())
+            .find_map(|section| section.find_pattern(pattern))
+    }
+
+    /// Returns the address of the first match of `pattern` in the executable sections of the
+    /// module.
+    ///
+    /// # Safety
+    /// The executable sections of the module must be readable, and must not be written to while
+    /// they are scanned.
+    pub unsafe fn find_code_pattern(self, pattern: &Pattern) -> Option<NonNull<u8>> {
+        self.sections()
+            .filter(|section| section.is_executable())
+            .find_map(|section| section.find_pattern(pattern))
+    }
+}
+
+/// Resolves the target of a relative operand, such as the RIP-relative memory operand of
+/// `lea rax, [rip + disp32]` or the target of `call rel32`.
+///
+/// The target is the address of the next instruction (`instruction + len`) plus the signed 32-bit
+/// displacement at `instruction + disp_offset`.
+///
+/// # Safety
+/// `instruction` must point to at least `disp_offset + 4` readable bytes, and the resulting
+/// address must be in bounds of the same module.
+#[inline]
+pub unsafe fn resolve_relative(
+    instruction: NonNull<u8>,
+    disp_offset: usize,
+    len: usize,
+) -> NonNull<u8> {
+    let disp = instruction.as_ptr().add(disp_offset).cast::<i32>().read_unaligned();
+    NonNull::new_unchecked(instruction.as_ptr().add(len).offset(disp as isize))
+}
+
+/// Reinterprets the address of the vtable of a class `C` found in memory as a typed reference.
+///
+/// # Safety
+/// `address` must point to a vtable of `C` (or of a class derived from it) which stays valid for
+/// the rest of the program.
+#[inline(always)]
+pub unsafe fn vmt<C: Class>(address: NonNull<u8>) -> &'static C::Vmt {
+    address.cast().as_ref()
+}
+
+/// Reinterprets the address of a function found in memory as a function pointer of type `F`.
+///
+/// # Safety
+/// `F` must be a function pointer type matching the signature and calling convention of the
+/// function at `address`.
+#[inline(always)]
+pub unsafe fn function<F: Copy>(address: NonNull<u8>) -> F {
+    const {
+        assert!(
+            size_of::<F>() == size_of::<*const ()>(),
+            "F must be a function pointer type"
+        )
+    };
+    core::mem::transmute_copy(&address)
+}
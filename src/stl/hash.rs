@@ -0,0 +1,216 @@
+use core::{fmt, marker::PhantomData};
+
+use super::{
+    iter::{Iter, MapIter},
+    list::MsvcList,
+    repr::NodeRepr,
+    CxxPair, DefaultStl, StlLayout,
+};
+
+/// Read-only view of a `std::unordered_set<K>` using the default hasher, equality predicate and
+/// allocator.
+///
+/// Also matches the layout of `std::unordered_multiset<K>`.
+#[repr(transparent)]
+pub struct CxxUnorderedSet<K: 'static, L: StlLayout = DefaultStl>(L::Hash<K>);
+
+/// Read-only view of a `std::unordered_multiset<K>`. Same as [`CxxUnorderedSet`].
+pub type CxxUnorderedMultiSet<K, L = DefaultStl> = CxxUnorderedSet<K, L>;
+
+impl<K: 'static, L: StlLayout> CxxUnorderedSet<K, L> {
+    /// Returns the number of elements in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the set contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the elements of the set, in unspecified order.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, L::Hash<K>> {
+        Iter::new(&self.0)
+    }
+}
+
+impl<'a, K: 'static, L: StlLayout> IntoIterator for &'a CxxUnorderedSet<K, L> {
+    type Item = &'a K;
+    type IntoIter = Iter<'a, K, L::Hash<K>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: 'static + fmt::Debug, L: StlLayout> fmt::Debug for CxxUnorderedSet<K, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// Read-only view of a `std::unordered_map<K, V>` using the default hasher, equality predicate
+/// and allocator.
+///
+/// Also matches the layout of `std::unordered_multimap<K, V>`.
+#[repr(transparent)]
+pub struct CxxUnorderedMap<K: 'static, V: 'static, L: StlLayout = DefaultStl>(
+    L::Hash<CxxPair<K, V>>,
+);
+
+/// Read-only view of a `std::unordered_multimap<K, V>`. Same as [`CxxUnorderedMap`].
+pub type CxxUnorderedMultiMap<K, V, L = DefaultStl> = CxxUnorderedMap<K, V, L>;
+
+impl<K: 'static, V: 'static, L: StlLayout> CxxUnorderedMap<K, V, L> {
+    /// Returns the number of elements in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the key-value pairs of the map, in unspecified order.
+    #[inline]
+    pub fn iter(&self) -> MapIter<'_, K, V, L::Hash<CxxPair<K, V>>> {
+        MapIter::new(&self.0)
+    }
+
+    /// Returns an iterator over the keys of the map, in unspecified order.
+    #[inline]
+    pub fn keys(&self) -> impl ExactSizeIterator<Item = &K> + Clone + '_ {
+        self.iter().map(|(k, _)| k)
+    }
+
+    /// Returns an iterator over the values of the map, in unspecified order.
+    #[inline]
+    pub fn values(&self) -> impl ExactSizeIterator<Item = &V> + Clone + '_ {
+        self.iter().map(|(_, v)| v)
+    }
+
+    /// Returns the value corresponding to `key`, if any.
+    ///
+    /// As the hasher is not available, this performs a linear search.
+    pub fn get(&self, key: &K) -> Option<&V>
+    where
+        K: PartialEq,
+    {
+        self.iter().find_map(|(k, v)| (k == key).then_some(v))
+    }
+}
+
+impl<'a, K: 'static, V: 'static, L: StlLayout> IntoIterator for &'a CxxUnorderedMap<K, V, L> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = MapIter<'a, K, V, L::Hash<CxxPair<K, V>>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: 'static + fmt::Debug, V: 'static + fmt::Debug, L: StlLayout> fmt::Debug
+    for CxxUnorderedMap<K, V, L>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Layout of `std::_Hash` in MSVC's STL, backing the unordered containers.
+///
+/// Elements are stored in a `std::list`, which buckets point into.
+#[repr(C)]
+pub struct MsvcHash<T> {
+    _max_bucket_size: f32,
+    list: MsvcList<T>,
+    _buckets: [usize; 3],
+    _mask: usize,
+    _max_idx: usize,
+}
+
+unsafe impl<T: 'static> NodeRepr<T> for MsvcHash<T> {
+    type Node = <MsvcList<T> as NodeRepr<T>>::Node;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    #[inline]
+    fn first(&self) -> *const Self::Node {
+        self.list.first()
+    }
+
+    #[inline]
+    unsafe fn next(&self, node: *const Self::Node) -> *const Self::Node {
+        self.list.next(node)
+    }
+
+    #[inline]
+    unsafe fn value<'a>(node: *const Self::Node) -> &'a T {
+        MsvcList::value(node)
+    }
+}
+
+/// Link part of a `std::_Hashtable` node in libstdc++.
+#[repr(C)]
+struct GnuHashNodeBase {
+    next: *const GnuHashNodeBase,
+}
+
+/// Node of a `std::_Hashtable` in libstdc++. It may be followed by the cached hash of the value,
+/// which is not accessed.
+#[repr(C)]
+pub struct GnuHashNode<T> {
+    base: GnuHashNodeBase,
+    value: T,
+}
+
+/// Layout of `std::_Hashtable` in libstdc++, backing the unordered containers.
+///
+/// Elements are stored in a singly linked list starting at `before_begin`, which buckets point
+/// into.
+#[repr(C)]
+pub struct GnuHash<T> {
+    _buckets: *const *const GnuHashNodeBase,
+    _bucket_count: usize,
+    before_begin: GnuHashNodeBase,
+    element_count: usize,
+    _max_load_factor: f32,
+    _next_resize: usize,
+    _single_bucket: *const GnuHashNodeBase,
+    _marker: PhantomData<T>,
+}
+
+unsafe impl<T: 'static> NodeRepr<T> for GnuHash<T> {
+    type Node = GnuHashNode<T>;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.element_count
+    }
+
+    #[inline]
+    fn first(&self) -> *const Self::Node {
+        self.before_begin.next as *const _
+    }
+
+    #[inline]
+    unsafe fn next(&self, node: *const Self::Node) -> *const Self::Node {
+        (*node).base.next as *const _
+    }
+
+    #[inline]
+    unsafe fn value<'a>(node: *const Self::Node) -> &'a T {
+        &(*node).value
+    }
+}
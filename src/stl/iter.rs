@@ -0,0 +1,246 @@
+use core::{iter::FusedIterator, marker::PhantomData, ptr::NonNull};
+
+use super::{repr::NodeRepr, CxxPair, CxxSharedPtr, CxxUniquePtr};
+use crate::{CBox, CRef, Class, Cls};
+
+/// Iterator over the elements of a node-based container.
+#[allow(private_bounds)]
+pub struct Iter<'a, T: 'static, R: NodeRepr<T>> {
+    container: &'a R,
+    node: *const R::Node,
+    remaining: usize,
+    _marker: PhantomData<&'a T>,
+}
+
+#[allow(private_bounds)]
+impl<'a, T: 'static, R: NodeRepr<T>> Iter<'a, T, R> {
+    #[inline]
+    pub(crate) fn new(container: &'a R) -> Self {
+        Self {
+            container,
+            node: container.first(),
+            remaining: container.len(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, T: 'static, R: NodeRepr<T>> Iterator for Iter<'a, T, R> {
+    type Item = &'a T;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        self.remaining -= 1;
+
+        let node = self.node;
+        if self.remaining != 0 {
+            self.node = unsafe { self.container.next(node) };
+        }
+        Some(unsafe { R::value(node) })
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl<T: 'static, R: NodeRepr<T>> ExactSizeIterator for Iter<'_, T, R> {}
+impl<T: 'static, R: NodeRepr<T>> FusedIterator for Iter<'_, T, R> {}
+
+impl<T: 'static, R: NodeRepr<T>> Clone for Iter<'_, T, R> {
+    fn clone(&self) -> Self {
+        Self { ..*self }
+    }
+}
+
+/// Iterator over the key-value pairs of a map.
+#[allow(private_bounds)]
+pub struct MapIter<'a, K: 'static, V: 'static, R: NodeRepr<CxxPair<K, V>>>(
+    Iter<'a, CxxPair<K, V>, R>,
+);
+
+#[allow(private_bounds)]
+impl<'a, K: 'static, V: 'static, R: NodeRepr<CxxPair<K, V>>> MapIter<'a, K, V, R> {
+    #[inline]
+    pub(crate) fn new(container: &'a R) -> Self {
+        Self(Iter::new(container))
+    }
+}
+
+impl<'a, K: 'static, V: 'static, R: NodeRepr<CxxPair<K, V>>> Iterator for MapIter<'a, K, V, R> {
+    type Item = (&'a K, &'a V);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.next().map(|pair| (&pair.first, &pair.second))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.0.size_hint()
+    }
+}
+
+impl<K: 'static, V: 'static, R: NodeRepr<CxxPair<K, V>>> ExactSizeIterator
+    for MapIter<'_, K, V, R>
+{
+}
+impl<K: 'static, V: 'static, R: NodeRepr<CxxPair<K, V>>> FusedIterator for MapIter<'_, K, V, R> {}
+
+impl<K: 'static, V: 'static, R: NodeRepr<CxxPair<K, V>>> Clone for MapIter<'_, K, V, R> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+/// Pointer-like types to class instances, as found in the containers of game object registries.
+///
+/// Nothing is assumed about the validity of the pointers, so iterating over containers of these
+/// requires [`ClassIterExt::classes_unchecked`]. Owning pointers also implement [`ClassPtr`].
+pub trait RawClassPtr {
+    /// The class pointed to.
+    type Class: Class;
+
+    /// Returns the pointer to the class instance, or [`None`] if the pointer is null.
+    fn class_ptr(&self) -> Option<NonNull<Cls<Self::Class>>>;
+}
+
+/// Owning pointers to class instances, which can be iterated over with [`ClassIterExt::classes`].
+///
+/// # Safety
+/// [`RawClassPtr::class_ptr`] must return either [`None`] or a pointer to a valid instance of
+/// [`RawClassPtr::Class`] which lives at least as long as `self`.
+pub unsafe trait ClassPtr: RawClassPtr {}
+
+impl<C: Class> RawClassPtr for *const Cls<C> {
+    type Class = C;
+
+    #[inline]
+    fn class_ptr(&self) -> Option<NonNull<Cls<C>>> {
+        NonNull::new(*self as *mut _)
+    }
+}
+impl<C: Class> RawClassPtr for *mut Cls<C> {
+    type Class = C;
+
+    #[inline]
+    fn class_ptr(&self) -> Option<NonNull<Cls<C>>> {
+        NonNull::new(*self)
+    }
+}
+impl<C: Class> RawClassPtr for NonNull<Cls<C>> {
+    type Class = C;
+
+    #[inline]
+    fn class_ptr(&self) -> Option<NonNull<Cls<C>>> {
+        Some(*self)
+    }
+}
+impl<C: Class> RawClassPtr for Option<NonNull<Cls<C>>> {
+    type Class = C;
+
+    #[inline]
+    fn class_ptr(&self) -> Option<NonNull<Cls<C>>> {
+        *self
+    }
+}
+impl<C: Class, M: crate::cref_sealed::Mutability> RawClassPtr for CBox<C, M> {
+    type Class = C;
+
+    #[inline]
+    fn class_ptr(&self) -> Option<NonNull<Cls<C>>> {
+        Some(self.0)
+    }
+}
+unsafe impl<C: Class, M: crate::cref_sealed::Mutability> ClassPtr for CBox<C, M> {}
+impl<C: Class, M: crate::cref_sealed::Mutability> RawClassPtr for Option<CBox<C, M>> {
+    type Class = C;
+
+    #[inline]
+    fn class_ptr(&self) -> Option<NonNull<Cls<C>>> {
+        self.as_ref().map(|b| b.0)
+    }
+}
+unsafe impl<C: Class, M: crate::cref_sealed::Mutability> ClassPtr for Option<CBox<C, M>> {}
+impl<C: Class> RawClassPtr for CxxUniquePtr<Cls<C>> {
+    type Class = C;
+
+    #[inline]
+    fn class_ptr(&self) -> Option<NonNull<Cls<C>>> {
+        NonNull::new(self.as_ptr())
+    }
+}
+unsafe impl<C: Class> ClassPtr for CxxUniquePtr<Cls<C>> {}
+impl<C: Class> RawClassPtr for CxxSharedPtr<Cls<C>> {
+    type Class = C;
+
+    #[inline]
+    fn class_ptr(&self) -> Option<NonNull<Cls<C>>> {
+        NonNull::new(self.as_ptr() as *mut _)
+    }
+}
+unsafe impl<C: Class> ClassPtr for CxxSharedPtr<Cls<C>> {}
+
+/// Iterator adapter turning references to class pointers into [`CRef`]s, skipping null pointers.
+///
+/// Created by [`ClassIterExt::classes`] and [`ClassIterExt::classes_unchecked`].
+#[derive(Clone)]
+pub struct Classes<I>(I);
+
+impl<'a, P, I> Iterator for Classes<I>
+where
+    P: RawClassPtr + 'a,
+    I: Iterator<Item = &'a P>,
+{
+    type Item = CRef<'a, P::Class>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0
+            .by_ref()
+            .find_map(|p| p.class_ptr())
+            .map(|p| CRef::new(unsafe { &*p.as_ptr() }))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.0.size_hint().1)
+    }
+}
+
+impl<'a, P, I> FusedIterator for Classes<I>
+where
+    P: RawClassPtr + 'a,
+    I: FusedIterator<Item = &'a P>,
+{
+}
+
+/// Extension trait for iterators over class pointers, such as the elements of a
+/// `std::vector<C*>` or the values of a `std::map<K, C*>`.
+pub trait ClassIterExt<'a, P: RawClassPtr + 'a>: Iterator<Item = &'a P> + Sized {
+    /// Turns the owning class pointers yielded by this iterator into [`CRef`]s, skipping null
+    /// pointers.
+    #[inline]
+    fn classes(self) -> Classes<Self>
+    where
+        P: ClassPtr,
+    {
+        Classes(self)
+    }
+
+    /// Turns the class pointers yielded by this iterator into [`CRef`]s, skipping null pointers.
+    ///
+    /// # Safety
+    /// Every non-null pointer yielded must point to a valid instance of the class, which is not
+    /// mutated nor freed while the [`CRef`]s borrowing the iterated container are alive.
+    #[inline]
+    unsafe fn classes_unchecked(self) -> Classes<Self> {
+        Classes(self)
+    }
+}
+
+impl<'a, P: RawClassPtr + 'a, I: Iterator<Item = &'a P>> ClassIterExt<'a, P> for I {}
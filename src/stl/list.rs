@@ -0,0 +1,134 @@
+use core::fmt;
+
+use super::{iter::Iter, repr::NodeRepr, DefaultStl, StlLayout};
+
+/// Read-only view of a `std::list<T>` using the default allocator.
+#[repr(transparent)]
+pub struct CxxList<T: 'static, L: StlLayout = DefaultStl>(L::List<T>);
+
+impl<T: 'static, L: StlLayout> CxxList<T, L> {
+    /// Returns the number of elements in the list.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the list contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the elements of the list, from front to back.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, T, L::List<T>> {
+        Iter::new(&self.0)
+    }
+
+    /// Returns the first element of the list, if any.
+    #[inline]
+    pub fn front(&self) -> Option<&T> {
+        self.iter().next()
+    }
+}
+
+impl<'a, T: 'static, L: StlLayout> IntoIterator for &'a CxxList<T, L> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T, L::List<T>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: 'static + fmt::Debug, L: StlLayout> fmt::Debug for CxxList<T, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+/// Node of a `std::list` in MSVC's STL. The list's head is an allocated sentinel node.
+#[repr(C)]
+pub struct MsvcListNode<T> {
+    next: *const MsvcListNode<T>,
+    prev: *const MsvcListNode<T>,
+    value: T,
+}
+
+/// Layout of `std::list` in MSVC's STL.
+#[repr(C)]
+pub struct MsvcList<T> {
+    head: *const MsvcListNode<T>,
+    size: usize,
+}
+
+unsafe impl<T: 'static> NodeRepr<T> for MsvcList<T> {
+    type Node = MsvcListNode<T>;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.size
+    }
+
+    #[inline]
+    fn first(&self) -> *const Self::Node {
+        unsafe { (*self.head).next }
+    }
+
+    #[inline]
+    unsafe fn next(&self, node: *const Self::Node) -> *const Self::Node {
+        (*node).next
+    }
+
+    #[inline]
+    unsafe fn value<'a>(node: *const Self::Node) -> &'a T {
+        &(*node).value
+    }
+}
+
+/// Link part of a `std::list` node in libstdc++.
+#[repr(C)]
+struct GnuListNodeBase {
+    next: *const GnuListNodeBase,
+    prev: *const GnuListNodeBase,
+}
+
+/// Node of a `std::list` in libstdc++.
+#[repr(C)]
+pub struct GnuListNode<T> {
+    base: GnuListNodeBase,
+    value: T,
+}
+
+/// Layout of `std::list` in libstdc++. The list's head is a sentinel node stored inline.
+#[repr(C)]
+pub struct GnuList<T> {
+    header: GnuListNodeBase,
+    size: usize,
+    _marker: core::marker::PhantomData<T>,
+}
+
+unsafe impl<T: 'static> NodeRepr<T> for GnuList<T> {
+    type Node = GnuListNode<T>;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.size
+    }
+
+    #[inline]
+    fn first(&self) -> *const Self::Node {
+        self.header.next as *const _
+    }
+
+    #[inline]
+    unsafe fn next(&self, node: *const Self::Node) -> *const Self::Node {
+        (*node).base.next as *const _
+    }
+
+    #[inline]
+    unsafe fn value<'a>(node: *const Self::Node) -> &'a T {
+        &(*node).value
+    }
+}
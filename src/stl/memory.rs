@@ -0,0 +1,125 @@
+use core::{
+    fmt,
+    ptr::NonNull,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+use crate::{CRef, CRefMut, Class, Cls};
+
+/// View of a `std::unique_ptr<T>` using the default deleter.
+///
+/// For pointers to classes, [`crate::CBox`] can be used instead to access the pointee as a
+/// [`crate::DynCls`], or [`CxxUniquePtr::as_cref`] to obtain a [`CRef`].
+#[repr(transparent)]
+pub struct CxxUniquePtr<T>(Option<NonNull<T>>);
+
+impl<T> CxxUniquePtr<T> {
+    /// Returns the raw pointer held by the `unique_ptr`, which may be null.
+    #[inline]
+    pub fn as_ptr(&self) -> *mut T {
+        self.0.map_or(core::ptr::null_mut(), NonNull::as_ptr)
+    }
+
+    /// Returns `true` if the `unique_ptr` is empty.
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        self.0.is_none()
+    }
+
+    /// Returns a reference to the pointee, if any.
+    #[inline]
+    pub fn get(&self) -> Option<&T> {
+        self.0.map(|p| unsafe { &*p.as_ptr() })
+    }
+
+    /// Returns a mutable reference to the pointee, if any.
+    #[inline]
+    pub fn get_mut(&mut self) -> Option<&mut T> {
+        self.0.map(|p| unsafe { &mut *p.as_ptr() })
+    }
+}
+
+impl<C: Class> CxxUniquePtr<Cls<C>> {
+    /// Returns a [`CRef`] to the owned class instance, if any.
+    #[inline]
+    pub fn as_cref(&self) -> Option<CRef<'_, C>> {
+        self.get().map(CRef::new)
+    }
+
+    /// Returns a [`CRefMut`] to the owned class instance, if any.
+    #[inline]
+    pub fn as_cref_mut(&mut self) -> Option<CRefMut<'_, C>> {
+        self.get_mut().map(CRefMut::new_mut)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for CxxUniquePtr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CxxUniquePtr").field(&self.get()).finish()
+    }
+}
+
+/// Header of a `std::shared_ptr` control block. Identical for both supported standard library
+/// implementations.
+#[repr(C)]
+struct RefCountBase {
+    _vtable: *const (),
+    uses: u32,
+    _weaks: u32,
+}
+
+/// View of a `std::shared_ptr<T>`.
+///
+/// As ownership of the pointee is shared, only immutable access is provided.
+#[repr(C)]
+pub struct CxxSharedPtr<T> {
+    ptr: Option<NonNull<T>>,
+    ctrl: Option<NonNull<RefCountBase>>,
+}
+
+impl<T> CxxSharedPtr<T> {
+    /// Returns the raw pointer held by the `shared_ptr`, which may be null.
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self.ptr.map_or(core::ptr::null(), |p| p.as_ptr())
+    }
+
+    /// Returns `true` if the `shared_ptr` holds a null pointer.
+    #[inline]
+    pub fn is_null(&self) -> bool {
+        self.ptr.is_none()
+    }
+
+    /// Returns a reference to the pointee, if any.
+    #[inline]
+    pub fn get(&self) -> Option<&T> {
+        self.ptr.map(|p| unsafe { &*p.as_ptr() })
+    }
+
+    /// Returns the number of `shared_ptr` instances managing the pointee, like
+    /// `std::shared_ptr::use_count`.
+    #[inline]
+    pub fn use_count(&self) -> usize {
+        self.ctrl.map_or(0, |c| unsafe {
+            let uses = AtomicU32::from_ptr(core::ptr::addr_of_mut!((*c.as_ptr()).uses));
+            uses.load(Ordering::Relaxed) as usize
+        })
+    }
+}
+
+impl<C: Class> CxxSharedPtr<Cls<C>> {
+    /// Returns a [`CRef`] to the shared class instance, if any.
+    #[inline]
+    pub fn as_cref(&self) -> Option<CRef<'_, C>> {
+        self.get().map(CRef::new)
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for CxxSharedPtr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CxxSharedPtr")
+            .field("ptr", &self.get())
+            .field("use_count", &self.use_count())
+            .finish()
+    }
+}
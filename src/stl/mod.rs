@@ -0,0 +1,151 @@
+//! Layout-compatible views of C++ standard library types.
+//!
+//! The types in this module have the exact memory layout of their C++ counterpart when the
+//! default allocator is used, so they can be used as fields of class data structs in place of
+//! opaque byte arrays. Two implementations of the standard library are supported, selected
+//! through the [`StlLayout`] type parameter:
+//! - [`MsvcStl`], Microsoft's STL as shipped with MSVC (release builds, i.e. with
+//!   `_ITERATOR_DEBUG_LEVEL=0`);
+//! - [`LibStdCxx`], GCC's libstdc++ using the C++11 ABI.
+//!
+//! When not specified, the layout defaults to [`DefaultStl`], the implementation normally used on
+//! the compilation target.
+//!
+//! # Ownership
+//! These types are *views*: the memory they manage is owned by C++ code and must be allocated and
+//! freed by it. As such, they cannot be constructed from Rust and do not run any destructor when
+//! dropped. They should only be accessed through references into C++-owned memory; some of them
+//! (e.g. a libstdc++ [`CxxString`] using its small string buffer) point into themselves and are
+//! invalidated when moved.
+//!
+//! Node-based containers (lists, trees and hash tables) only provide read-only traversal. When
+//! their elements are pointers to class instances (see [`RawClassPtr`]), [`ClassIterExt::classes`]
+//! and its unsafe counterpart for raw pointers can be used to iterate over them as
+//! [`CRef`](crate::CRef)s.
+
+mod hash;
+mod iter;
+mod list;
+mod memory;
+mod string;
+mod tree;
+mod vector;
+
+pub use hash::{CxxUnorderedMap, CxxUnorderedMultiMap, CxxUnorderedMultiSet, CxxUnorderedSet};
+pub use iter::{ClassIterExt, ClassPtr, Classes, Iter, MapIter, RawClassPtr};
+pub use list::CxxList;
+pub use memory::{CxxSharedPtr, CxxUniquePtr};
+pub use string::{
+    CapacityError, CxxBasicString, CxxString, CxxU16String, CxxU32String, CxxWString,
+};
+pub use tree::{CxxMap, CxxMultiMap, CxxMultiSet, CxxSet};
+pub use vector::CxxVector;
+
+/// Layout of `std::pair<K, V>`, the element type of maps.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CxxPair<K, V> {
+    pub first: K,
+    pub second: V,
+}
+
+mod sealed {
+    pub trait Sealed {}
+}
+
+pub(crate) mod repr {
+    /// Raw representation of a `std::basic_string` for a given standard library implementation.
+    ///
+    /// # Safety
+    /// Implementors must match the layout of the C++ type and report the buffer pointer, length
+    /// and capacity accurately.
+    pub unsafe trait StringRepr<Ch>: 'static {
+        fn as_ptr(&self) -> *const Ch;
+        fn as_mut_ptr(&mut self) -> *mut Ch;
+        fn len(&self) -> usize;
+        fn capacity(&self) -> usize;
+
+        /// # Safety
+        /// `len` must not exceed the capacity of the string.
+        unsafe fn set_len(&mut self, len: usize);
+    }
+
+    /// Raw representation of a node-based container for a given standard library implementation.
+    ///
+    /// # Safety
+    /// Implementors must match the layout of the C++ type. Starting from [`NodeRepr::first`] and
+    /// applying [`NodeRepr::next`] [`NodeRepr::len`] - 1 times must visit every element once.
+    pub unsafe trait NodeRepr<T>: 'static {
+        type Node;
+
+        fn len(&self) -> usize;
+        fn first(&self) -> *const Self::Node;
+
+        /// # Safety
+        /// `node` must be an element node of this container.
+        unsafe fn next(&self, node: *const Self::Node) -> *const Self::Node;
+
+        /// # Safety
+        /// `node` must be an element node of a container that outlives `'a`.
+        unsafe fn value<'a>(node: *const Self::Node) -> &'a T;
+    }
+}
+
+/// Character types supported by [`CxxBasicString`].
+pub trait CharType: 'static + Copy + Eq + Default + sealed::Sealed {}
+
+impl sealed::Sealed for u8 {}
+impl CharType for u8 {}
+impl sealed::Sealed for u16 {}
+impl CharType for u16 {}
+impl sealed::Sealed for u32 {}
+impl CharType for u32 {}
+
+/// Marker trait for a C++ standard library implementation, determining the layout of the types in
+/// the [`stl`](self) module.
+#[allow(private_bounds)]
+pub trait StlLayout: 'static + sealed::Sealed {
+    /// The type of `wchar_t`.
+    type WChar: CharType;
+
+    #[doc(hidden)]
+    type String<Ch: CharType>: repr::StringRepr<Ch>;
+    #[doc(hidden)]
+    type List<T: 'static>: repr::NodeRepr<T>;
+    #[doc(hidden)]
+    type Tree<T: 'static>: repr::NodeRepr<T>;
+    #[doc(hidden)]
+    type Hash<T: 'static>: repr::NodeRepr<T>;
+}
+
+/// Layout of Microsoft's STL, as shipped with MSVC.
+pub enum MsvcStl {}
+
+impl sealed::Sealed for MsvcStl {}
+impl StlLayout for MsvcStl {
+    type WChar = u16;
+    type String<Ch: CharType> = string::MsvcString<Ch>;
+    type List<T: 'static> = list::MsvcList<T>;
+    type Tree<T: 'static> = tree::MsvcTree<T>;
+    type Hash<T: 'static> = hash::MsvcHash<T>;
+}
+
+/// Layout of GCC's libstdc++, using the C++11 ABI (`_GLIBCXX_USE_CXX11_ABI=1`).
+pub enum LibStdCxx {}
+
+impl sealed::Sealed for LibStdCxx {}
+impl StlLayout for LibStdCxx {
+    type WChar = u32;
+    type String<Ch: CharType> = string::GnuString<Ch>;
+    type List<T: 'static> = list::GnuList<T>;
+    type Tree<T: 'static> = tree::GnuTree<T>;
+    type Hash<T: 'static> = hash::GnuHash<T>;
+}
+
+/// The standard library implementation normally used on the compilation target.
+#[cfg(target_env = "msvc")]
+pub type DefaultStl = MsvcStl;
+
+/// The standard library implementation normally used on the compilation target.
+#[cfg(not(target_env = "msvc"))]
+pub type DefaultStl = LibStdCxx;
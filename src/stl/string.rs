@@ -0,0 +1,320 @@
+use core::{ffi::CStr, fmt, mem::size_of};
+
+use super::{repr::StringRepr, CharType, DefaultStl, StlLayout};
+
+/// View of a `std::basic_string<Ch>` using the default allocator.
+///
+/// The string can be read and modified in place, but cannot grow beyond its current capacity as
+/// that would require allocating through the C++ allocator.
+#[repr(transparent)]
+pub struct CxxBasicString<Ch: CharType, L: StlLayout = DefaultStl>(L::String<Ch>);
+
+/// View of a `std::string`.
+pub type CxxString<L = DefaultStl> = CxxBasicString<u8, L>;
+
+/// View of a `std::wstring`.
+pub type CxxWString<L = DefaultStl> = CxxBasicString<<L as StlLayout>::WChar, L>;
+
+/// View of a `std::u16string`.
+pub type CxxU16String<L = DefaultStl> = CxxBasicString<u16, L>;
+
+/// View of a `std::u32string`.
+pub type CxxU32String<L = DefaultStl> = CxxBasicString<u32, L>;
+
+/// Error returned when writing more characters to a [`CxxBasicString`] than it can hold without
+/// reallocating.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CapacityError {
+    /// The number of characters that were to be written.
+    pub required: usize,
+    /// The capacity of the string.
+    pub capacity: usize,
+}
+
+impl fmt::Display for CapacityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "string capacity exceeded ({} characters required, capacity is {})",
+            self.required, self.capacity
+        )
+    }
+}
+
+impl<Ch: CharType, L: StlLayout> CxxBasicString<Ch, L> {
+    /// Returns the length of the string, in characters.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the string is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the number of characters the string can hold without reallocating, excluding the
+    /// null terminator.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.0.capacity()
+    }
+
+    /// Returns a pointer to the null-terminated character buffer of the string.
+    #[inline]
+    pub fn as_ptr(&self) -> *const Ch {
+        self.0.as_ptr()
+    }
+
+    /// Returns the characters of the string, excluding the null terminator.
+    #[inline]
+    pub fn as_slice(&self) -> &[Ch] {
+        unsafe { core::slice::from_raw_parts(self.0.as_ptr(), self.0.len()) }
+    }
+
+    /// Returns the characters of the string as a mutable slice, excluding the null terminator.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [Ch] {
+        unsafe { core::slice::from_raw_parts_mut(self.0.as_mut_ptr(), self.0.len()) }
+    }
+
+    /// Shortens the string to `len` characters. Has no effect if `len` is greater than the
+    /// current length.
+    pub fn truncate(&mut self, len: usize) {
+        if len < self.len() {
+            unsafe { self.set_len_terminated(len) }
+        }
+    }
+
+    /// Empties the string, keeping its capacity.
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+
+    /// Replaces the contents of the string with `chars`, provided they fit in its current
+    /// capacity.
+    pub fn try_assign(&mut self, chars: &[Ch]) -> Result<(), CapacityError> {
+        let capacity = self.capacity();
+        if chars.len() > capacity {
+            return Err(CapacityError {
+                required: chars.len(),
+                capacity,
+            });
+        }
+        unsafe {
+            core::ptr::copy(chars.as_ptr(), self.0.as_mut_ptr(), chars.len());
+            self.set_len_terminated(chars.len());
+        }
+        Ok(())
+    }
+
+    /// Appends `chars` to the string, provided the result fits in its current capacity.
+    pub fn try_push(&mut self, chars: &[Ch]) -> Result<(), CapacityError> {
+        let (len, capacity) = (self.len(), self.capacity());
+        if chars.len() > capacity - len {
+            return Err(CapacityError {
+                required: len + chars.len(),
+                capacity,
+            });
+        }
+        unsafe {
+            core::ptr::copy(chars.as_ptr(), self.0.as_mut_ptr().add(len), chars.len());
+            self.set_len_terminated(len + chars.len());
+        }
+        Ok(())
+    }
+
+    unsafe fn set_len_terminated(&mut self, len: usize) {
+        self.0.set_len(len);
+        self.0.as_mut_ptr().add(len).write(Ch::default());
+    }
+}
+
+impl<L: StlLayout> CxxBasicString<u8, L> {
+    /// Returns the bytes of the string, excluding the null terminator.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        self.as_slice()
+    }
+
+    /// Returns the string as a [`CStr`].
+    ///
+    /// If the string contains interior null bytes, the returned [`CStr`] stops at the first one.
+    #[inline]
+    pub fn as_c_str(&self) -> &CStr {
+        unsafe { CStr::from_ptr(self.as_ptr() as *const _) }
+    }
+
+    /// Returns the string as a `&str` if it is valid UTF-8.
+    #[inline]
+    pub fn to_str(&self) -> Result<&str, core::str::Utf8Error> {
+        core::str::from_utf8(self.as_bytes())
+    }
+
+    /// Replaces the contents of the string with `s`, provided it fits in its current capacity.
+    #[inline]
+    pub fn try_assign_str(&mut self, s: &str) -> Result<(), CapacityError> {
+        self.try_assign(s.as_bytes())
+    }
+}
+
+impl<Ch: CharType, L: StlLayout, L2: StlLayout> PartialEq<CxxBasicString<Ch, L2>>
+    for CxxBasicString<Ch, L>
+{
+    fn eq(&self, other: &CxxBasicString<Ch, L2>) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+impl<Ch: CharType, L: StlLayout> Eq for CxxBasicString<Ch, L> {}
+
+impl<Ch: CharType + core::hash::Hash, L: StlLayout> core::hash::Hash for CxxBasicString<Ch, L> {
+    fn hash<H: core::hash::Hasher>(&self, state: &mut H) {
+        self.as_slice().hash(state)
+    }
+}
+
+impl<Ch: CharType, L: StlLayout> PartialEq<[Ch]> for CxxBasicString<Ch, L> {
+    fn eq(&self, other: &[Ch]) -> bool {
+        self.as_slice() == other
+    }
+}
+
+impl<L: StlLayout> PartialEq<str> for CxxBasicString<u8, L> {
+    fn eq(&self, other: &str) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<L: StlLayout> PartialEq<&str> for CxxBasicString<u8, L> {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_bytes() == other.as_bytes()
+    }
+}
+
+impl<Ch: CharType + fmt::Debug, L: StlLayout> fmt::Debug for CxxBasicString<Ch, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (size_of::<Ch>() == 1).then(|| self.as_slice()) {
+            Some(chars) => {
+                // SAFETY: Ch is u8 when its size is 1
+                let bytes = unsafe { &*(chars as *const [Ch] as *const [u8]) };
+                match core::str::from_utf8(bytes) {
+                    Ok(s) => fmt::Debug::fmt(s, f),
+                    Err(_) => fmt::Debug::fmt(bytes, f),
+                }
+            }
+            None => fmt::Debug::fmt(self.as_slice(), f),
+        }
+    }
+}
+
+/// Size of the small string buffer, in bytes. The same for both implementations.
+const SSO_BYTES: usize = 16;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+union SsoBuf<Ch> {
+    buf: [u8; SSO_BYTES],
+    ptr: *mut Ch,
+    capacity: usize,
+}
+
+/// Layout of `std::basic_string` in MSVC's STL.
+#[repr(C)]
+pub struct MsvcString<Ch> {
+    bx: SsoBuf<Ch>,
+    size: usize,
+    res: usize,
+}
+
+impl<Ch> MsvcString<Ch> {
+    const BUF_SIZE: usize = match SSO_BYTES / size_of::<Ch>() {
+        0 => 1,
+        n => n,
+    };
+
+    #[inline]
+    fn is_large(&self) -> bool {
+        self.res >= Self::BUF_SIZE
+    }
+}
+
+unsafe impl<Ch: CharType> StringRepr<Ch> for MsvcString<Ch> {
+    #[inline]
+    fn as_ptr(&self) -> *const Ch {
+        match self.is_large() {
+            true => unsafe { self.bx.ptr },
+            false => unsafe { self.bx.buf.as_ptr() as *const Ch },
+        }
+    }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut Ch {
+        match self.is_large() {
+            true => unsafe { self.bx.ptr },
+            false => unsafe { self.bx.buf.as_mut_ptr() as *mut Ch },
+        }
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.size
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        self.res
+    }
+
+    #[inline]
+    unsafe fn set_len(&mut self, len: usize) {
+        self.size = len;
+    }
+}
+
+/// Layout of `std::basic_string` in libstdc++'s C++11 ABI.
+#[repr(C)]
+pub struct GnuString<Ch> {
+    ptr: *mut Ch,
+    len: usize,
+    local: SsoBuf<Ch>,
+}
+
+impl<Ch> GnuString<Ch> {
+    const LOCAL_CAPACITY: usize = (SSO_BYTES - 1) / size_of::<Ch>();
+
+    #[inline]
+    fn is_local(&self) -> bool {
+        core::ptr::eq(self.ptr as *const u8, unsafe { self.local.buf.as_ptr() })
+    }
+}
+
+unsafe impl<Ch: CharType> StringRepr<Ch> for GnuString<Ch> {
+    #[inline]
+    fn as_ptr(&self) -> *const Ch {
+        self.ptr
+    }
+
+    #[inline]
+    fn as_mut_ptr(&mut self) -> *mut Ch {
+        self.ptr
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn capacity(&self) -> usize {
+        match self.is_local() {
+            true => Self::LOCAL_CAPACITY,
+            false => unsafe { self.local.capacity },
+        }
+    }
+
+    #[inline]
+    unsafe fn set_len(&mut self, len: usize) {
+        self.len = len;
+    }
+}
@@ -0,0 +1,247 @@
+use core::{fmt, marker::PhantomData};
+
+use super::{
+    iter::{Iter, MapIter},
+    repr::NodeRepr,
+    CxxPair, DefaultStl, StlLayout,
+};
+
+/// Read-only view of a `std::set<K>` using the default comparator and allocator.
+///
+/// Also matches the layout of `std::multiset<K>`.
+#[repr(transparent)]
+pub struct CxxSet<K: 'static, L: StlLayout = DefaultStl>(L::Tree<K>);
+
+/// Read-only view of a `std::multiset<K>`. Same as [`CxxSet`].
+pub type CxxMultiSet<K, L = DefaultStl> = CxxSet<K, L>;
+
+impl<K: 'static, L: StlLayout> CxxSet<K, L> {
+    /// Returns the number of elements in the set.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the set contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the elements of the set, in ascending order.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, K, L::Tree<K>> {
+        Iter::new(&self.0)
+    }
+}
+
+impl<'a, K: 'static, L: StlLayout> IntoIterator for &'a CxxSet<K, L> {
+    type Item = &'a K;
+    type IntoIter = Iter<'a, K, L::Tree<K>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: 'static + fmt::Debug, L: StlLayout> fmt::Debug for CxxSet<K, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+/// Read-only view of a `std::map<K, V>` using the default comparator and allocator.
+///
+/// Also matches the layout of `std::multimap<K, V>`.
+#[repr(transparent)]
+pub struct CxxMap<K: 'static, V: 'static, L: StlLayout = DefaultStl>(L::Tree<CxxPair<K, V>>);
+
+/// Read-only view of a `std::multimap<K, V>`. Same as [`CxxMap`].
+pub type CxxMultiMap<K, V, L = DefaultStl> = CxxMap<K, V, L>;
+
+impl<K: 'static, V: 'static, L: StlLayout> CxxMap<K, V, L> {
+    /// Returns the number of elements in the map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator over the key-value pairs of the map, in ascending key order.
+    #[inline]
+    pub fn iter(&self) -> MapIter<'_, K, V, L::Tree<CxxPair<K, V>>> {
+        MapIter::new(&self.0)
+    }
+
+    /// Returns an iterator over the keys of the map, in ascending order.
+    #[inline]
+    pub fn keys(&self) -> impl ExactSizeIterator<Item = &K> + Clone + '_ {
+        self.iter().map(|(k, _)| k)
+    }
+
+    /// Returns an iterator over the values of the map, in ascending key order.
+    #[inline]
+    pub fn values(&self) -> impl ExactSizeIterator<Item = &V> + Clone + '_ {
+        self.iter().map(|(_, v)| v)
+    }
+
+    /// Returns the value corresponding to `key`, if any.
+    ///
+    /// As the comparator is not available, this performs a linear search.
+    pub fn get(&self, key: &K) -> Option<&V>
+    where
+        K: PartialEq,
+    {
+        self.iter().find_map(|(k, v)| (k == key).then_some(v))
+    }
+}
+
+impl<'a, K: 'static, V: 'static, L: StlLayout> IntoIterator for &'a CxxMap<K, V, L> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = MapIter<'a, K, V, L::Tree<CxxPair<K, V>>>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: 'static + fmt::Debug, V: 'static + fmt::Debug, L: StlLayout> fmt::Debug
+    for CxxMap<K, V, L>
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// Node of a `std::_Tree` (red-black tree) in MSVC's STL.
+///
+/// The tree's head is an allocated sentinel node whose `left`, `parent` and `right` are the
+/// leftmost, root and rightmost nodes. Leaves point to the head, which has `is_nil` set.
+#[repr(C)]
+pub struct MsvcTreeNode<T> {
+    left: *const MsvcTreeNode<T>,
+    parent: *const MsvcTreeNode<T>,
+    right: *const MsvcTreeNode<T>,
+    _color: u8,
+    is_nil: u8,
+    value: T,
+}
+
+/// Layout of `std::_Tree` in MSVC's STL, backing `std::set` and `std::map`.
+#[repr(C)]
+pub struct MsvcTree<T> {
+    head: *const MsvcTreeNode<T>,
+    size: usize,
+}
+
+unsafe impl<T: 'static> NodeRepr<T> for MsvcTree<T> {
+    type Node = MsvcTreeNode<T>;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.size
+    }
+
+    #[inline]
+    fn first(&self) -> *const Self::Node {
+        unsafe { (*self.head).left }
+    }
+
+    unsafe fn next(&self, mut node: *const Self::Node) -> *const Self::Node {
+        if (*(*node).right).is_nil == 0 {
+            node = (*node).right;
+            while (*(*node).left).is_nil == 0 {
+                node = (*node).left;
+            }
+            return node;
+        }
+
+        let mut parent = (*node).parent;
+        while (*parent).is_nil == 0 && node == (*parent).right {
+            node = parent;
+            parent = (*node).parent;
+        }
+        parent
+    }
+
+    #[inline]
+    unsafe fn value<'a>(node: *const Self::Node) -> &'a T {
+        &(*node).value
+    }
+}
+
+/// Link part of a `std::_Rb_tree` node in libstdc++.
+#[repr(C)]
+struct GnuRbNodeBase {
+    _color: u32,
+    parent: *const GnuRbNodeBase,
+    left: *const GnuRbNodeBase,
+    right: *const GnuRbNodeBase,
+}
+
+/// Node of a `std::_Rb_tree` in libstdc++.
+#[repr(C)]
+pub struct GnuRbNode<T> {
+    base: GnuRbNodeBase,
+    value: T,
+}
+
+/// Layout of `std::_Rb_tree` in libstdc++, backing `std::set` and `std::map`.
+///
+/// The tree's header is a sentinel node stored inline whose `parent`, `left` and `right` are the
+/// root, leftmost and rightmost nodes. It is preceded by the (empty) comparator.
+#[repr(C)]
+pub struct GnuTree<T> {
+    _compare: usize,
+    header: GnuRbNodeBase,
+    count: usize,
+    _marker: PhantomData<T>,
+}
+
+unsafe impl<T: 'static> NodeRepr<T> for GnuTree<T> {
+    type Node = GnuRbNode<T>;
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.count
+    }
+
+    #[inline]
+    fn first(&self) -> *const Self::Node {
+        self.header.left as *const _
+    }
+
+    unsafe fn next(&self, node: *const Self::Node) -> *const Self::Node {
+        // Same as `_Rb_tree_increment`
+        let mut node = node as *const GnuRbNodeBase;
+        if !(*node).right.is_null() {
+            node = (*node).right;
+            while !(*node).left.is_null() {
+                node = (*node).left;
+            }
+            return node as *const _;
+        }
+
+        let mut parent = (*node).parent;
+        while node == (*parent).right {
+            node = parent;
+            parent = (*parent).parent;
+        }
+        if (*node).right != parent {
+            node = parent;
+        }
+        node as *const _
+    }
+
+    #[inline]
+    unsafe fn value<'a>(node: *const Self::Node) -> &'a T {
+        &(*node).value
+    }
+}
@@ -0,0 +1,104 @@
+use core::{
+    fmt,
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+};
+
+/// View of a `std::vector<T>` using the default allocator.
+///
+/// Both supported standard library implementations store a vector as three pointers to the
+/// start, end and end of capacity of its buffer. The vector [`Deref`]s into a slice of its
+/// elements, which can be modified in place.
+#[repr(C)]
+pub struct CxxVector<T> {
+    first: *mut T,
+    last: *mut T,
+    end: *mut T,
+}
+
+impl<T> CxxVector<T> {
+    /// Returns the number of elements in the vector.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match size_of::<T>() {
+            0 => 0,
+            size => (self.last as usize - self.first as usize) / size,
+        }
+    }
+
+    /// Returns `true` if the vector contains no elements.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.first == self.last
+    }
+
+    /// Returns the number of elements the vector can hold without reallocating.
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        match size_of::<T>() {
+            0 => 0,
+            size => (self.end as usize - self.first as usize) / size,
+        }
+    }
+
+    /// Returns a pointer to the buffer of the vector, which may be null if it has never
+    /// allocated.
+    #[inline]
+    pub fn as_ptr(&self) -> *const T {
+        self.first
+    }
+
+    /// Returns the elements of the vector.
+    #[inline]
+    pub fn as_slice(&self) -> &[T] {
+        let first = NonNull::new(self.first).unwrap_or(NonNull::dangling());
+        unsafe { core::slice::from_raw_parts(first.as_ptr(), self.len()) }
+    }
+
+    /// Returns the elements of the vector as a mutable slice.
+    #[inline]
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
+        let first = NonNull::new(self.first).unwrap_or(NonNull::dangling());
+        unsafe { core::slice::from_raw_parts_mut(first.as_ptr(), self.len()) }
+    }
+}
+
+impl<T> Deref for CxxVector<T> {
+    type Target = [T];
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+impl<T> DerefMut for CxxVector<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.as_mut_slice()
+    }
+}
+
+impl<'a, T> IntoIterator for &'a CxxVector<T> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_slice().iter()
+    }
+}
+impl<'a, T> IntoIterator for &'a mut CxxVector<T> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.as_mut_slice().iter_mut()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for CxxVector<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
@@ -0,0 +1,195 @@
+//! Weak references to class instances owned by an external handle system.
+//!
+//! Game engines rarely hand out long-lived pointers to their objects. Instead, they use handle
+//! tables (an index and a generation counter) or weak references that can be invalidated at any
+//! point, e.g. when an object is destroyed at the end of a frame. Holding a [`CRef`] across such
+//! a boundary leaves it dangling.
+//!
+//! A [`CWeak`] stores the handle instead of a pointer. It can only be turned into a [`CRef`]
+//! through a [`HandleResolver`], and the resulting reference borrows the resolver. This ties the
+//! reference's lifetime to the handle system rather than to the weak pointer itself.
+
+use core::{marker::PhantomData, ptr::NonNull};
+
+use crate::{CRef, CRefMut, Class, Cls, SubclassOf};
+
+/// Trait implemented by handle systems which can resolve handles of type `H` to live instances of
+/// (a subclass of) `C`.
+///
+/// # Safety
+/// If [`HandleResolver::resolve`] returns a pointer, it must be well aligned and point to a valid
+/// instance of `C` which remains alive for as long as the resolver is borrowed. Furthermore, the
+/// instance must not be accessed mutably through any other means while the resolver is borrowed
+/// mutably.
+pub unsafe trait HandleResolver<C: Class, H: ?Sized> {
+    /// Attempts to resolve `handle` to an instance of `C`. Returns [`None`] if the handle has been
+    /// invalidated.
+    fn resolve(&self, handle: &H) -> Option<NonNull<Cls<C>>>;
+}
+
+/// [`HandleResolver`] implemented using a closure.
+pub struct FnResolver<F>(F);
+
+impl<F> FnResolver<F> {
+    /// Wraps a closure resolving handles into a [`HandleResolver`].
+    ///
+    /// # Safety
+    /// The closure must uphold the invariants of [`HandleResolver::resolve`].
+    #[inline(always)]
+    pub const unsafe fn new(fun: F) -> Self {
+        Self(fun)
+    }
+}
+
+unsafe impl<C, H, F> HandleResolver<C, H> for FnResolver<F>
+where
+    C: Class,
+    H: ?Sized,
+    F: Fn(&H) -> Option<NonNull<Cls<C>>>,
+{
+    #[inline(always)]
+    fn resolve(&self, handle: &H) -> Option<NonNull<Cls<C>>> {
+        (self.0)(handle)
+    }
+}
+
+/// Generic handle made of a slot index and a generation counter, as used by most handle tables.
+///
+/// Provided for convenience; any type can be used as the handle of a [`CWeak`].
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct GenHandle {
+    /// The index of the slot holding the object.
+    pub index: u32,
+    /// The generation of the slot at the time the handle was created.
+    pub generation: u32,
+}
+
+impl GenHandle {
+    /// Creates a handle from an index and generation.
+    #[inline(always)]
+    pub const fn new(index: u32, generation: u32) -> Self {
+        Self { index, generation }
+    }
+}
+
+/// Weak reference to an instance of `C` (or any of its subclasses), stored as a handle of type
+/// `H`.
+///
+/// The handle resolves to an instance of `D`, which is `C` unless the weak reference was
+/// obtained through [`CWeak::upcast`]. Dereferencing requires resolving the handle through a
+/// [`HandleResolver<D, H>`], which yields a [`CRef`] or [`CRefMut`] borrowing the resolver.
+///
+/// # FFI considerations
+/// [`CWeak`] is `repr(transparent)` over `H`, so it can be used directly as a field of a class
+/// layout if `H` matches the layout of the C++ handle type.
+#[repr(transparent)]
+pub struct CWeak<C: Class, H, D: SubclassOf<C> = C>(H, WeakMarker<C, D>);
+
+// A weak reference does not own anything, and can be sent between threads if its handle can.
+type WeakMarker<C, D> = PhantomData<fn() -> (Cls<C>, Cls<D>)>;
+
+impl<C: Class, H, D: SubclassOf<C>> CWeak<C, H, D> {
+    /// Creates a weak reference from a handle.
+    #[inline(always)]
+    pub const fn new(handle: H) -> Self {
+        Self(handle, PhantomData)
+    }
+
+    /// Gets a reference to the underlying handle.
+    #[inline(always)]
+    pub const fn handle(&self) -> &H {
+        &self.0
+    }
+
+    /// Consumes the weak reference, returning the underlying handle.
+    #[inline(always)]
+    pub fn into_handle(self) -> H {
+        self.0
+    }
+
+    /// Attempts to resolve the handle, returning a reference to the instance if it is still alive.
+    ///
+    /// The reference borrows `resolver`, which prevents it from being held past the point where
+    /// the handle system may invalidate it.
+    #[inline(always)]
+    pub fn upgrade<'a, R>(&self, resolver: &'a R) -> Option<CRef<'a, C>>
+    where
+        R: HandleResolver<D, H> + ?Sized,
+    {
+        resolver.resolve(&self.0).map(|ptr| unsafe { &*ptr.as_ptr() }.into())
+    }
+
+    /// Attempts to resolve the handle, returning a mutable reference to the instance if it is
+    /// still alive.
+    ///
+    /// The reference mutably borrows `resolver`, so at most one instance can be mutably accessed
+    /// through a given resolver at a time.
+    #[inline(always)]
+    pub fn upgrade_mut<'a, R>(&self, resolver: &'a mut R) -> Option<CRefMut<'a, C>>
+    where
+        R: HandleResolver<D, H> + ?Sized,
+    {
+        resolver.resolve(&self.0).map(|ptr| unsafe { &mut *ptr.as_ptr() }.into())
+    }
+
+    /// Returns `true` if the handle currently resolves to an instance.
+    #[inline(always)]
+    pub fn is_alive<R>(&self, resolver: &R) -> bool
+    where
+        R: HandleResolver<D, H> + ?Sized,
+    {
+        resolver.resolve(&self.0).is_some()
+    }
+
+    /// Upcast to a weak reference to a base type.
+    ///
+    /// The handle is kept as-is and must still be resolved through a [`HandleResolver<D, H>`].
+    #[inline(always)]
+    pub fn upcast<B: Class>(self) -> CWeak<B, H, D>
+    where
+        D: SubclassOf<B>,
+    {
+        CWeak(self.0, PhantomData)
+    }
+
+    /// Upcast to a weak reference to a base type, copying the handle.
+    #[inline(always)]
+    pub fn upcast_ref<B: Class>(&self) -> CWeak<B, H, D>
+    where
+        D: SubclassOf<B>,
+        H: Clone,
+    {
+        CWeak(self.0.clone(), PhantomData)
+    }
+}
+
+impl<C: Class, H, D: SubclassOf<C>> From<H> for CWeak<C, H, D> {
+    #[inline(always)]
+    fn from(handle: H) -> Self {
+        Self::new(handle)
+    }
+}
+
+impl<C: Class, H: Clone, D: SubclassOf<C>> Clone for CWeak<C, H, D> {
+    #[inline(always)]
+    fn clone(&self) -> Self {
+        Self(self.0.clone(), PhantomData)
+    }
+}
+impl<C: Class, H: Copy, D: SubclassOf<C>> Copy for CWeak<C, H, D> {}
+
+impl<C: Class, H: PartialEq, D: SubclassOf<C>> PartialEq for CWeak<C, H, D> {
+    #[inline(always)]
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+impl<C: Class, H: Eq, D: SubclassOf<C>> Eq for CWeak<C, H, D> {}
+
+impl<C: Class, H: core::hash::Hash, D: SubclassOf<C>> core::hash::Hash for CWeak<C, H, D> {
+    #[inline(always)]
+    fn hash<S: core::hash::Hasher>(&self, state: &mut S) {
+        self.0.hash(state)
+    }
+}
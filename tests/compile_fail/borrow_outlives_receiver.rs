@@ -0,0 +1,23 @@
+use bridgeless::*;
+
+#[repr(C)]
+pub struct Holder {
+    pub value: i64,
+}
+
+#[class]
+pub trait Holder {
+    fn get(&self, key: &i64) -> &i64 {
+        let _ = key;
+        &self.value
+    }
+}
+
+fn main() {
+    let key = 0;
+    let value = {
+        let holder: Cls<Holder> = Cls::new(HolderLayout((), Holder { value: 1 }));
+        holder.get(&key)
+    };
+    assert_eq!(*value, 1);
+}
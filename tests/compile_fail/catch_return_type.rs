@@ -0,0 +1,19 @@
+use bridgeless::*;
+
+#[repr(C)]
+pub struct Node {
+    pub value: i64,
+}
+
+#[class(panic = "catch")]
+pub trait Node {
+    fn value(&self) -> i64;
+
+    fn parent<'a>(&'a self) -> CRef<'a, Node>;
+
+    fn child(&mut self, index: usize) -> *mut Node;
+
+    fn name(&self) -> &i64;
+}
+
+fn main() {}
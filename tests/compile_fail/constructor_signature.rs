@@ -0,0 +1,22 @@
+use bridgeless::*;
+
+#[repr(C)]
+pub struct Base {
+    pub value: i64,
+}
+
+#[class]
+pub trait Base {
+    #[constructor]
+    fn unbound(value: i64);
+
+    #[constructor]
+    #[rva(0x1000)]
+    fn with_receiver(&mut self) -> i64;
+
+    #[constructor]
+    #[rva(0x2000)]
+    fn with_body(value: i64) {}
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use bridgeless::*;
+
+#[repr(C)]
+pub struct Base {
+    pub value: i64,
+}
+
+#[class]
+pub trait Base {
+    #[constructor]
+    #[rva(0x1000)]
+    fn construct(value: i64);
+
+    #[destructor]
+    fn destructor(&mut self);
+}
+
+fn main() {}
@@ -0,0 +1,37 @@
+use bridgeless::*;
+
+#[repr(C)]
+pub struct Node {
+    pub id: i64,
+}
+
+#[class]
+pub trait Node {
+    fn this(&self) -> CRef<'_, Node>;
+}
+
+#[repr(C)]
+pub struct Leaf {
+    pub value: i64,
+}
+
+#[class]
+pub trait Leaf: Node_Meta {}
+
+#[repr(C)]
+pub struct Other {
+    pub value: i64,
+}
+
+#[class]
+pub trait Other {}
+
+#[class_impl]
+impl Node_Impl for Impl<Leaf> {
+    #[covariant]
+    fn this(&self) -> CRef<'_, Other> {
+        unimplemented!()
+    }
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use bridgeless::*;
+
+#[repr(C)]
+pub struct Base {
+    pub value: i64,
+}
+
+#[class]
+pub trait Base {
+    #[destructor]
+    fn destructor(&mut self) {}
+
+    #[destructor]
+    fn destroy(&self, flags: u32);
+}
+
+fn main() {}
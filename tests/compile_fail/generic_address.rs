@@ -0,0 +1,23 @@
+use bridgeless::*;
+
+#[repr(C)]
+pub struct Array<T> {
+    pub len: usize,
+    pub first: T,
+}
+
+#[class]
+pub trait Array<T: Copy> {
+    #[constructor]
+    #[symbol("_ZN5ArrayIiEC2Ev")]
+    fn construct();
+
+    #[rva(0x1000)]
+    fn clear(&mut self);
+
+    fn get(&self) -> T {
+        self.first
+    }
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+use bridgeless::*;
+
+#[repr(C)]
+pub struct Bounded {
+    pub value: i64,
+}
+
+#[class]
+pub trait Bounded {
+    fn shorten<'a, 'b: 'a>(&'a self, value: &'b i64) -> &'a i64;
+
+    fn pick<'a>(&'a self, value: &'a i64) -> &'a i64
+    where
+        'a: 'static;
+}
+
+fn main() {}
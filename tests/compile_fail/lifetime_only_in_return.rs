@@ -0,0 +1,13 @@
+use bridgeless::*;
+
+#[repr(C)]
+pub struct Leaky {
+    pub value: i64,
+}
+
+#[class]
+pub trait Leaky {
+    fn leak<'a>(&self) -> &'a i64;
+}
+
+fn main() {}
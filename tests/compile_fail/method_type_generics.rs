@@ -0,0 +1,13 @@
+use bridgeless::*;
+
+#[repr(C)]
+pub struct Generic {
+    pub value: i64,
+}
+
+#[class]
+pub trait Generic {
+    fn convert<T: From<i64>>(&self) -> T;
+}
+
+fn main() {}
@@ -0,0 +1,19 @@
+use bridgeless::*;
+
+#[repr(C)]
+pub struct Base {
+    pub value: i64,
+}
+
+#[class(panic = "catch")]
+pub trait Base {
+    fn get(&self) -> i64 {
+        self.value
+    }
+
+    fn set(&mut self, value: i64) {
+        self.value = value;
+    }
+}
+
+fn main() {}
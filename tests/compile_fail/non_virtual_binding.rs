@@ -0,0 +1,27 @@
+use bridgeless::*;
+
+#[repr(C)]
+pub struct Base {
+    pub value: i64,
+}
+
+#[class]
+pub trait Base {
+    #[symbol("base_get")]
+    fn get(&self) -> i64 {
+        self.value
+    }
+
+    #[offset(2)]
+    #[rva(0x1000)]
+    fn set(&mut self, value: i64);
+
+    #[rva(0x2000)]
+    #[symbol("base_reset")]
+    fn reset(&mut self);
+
+    #[symbol("base\0clear")]
+    fn clear(&mut self);
+}
+
+fn main() {}
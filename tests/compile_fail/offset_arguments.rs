@@ -0,0 +1,39 @@
+use bridgeless::*;
+
+#[repr(C)]
+pub struct Base {
+    pub value: i64,
+}
+
+#[class]
+pub trait Base {
+    #[offset(slot = 2)]
+    fn get(&self) -> i64;
+
+    #[offset(4, name = reserved)]
+    fn set(&mut self, value: i64);
+}
+
+#[repr(C)]
+pub struct Misaligned {
+    pub value: i64,
+}
+
+#[class]
+pub trait Misaligned {
+    #[offset(bytes = 0x1c)]
+    fn get(&self) -> i64;
+}
+
+#[repr(C)]
+pub struct Generic<T> {
+    pub value: T,
+}
+
+#[class]
+pub trait Generic<T: Copy> {
+    #[offset(bytes = 0x10)]
+    fn get(&self) -> T;
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+use bridgeless::*;
+
+#[repr(C)]
+pub struct Base {
+    pub value: i64,
+}
+
+#[class]
+pub trait Base {
+    fn get(&self, key: &i64) -> &i64;
+}
+
+#[repr(C)]
+pub struct Derived {
+    pub other: i64,
+}
+
+#[class]
+pub trait Derived: Base_Meta {}
+
+#[class_impl]
+impl Base_Impl for Impl<Derived> {
+    fn get(&self, key: &i64) -> &i64 {
+        key
+    }
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use bridgeless::*;
+
+#[repr(C)]
+pub struct Base {
+    pub value: i64,
+}
+
+#[class(panic = "ignore")]
+pub trait Base {
+    fn get(&self) -> i64;
+}
+
+#[repr(C)]
+pub struct Other {
+    pub value: i64,
+}
+
+#[class(on_panic = "abort")]
+pub trait Other {
+    fn get(&self) -> i64;
+}
+
+fn main() {}
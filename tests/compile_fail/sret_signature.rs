@@ -0,0 +1,20 @@
+use bridgeless::*;
+
+#[repr(C)]
+pub struct Base {
+    pub value: i64,
+}
+
+#[class]
+pub trait Base {
+    #[sret]
+    fn reset(&mut self);
+
+    #[sret(always)]
+    fn get(&self) -> i64;
+
+    #[sret]
+    fn log(&self, fmt: *const u8, _: ...) -> i64;
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use bridgeless::*;
+
+#[repr(C)]
+pub struct Logger {
+    pub count: i64,
+}
+
+#[class]
+pub trait Logger {
+    fn log(&mut self, fmt: *const u8, _: ...) -> i32 {
+        0
+    }
+}
+
+fn main() {}
@@ -0,0 +1,62 @@
+use core::{cell::Cell, ptr::NonNull};
+
+use bridgeless::{
+    address::{AddressSource, LazyAddress, Rva, Symbol},
+    module::{find_symbol, Module},
+};
+
+#[test]
+fn main_module_image() {
+    let base = Module::main().base();
+    let magic = unsafe { core::slice::from_raw_parts(base, 4) };
+
+    #[cfg(windows)]
+    assert_eq!(&magic[..2], b"MZ");
+    #[cfg(all(unix, not(target_vendor = "apple")))]
+    assert_eq!(magic, b"\x7fELF");
+
+    let rva = main_module_image as *const () as usize - base as usize;
+    assert_eq!(
+        Rva(rva).resolve().map(NonNull::as_ptr),
+        Some(main_module_image as *mut ())
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn exported_symbols() {
+    extern "C" {
+        fn strlen(s: *const core::ffi::c_char) -> usize;
+    }
+
+    assert_eq!(
+        find_symbol(c"strlen").map(NonNull::as_ptr),
+        Some(strlen as *mut ())
+    );
+    assert_eq!(Symbol(c"strlen").resolve(), find_symbol(c"strlen"));
+    assert_eq!(find_symbol(c"bridgeless_missing_symbol"), None);
+}
+
+#[test]
+fn lazy_address_is_cached() {
+    static ADDRESS: LazyAddress = LazyAddress::new();
+    let calls = Cell::new(0);
+    let source = || {
+        calls.set(calls.get() + 1);
+        NonNull::new(0x1000 as *mut ())
+    };
+
+    for _ in 0..3 {
+        assert_eq!(
+            ADDRESS.get_or_resolve(source, "source").as_ptr(),
+            0x1000 as *mut ()
+        );
+    }
+    assert_eq!(calls.get(), 1);
+}
+
+#[test]
+#[should_panic(expected = "failed to resolve the address of Missing::method")]
+fn unresolved_address_panics() {
+    LazyAddress::new().get_or_resolve(|| None, "Missing::method");
+}
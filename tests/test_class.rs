@@ -0,0 +1,141 @@
+use core::mem::size_of;
+
+use bridgeless::*;
+
+#[repr(C)]
+pub struct Container<T> {
+    pub fallback: T,
+}
+
+#[class]
+pub trait Container<T: Copy> {
+    fn len(&self) -> usize {
+        0
+    }
+
+    fn get(&self, index: usize) -> T {
+        let _ = index;
+        self.fallback
+    }
+
+    #[offset(3)]
+    fn set(&mut self, index: usize, value: T) -> bool {
+        let _ = (index, value);
+        false
+    }
+}
+
+#[repr(C)]
+pub struct Pair<T> {
+    pub items: [T; 2],
+}
+
+#[class]
+pub trait Pair<T: Copy>: Container_Meta<T> {
+    fn swap(&mut self) {
+        self.items.swap(0, 1);
+    }
+}
+
+#[class_impl]
+impl<T: Copy + 'static> Container_Impl<T> for Impl<Pair<T>> {
+    fn len(&self) -> usize {
+        2
+    }
+
+    fn get(&self, index: usize) -> T {
+        match self.items.get(index) {
+            Some(item) => *item,
+            None => self.upcast::<Container<T>>().fallback,
+        }
+    }
+
+    fn set(&mut self, index: usize, value: T) -> bool {
+        self.items.get_mut(index).map(|item| *item = value).is_some()
+    }
+}
+
+#[repr(C)]
+pub struct Ring<const N: usize> {
+    pub slots: [u8; N],
+}
+
+#[class]
+pub trait Ring<const N: usize> {
+    fn capacity(&self) -> usize {
+        N
+    }
+}
+
+fn new_pair<T: Copy + 'static>(fallback: T, items: [T; 2]) -> Cls<Pair<T>> {
+    Cls::new(PairLayout(
+        ContainerLayout((), Container { fallback }),
+        Pair { items },
+    ))
+}
+
+#[test]
+fn generic_class_own_impls() {
+    let mut container: Cls<Container<u32>> =
+        Cls::new(ContainerLayout((), Container { fallback: 7u32 }));
+
+    assert_eq!(container.len(), 0);
+    assert_eq!(container.get(3), 7);
+    assert!(!container.set(0, 1));
+}
+
+#[test]
+fn generic_class_overrides() {
+    let mut pair = new_pair(-1i32, [1, 2]);
+
+    assert_eq!(pair.len(), 2);
+    assert_eq!(pair.get(1), 2);
+    assert_eq!(pair.get(2), -1);
+
+    pair.swap();
+    assert_eq!(pair.items, [2, 1]);
+
+    // Calls through the base class dispatch to the overrides of the derived class
+    let container: &mut DynCls<Container<i32>> = pair.upcast_mut();
+    assert!(container.set(0, 5));
+    assert!(!container.set(2, 5));
+    assert_eq!(container.get(0), 5);
+    assert_eq!(pair.items, [5, 1]);
+}
+
+#[test]
+fn generic_class_is_monomorphized() {
+    let ints = new_pair(0i32, [1, 2]);
+    let floats = new_pair(0.5f64, [1.5, 2.5]);
+
+    assert_eq!(ints.get(0), 1);
+    assert_eq!(floats.get(1), 2.5);
+    assert_eq!(floats.get(2), 0.5);
+
+    assert_eq!(Pair::<f64>::base_offset::<Container<f64>>(), Some(0));
+    assert_eq!(Pair::<f64>::base_offset::<Container<i32>>(), None);
+    assert_ne!(
+        ints.layout().vtable() as *const _ as usize,
+        floats.layout().vtable() as *const _ as usize
+    );
+}
+
+#[test]
+fn generic_class_vtable_layout() {
+    // `set` is at index 3, leaving a gap of one slot
+    assert_eq!(size_of::<ContainerVmt<u8>>(), 4 * size_of::<usize>());
+    assert_eq!(size_of::<PairCombinedVmt<u8>>(), 5 * size_of::<usize>());
+    assert_eq!(
+        size_of::<PairLayout<u8, &PairCombinedVmt<u8>>>(),
+        3 * size_of::<usize>()
+    );
+}
+
+#[test]
+fn const_generic_class() {
+    let small: Cls<Ring<4>> = Cls::new(RingLayout((), Ring { slots: [0; 4] }));
+    let large: Cls<Ring<16>> = Cls::new(RingLayout((), Ring { slots: [0; 16] }));
+
+    assert_eq!(small.capacity(), 4);
+    assert_eq!(large.capacity(), 16);
+}
@@ -1,4 +1,12 @@
 // #![no_std]
+// Most of this file only needs to typecheck.
+#![allow(
+    dead_code,
+    unused_variables,
+    clippy::needless_update,
+    clippy::identity_op,
+    clippy::manual_map
+)]
 
 use std::marker::PhantomData;
 
@@ -22,12 +30,12 @@ struct MyBase<T>(T);
 
 #[repr(C)]
 pub struct A {
-    a_field: usize,
+    pub a_field: usize,
 }
 
 #[allow(non_snake_case)]
 pub mod A_Meta {
-    use bridgeless::internal::{HasConst, VmtPartGen};
+    use bridgeless::internal::VmtPartGen;
 
     use super::A;
     type Data = A;
@@ -42,20 +50,20 @@ pub mod A_Meta {
 }
 
 #[allow(non_camel_case_types)]
-trait A_Impl: 'static + bridgeless::internal::ClassWrapper {
+pub trait A_Impl: 'static + bridgeless::internal::ClassWrapper {
     fn virt_a(&mut self) -> usize {
         let base_offset = <Self as bridgeless::internal::ClassWrapper>::ClsType::base_offset::<A>()
             .expect("Unreachable code ran");
         unsafe {
             let thin_ptr = (self as *mut _ as *mut u8).add(base_offset);
-            let func = (*(thin_ptr as *mut AVmt)).virt_a.unwrap_unchecked();
+            let func = (*(thin_ptr as *const &'static AVmt)).virt_a.unwrap_unchecked();
             (func)(&mut *thin_ptr)
         }
     }
 }
 
 #[repr(C)]
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub struct AVmt {
     pub virt_a: Option<for<'a> unsafe extern "C" fn(&'a mut u8) -> usize>,
 }
@@ -71,9 +79,11 @@ impl AVmt {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub struct ACombinedVmt(pub AVmt);
 
+unsafe impl VmtSlots for ACombinedVmt {}
+
 #[repr(C)]
 pub struct ALayout<VPtr: 'static + Copy>(pub VPtr, pub A);
 impl<VPtr: 'static + Copy> ALayout<VPtr> {
@@ -108,13 +118,20 @@ unsafe impl Class for A {
     type Layout<VPtr: 'static + Copy> = ALayout<VPtr>;
 
     #[inline(always)]
-    fn base_offset<C: Class + ?Sized>() -> Option<usize> {
+    fn base_offset<C: Class>() -> Option<usize> {
         if std::any::TypeId::of::<C>() == std::any::TypeId::of::<A>() {
             Some(0)
-        } else {
+        }
+        else {
             None
         }
     }
+
+    #[inline(always)]
+    fn destructor(vmt: &'static ACombinedVmt) -> Option<Destructor> {
+        let _ = vmt;
+        None
+    }
 }
 
 unsafe impl<C: Class> internal::SubclassOf<A> for internal::SubclassOfWrapper<C> where
@@ -182,15 +199,15 @@ unsafe impl VmtPartGen<A> for A {
 
 #[repr(C)]
 pub struct B {
-    b_field: usize,
+    pub b_field: usize,
 }
 
 #[allow(non_snake_case)]
 pub mod B_Meta {
-    use bridgeless::internal::{HasConst, VmtPartGen};
+    use bridgeless::internal::VmtPartGen;
 
+    use super::A_Meta;
     use super::B;
-    use crate::A_Meta;
     type Data = B;
 
     pub trait InheritTrait: A_Meta::InheritTrait {}
@@ -200,7 +217,7 @@ pub mod B_Meta {
 }
 
 #[allow(non_camel_case_types)]
-trait B_Impl: bridgeless::internal::ClassWrapper
+pub trait B_Impl: bridgeless::internal::ClassWrapper
 where
     for<'a> Self: 'a,
 {
@@ -210,7 +227,7 @@ where
 }
 
 #[repr(C)]
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub struct BVmt {
     pub virt_b: Option<for<'a> unsafe extern "C" fn(&'a mut u8) -> usize>,
 }
@@ -226,9 +243,11 @@ impl BVmt {
 }
 
 #[repr(C)]
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug)]
 pub struct BCombinedVmt(pub <A as Class>::Vmt, pub BVmt);
 
+unsafe impl VmtSlots for BCombinedVmt {}
+
 #[repr(C)]
 pub struct BLayout<VPtr: 'static + Copy>(pub <A as Class>::Layout<VPtr>, pub B);
 impl<VPtr: 'static + Copy> BLayout<VPtr> {
@@ -264,15 +283,22 @@ unsafe impl Class for B {
     type Layout<VPtr: 'static + Copy> = BLayout<VPtr>;
 
     #[inline(always)]
-    fn base_offset<C: Class + ?Sized>() -> Option<usize> {
+    fn base_offset<C: Class>() -> Option<usize> {
         if std::any::TypeId::of::<C>() == std::any::TypeId::of::<B>() {
             Some(0)
-        } else if let Some(ofs) = <A as Class>::base_offset::<C>() {
+        }
+        else if let Some(ofs) = <A as Class>::base_offset::<C>() {
             Some(ofs + 0)
-        } else {
+        }
+        else {
             None
         }
     }
+
+    #[inline(always)]
+    fn destructor(vmt: &'static BCombinedVmt) -> Option<Destructor> {
+        <A as Class>::destructor(&vmt.0)
+    }
 }
 
 unsafe impl<C: Class> internal::SubclassOf<B> for internal::SubclassOfWrapper<C> where
@@ -337,8 +363,8 @@ impl<Ofs: HasConst<usize>> HasConst<BVmt> for BPartGen<Ofs> {
             let derived: &mut Impl<B> = &mut *FromThinPtr::from_thin_ptr_mut(derived_ptr);
             B_Impl::virt_b(derived)
         }
-        type VMT = <B as Class>::VmtPart;
-        VMT {
+        type Vmt = <B as Class>::VmtPart;
+        Vmt {
             virt_b: Some(virt_b::<Ofs>),
             ..BVmt::default()
         }
@@ -349,6 +375,7 @@ unsafe impl VmtPartGen<B> for B {
     type ForOffset<Ofs: HasConst<usize>> = BPartGen<Ofs>;
 }
 
+#[test]
 fn test() {
     let a = A::new(A { a_field: 0 });
     let b = B::new(a, B { b_field: 69 });
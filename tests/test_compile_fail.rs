@@ -0,0 +1,8 @@
+#[test]
+fn compile_fail() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/compile_fail/*.rs");
+    // The test crate is built with the features of bridgeless, which may be unified with `std`
+    #[cfg(not(feature = "std"))]
+    t.compile_fail("tests/compile_fail/no_std/*.rs");
+}
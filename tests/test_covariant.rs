@@ -0,0 +1,85 @@
+use bridgeless::*;
+
+#[repr(C)]
+pub struct Node {
+    pub id: i64,
+}
+
+#[class]
+pub trait Node {
+    fn this(&self) -> CRef<'_, Node> {
+        CRef::new(self.as_concrete())
+    }
+
+    fn find(&mut self, id: i64) -> Option<CRefMut<'_, Node>> {
+        (self.id == id).then(|| CRefMut::from(self.as_dyn_mut()))
+    }
+
+    fn duplicate(&self) -> *mut Cls<Node>;
+}
+
+#[repr(C)]
+pub struct Leaf {
+    pub value: i64,
+}
+
+#[class]
+pub trait Leaf: Node_Meta {}
+
+#[class_impl]
+impl Node_Impl for Impl<Leaf> {
+    #[covariant]
+    fn this(&self) -> CRef<'_, Leaf> {
+        CRef::new(self.as_concrete())
+    }
+
+    #[covariant]
+    fn find(&mut self, id: i64) -> Option<CRefMut<'_, Leaf>> {
+        if self.value != id {
+            return None;
+        }
+        Some(CRefMut::from(self.as_dyn_mut()))
+    }
+
+    #[covariant]
+    fn duplicate(&self) -> *mut Cls<Leaf> {
+        let node = self.upcast::<Node>();
+        Box::into_raw(Box::new(new_leaf(node.id, self.value)))
+    }
+}
+
+fn new_leaf(id: i64, value: i64) -> Cls<Leaf> {
+    Cls::new(LeafLayout(NodeLayout((), Node { id }), Leaf { value }))
+}
+
+#[test]
+fn covariant_overrides_through_base() {
+    let mut leaf = new_leaf(1, 7);
+    let node: &mut DynCls<Node> = leaf.upcast_mut();
+
+    assert_eq!(node.this().id, 1);
+    assert!(node.find(1).is_none());
+
+    let mut found = node.find(7).unwrap();
+    found.id = 2;
+    assert_eq!(node.id, 2);
+
+    let copy = node.duplicate();
+    let copy = unsafe { Box::from_raw(copy as *mut Cls<Leaf>) };
+    assert_eq!((copy.upcast::<Node>().id, copy.value), (2, 7));
+}
+
+#[test]
+fn covariant_return_conversions() {
+    let leaf = new_leaf(3, 4);
+    let node: CRef<'_, Node> = CovariantReturn::<Node>::upcast_return(CRef::new(&leaf));
+    assert_eq!(node.id, 3);
+
+    let null: *const Cls<Node> =
+        CovariantReturn::<Node>::upcast_return(core::ptr::null::<Cls<Leaf>>());
+    assert!(null.is_null());
+
+    let none: Option<CRef<'_, Node>> =
+        CovariantReturn::<Node>::upcast_return(None::<CRef<'_, Leaf>>);
+    assert!(none.is_none());
+}
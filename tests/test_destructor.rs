@@ -0,0 +1,175 @@
+use core::{cell::Cell, mem::ManuallyDrop};
+
+use bridgeless::*;
+
+/// Counts the number of times it is dropped.
+pub struct DropCounter<'a>(&'a Cell<u32>);
+
+impl Drop for DropCounter<'_> {
+    fn drop(&mut self) {
+        self.0.set(self.0.get() + 1);
+    }
+}
+
+#[repr(C)]
+pub struct Entity {
+    pub drops: DropCounter<'static>,
+}
+
+#[class]
+pub trait Entity {
+    fn kind(&self) -> u32 {
+        0
+    }
+
+    #[destructor]
+    fn destructor(&mut self);
+
+    fn tick(&mut self) {}
+}
+
+#[repr(C)]
+pub struct Actor {
+    pub drops: DropCounter<'static>,
+}
+
+#[class]
+pub trait Actor: Entity_Meta {
+    fn speed(&self) -> u32 {
+        1
+    }
+}
+
+#[class_impl]
+impl Entity_Impl for Impl<Actor> {
+    fn kind(&self) -> u32 {
+        1
+    }
+}
+
+#[repr(C)]
+pub struct Prop {
+    pub drops: DropCounter<'static>,
+}
+
+#[class]
+pub trait Prop {
+    fn weight(&self) -> u32 {
+        10
+    }
+}
+
+#[repr(C)]
+pub struct Slotted {
+    pub value: u32,
+}
+
+#[class]
+pub trait Slotted {
+    #[destructor]
+    fn destructor(&mut self);
+
+    #[offset(3)]
+    fn value(&self) -> u32 {
+        self.value
+    }
+}
+
+fn counter() -> &'static Cell<u32> {
+    Box::leak(Box::new(Cell::new(0)))
+}
+
+fn new_actor(entity_drops: &'static Cell<u32>, actor_drops: &'static Cell<u32>) -> Cls<Actor> {
+    Cls::new(ActorLayout(
+        EntityLayout(
+            (),
+            Entity {
+                drops: DropCounter(entity_drops),
+            },
+        ),
+        Actor {
+            drops: DropCounter(actor_drops),
+        },
+    ))
+}
+
+#[test]
+fn drop_calls_virtual_destructor() {
+    let (entity_drops, actor_drops) = (counter(), counter());
+
+    let entity: Cls<Entity> = Cls::new(EntityLayout(
+        (),
+        Entity {
+            drops: DropCounter(entity_drops),
+        },
+    ));
+    assert!(Entity::destructor(entity.layout().vtable()).is_some());
+    drop(entity);
+    assert_eq!(entity_drops.get(), 1);
+
+    let actor = new_actor(entity_drops, actor_drops);
+    assert_eq!(actor.kind(), 1);
+    assert!(Actor::destructor(actor.layout().vtable()).is_some());
+    drop(actor);
+    assert_eq!((entity_drops.get(), actor_drops.get()), (2, 1));
+}
+
+#[test]
+fn destructor_through_base_vtable() {
+    let (entity_drops, actor_drops) = (counter(), counter());
+    let mut actor = ManuallyDrop::new(new_actor(entity_drops, actor_drops));
+
+    // Like `delete entity` on a `Entity*` in C++, which calls the most-derived destructor
+    let entity: &mut DynCls<Entity> = actor.upcast_mut();
+    let destructor = Entity::destructor(entity.as_concrete().layout().vtable()).unwrap();
+    unsafe { destructor.destruct(entity as *mut DynCls<Entity> as *mut u8) };
+    assert_eq!((entity_drops.get(), actor_drops.get()), (1, 1));
+}
+
+#[test]
+fn manually_drop_skips_destructor() {
+    let (entity_drops, actor_drops) = (counter(), counter());
+
+    {
+        let actor = ManuallyDrop::new(new_actor(entity_drops, actor_drops));
+        assert_eq!(actor.speed(), 1);
+    }
+    assert_eq!((entity_drops.get(), actor_drops.get()), (0, 0));
+
+    let mut actor = ManuallyDrop::new(new_actor(entity_drops, actor_drops));
+    unsafe { ManuallyDrop::drop(&mut actor) };
+    assert_eq!((entity_drops.get(), actor_drops.get()), (1, 1));
+
+    let layout = unsafe { new_actor(entity_drops, actor_drops).into_layout() };
+    assert_eq!((entity_drops.get(), actor_drops.get()), (1, 1));
+    drop(layout);
+    assert_eq!((entity_drops.get(), actor_drops.get()), (2, 2));
+}
+
+#[test]
+fn classes_without_destructor_drop_their_layout() {
+    let drops = counter();
+    let prop: Cls<Prop> = Cls::new(PropLayout(
+        (),
+        Prop {
+            drops: DropCounter(drops),
+        },
+    ));
+
+    assert!(Prop::destructor(prop.layout().vtable()).is_none());
+    assert_eq!(prop.weight(), 10);
+    drop(prop);
+    assert_eq!(drops.get(), 1);
+}
+
+#[test]
+fn destructor_slots() {
+    let ptr = size_of::<usize>();
+    assert_eq!(Destructor::SLOTS * ptr, size_of::<Destructor>());
+    assert_eq!(size_of::<EntityVmt>(), (Destructor::SLOTS + 2) * ptr);
+
+    // Explicit offsets are vtable indices, counting every slot of the destructor
+    assert_eq!(size_of::<SlottedVmt>(), 4 * ptr);
+    let slotted: Cls<Slotted> = Cls::new(SlottedLayout((), Slotted { value: 5 }));
+    assert_eq!(slotted.value(), 5);
+}
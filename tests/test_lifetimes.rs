@@ -0,0 +1,123 @@
+use bridgeless::*;
+
+#[repr(C)]
+pub struct Registry {
+    pub values: [i64; 4],
+}
+
+#[class]
+pub trait Registry {
+    fn first(&self) -> &i64 {
+        &self.values[0]
+    }
+
+    fn find(&self, key: &i64) -> Option<&i64> {
+        self.values.iter().find(|value| *value == key)
+    }
+
+    fn larger<'a>(&self, a: &'a i64, b: &'a i64) -> &'a i64 {
+        let _ = self;
+        if a > b {
+            a
+        }
+        else {
+            b
+        }
+    }
+
+    fn slot(&mut self, index: usize) -> &mut i64 {
+        &mut self.values[index]
+    }
+
+    fn this(&self) -> CRef<'_, Registry> {
+        CRef::new(self.as_concrete())
+    }
+
+    fn visit(&self, f: for<'b> extern "C" fn(&'b i64) -> &'b i64) -> i64 {
+        *f(&self.values[1])
+    }
+}
+
+#[repr(C)]
+pub struct Sorted {
+    pub offset: i64,
+}
+
+#[class]
+pub trait Sorted: Registry_Meta {}
+
+#[class_impl]
+impl Registry_Impl for Impl<Sorted> {
+    fn first(&self) -> &i64 {
+        self.upcast::<Registry>().values.iter().min().unwrap()
+    }
+
+    fn slot(&mut self, index: usize) -> &mut i64 {
+        let index = index + self.offset as usize;
+        &mut self.upcast_mut::<Registry>().values[index]
+    }
+}
+
+fn new_sorted(values: [i64; 4], offset: i64) -> Cls<Sorted> {
+    Cls::new(SortedLayout(
+        RegistryLayout((), Registry { values }),
+        Sorted { offset },
+    ))
+}
+
+extern "C" fn identity(value: &i64) -> &i64 {
+    value
+}
+
+#[test]
+fn borrowed_returns() {
+    let mut registry: Cls<Registry> = Cls::new(RegistryLayout(
+        (),
+        Registry {
+            values: [4, 3, 2, 1],
+        },
+    ));
+
+    assert_eq!(*registry.first(), 4);
+    assert_eq!(registry.find(&2), Some(&2));
+    assert_eq!(registry.find(&7), None);
+    assert_eq!(*registry.larger(&5, &9), 9);
+    assert_eq!(registry.visit(identity), 3);
+
+    *registry.slot(3) = 10;
+    assert_eq!(registry.values, [4, 3, 2, 10]);
+    assert_eq!(registry.this().values, [4, 3, 2, 10]);
+}
+
+#[test]
+fn borrowed_returns_of_overrides() {
+    let mut sorted = new_sorted([4, 3, 2, 1], 1);
+    let registry: &mut DynCls<Registry> = sorted.upcast_mut();
+
+    assert_eq!(*registry.first(), 1);
+    assert_eq!(registry.find(&4), Some(&4));
+
+    *registry.slot(0) = 0;
+    assert_eq!(registry.values, [4, 0, 2, 1]);
+    assert_eq!(*registry.first(), 0);
+
+    let this = registry.this();
+    assert_eq!(this.values, [4, 0, 2, 1]);
+}
+
+#[test]
+fn returned_borrow_outlives_argument_borrow() {
+    let registry: Cls<Registry> = Cls::new(RegistryLayout(
+        (),
+        Registry {
+            values: [1, 2, 3, 4],
+        },
+    ));
+
+    // The result of `find` is only tied to the receiver, not to `key`
+    let found = {
+        let key = 3;
+        registry.find(&key)
+    };
+    assert_eq!(found, Some(&3));
+}
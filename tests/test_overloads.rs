@@ -0,0 +1,123 @@
+use core::mem::offset_of;
+
+use bridgeless::*;
+
+#[repr(C)]
+pub struct Canvas {
+    pub scale: i32,
+}
+
+#[class]
+pub trait Canvas {
+    #[cpp_name = "draw"]
+    fn draw_int(&self, value: i32) -> i32 {
+        value * self.scale
+    }
+
+    fn clear(&mut self) {}
+
+    #[cpp_name = "draw"]
+    fn draw_float(&self, value: f32) -> i32 {
+        (value * self.scale as f32) as i32
+    }
+
+    fn size(&self) -> i32 {
+        self.scale
+    }
+
+    #[offset(5)]
+    #[cpp_name = "draw"]
+    fn draw_pair(&self, a: i32, b: i32) -> i32 {
+        (a + b) * self.scale
+    }
+}
+
+#[repr(C)]
+pub struct Screen {
+    pub offset: i32,
+}
+
+#[class]
+pub trait Screen: Canvas_Meta {}
+
+#[class_impl]
+impl Canvas_Impl for Impl<Screen> {
+    fn draw_float(&self, value: f32) -> i32 {
+        value as i32 + self.offset
+    }
+}
+
+#[test]
+fn overload_slot_order() {
+    let ptr = size_of::<usize>();
+    let offsets = [
+        offset_of!(CanvasVmt, draw_int),
+        offset_of!(CanvasVmt, clear),
+        offset_of!(CanvasVmt, draw_float),
+        offset_of!(CanvasVmt, size),
+        offset_of!(CanvasVmt, draw_pair),
+    ];
+    // MSVC groups overloads at the first one in reverse order, keeping the slot indices in use
+    let expected = match cfg!(target_env = "msvc") {
+        true => [2, 3, 1, 5, 0],
+        false => [0, 1, 2, 3, 5],
+    };
+    assert_eq!(offsets, expected.map(|i| i * ptr));
+    assert_eq!(size_of::<CanvasVmt>(), 6 * ptr);
+}
+
+#[test]
+fn slot_constants() {
+    let slots = [
+        CanvasVmt::DRAW_INT_SLOT,
+        CanvasVmt::CLEAR_SLOT,
+        CanvasVmt::DRAW_FLOAT_SLOT,
+        CanvasVmt::SIZE_SLOT,
+        CanvasVmt::DRAW_PAIR_SLOT,
+    ];
+    let expected = match cfg!(target_env = "msvc") {
+        true => [2, 3, 1, 5, 0],
+        false => [0, 1, 2, 3, 5],
+    };
+    assert_eq!(slots, expected);
+    assert_eq!(CanvasVmt::DRAW_INT_NAME, "draw");
+    assert_eq!(CanvasVmt::DRAW_PAIR_NAME, "draw");
+    assert_eq!(CanvasVmt::CLEAR_NAME, "clear");
+
+    // Indices are in the main vtable, after the slots of the base classes
+    assert_eq!(ScreenCombinedVmt::SLOT_COUNT, 6);
+}
+
+#[test]
+fn call_typed_slots() {
+    let mut screen: Cls<Screen> = Cls::new(ScreenLayout(
+        CanvasLayout((), Canvas { scale: 3 }),
+        Screen { offset: 10 },
+    ));
+    let vmt = screen.layout().vtable();
+    let canvas = vmt.0 .0.typed();
+
+    let draw_float = canvas.draw_float.unwrap();
+    assert_eq!(
+        unsafe { draw_float(screen.upcast::<Canvas>().into(), 2.5) },
+        12
+    );
+    assert_eq!(
+        vmt.slot(CanvasVmt::DRAW_FLOAT_SLOT),
+        draw_float as *const ()
+    );
+    let clear = canvas.clear.unwrap();
+    unsafe { clear(screen.upcast_mut::<Canvas>().into()) };
+}
+
+#[test]
+fn call_overloads() {
+    let screen: Cls<Screen> = Cls::new(ScreenLayout(
+        CanvasLayout((), Canvas { scale: 3 }),
+        Screen { offset: 10 },
+    ));
+    assert_eq!(screen.draw_int(2), 6);
+    assert_eq!(screen.draw_float(2.5), 12);
+    assert_eq!(screen.draw_pair(1, 2), 9);
+    assert_eq!(screen.size(), 3);
+}
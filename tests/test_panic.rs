@@ -0,0 +1,72 @@
+use std::{
+    env,
+    panic::{self, AssertUnwindSafe},
+    process::Command,
+};
+
+use bridgeless::*;
+
+#[repr(C)]
+pub struct Widget {
+    pub updates: u32,
+}
+
+#[class]
+pub trait Widget {
+    fn update(&mut self, delta: u32) -> u32 {
+        assert!(delta != 0, "zero delta");
+        self.updates += 1;
+        self.updates
+    }
+}
+
+#[repr(C)]
+pub struct Task {
+    pub runs: u32,
+}
+
+#[class(panic = "unwind")]
+pub trait Task {
+    fn run(&mut self, input: u32) -> u32 {
+        self.runs += 1;
+        assert!(input != 0, "zero input");
+        input
+    }
+}
+
+/// Environment variable set when running a test in a child process.
+const CHILD_ENV: &str = "BRIDGELESS_PANIC_TEST_CHILD";
+
+#[test]
+fn abort_on_panic() {
+    if env::var_os(CHILD_ENV).is_some() {
+        let mut widget: Cls<Widget> = Cls::new(WidgetLayout((), Widget { updates: 0 }));
+        assert_eq!(widget.update(1), 1);
+        widget.update(0);
+        return;
+    }
+
+    let output = Command::new(env::current_exe().unwrap())
+        .args(["abort_on_panic", "--exact", "--nocapture"])
+        .env(CHILD_ENV, "1")
+        .output()
+        .unwrap();
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("zero delta"), "{stderr}");
+    assert!(
+        stderr.contains("panic in `Widget::update` called from C++ cannot unwind, aborting"),
+        "{stderr}"
+    );
+}
+
+#[test]
+fn unwind_on_panic() {
+    let mut task: Cls<Task> = Cls::new(TaskLayout((), Task { runs: 0 }));
+    assert_eq!(task.run(3), 3);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| task.run(0)));
+    let payload = result.expect_err("task should have panicked");
+    assert_eq!(payload.downcast_ref::<&str>(), Some(&"zero input"));
+    assert_eq!(task.runs, 2);
+}
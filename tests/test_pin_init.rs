@@ -0,0 +1,118 @@
+use core::{
+    cell::{Cell, RefCell},
+    mem::MaybeUninit,
+    ptr::{self, NonNull},
+};
+
+use bridgeless::*;
+
+thread_local! {
+    /// Addresses of the live `Node` instances, like an intrusive list maintained in C++.
+    static NODES: RefCell<Vec<NonNull<Cls<Node>>>> = const { RefCell::new(Vec::new()) };
+}
+
+fn registered() -> Vec<NonNull<Cls<Node>>> {
+    NODES.with_borrow(|nodes| nodes.clone())
+}
+
+/// Removes the node from `NODES` when dropped.
+pub struct Registration(NonNull<Cls<Node>>);
+
+impl Drop for Registration {
+    fn drop(&mut self) {
+        NODES.with_borrow_mut(|nodes| nodes.retain(|&node| node != self.0));
+    }
+}
+
+#[repr(C)]
+pub struct Node {
+    pub id: u32,
+    pub registration: Registration,
+}
+
+#[class]
+pub trait Node {
+    #[destructor]
+    fn destructor(&mut self);
+
+    fn value(&self) -> u32 {
+        self.id
+    }
+}
+
+impl Node {
+    fn register(id: u32) -> impl Init<Node> {
+        Node::pin_init(move |this| {
+            NODES.with_borrow_mut(|nodes| nodes.push(this.this()));
+            Node {
+                id,
+                registration: Registration(this.this()),
+            }
+        })
+    }
+}
+
+#[repr(C)]
+pub struct Counter {
+    pub base_id: u32,
+    pub count: Cell<u32>,
+}
+
+#[class]
+pub trait Counter: Node_Meta {}
+
+#[class_impl]
+impl Node_Impl for Impl<Counter> {
+    fn value(&self) -> u32 {
+        self.count.get()
+    }
+}
+
+#[test]
+fn emplace_root_class() {
+    let mut slot = MaybeUninit::uninit();
+    let node = unsafe { Cls::emplace(&mut slot, Node::register(1)) };
+
+    assert_eq!(node.value(), 1);
+    assert_eq!(registered(), [NonNull::from(&*node)]);
+    assert!(ptr::eq(slot.as_ptr(), registered()[0].as_ptr()));
+
+    unsafe { ptr::drop_in_place(slot.as_mut_ptr()) };
+    assert!(registered().is_empty());
+}
+
+#[test]
+fn emplace_derived_class() {
+    let mut slot = MaybeUninit::<Cls<Counter>>::uninit();
+    let init = Counter::pin_init(Node::register(7), |mut this| {
+        // The base is initialized at the final address before the derived class
+        let base_id = this.base::<Node>().id;
+        assert_eq!(registered(), [this.this().cast()]);
+        Counter {
+            base_id,
+            count: Cell::new(3),
+        }
+    });
+    let counter = unsafe { Cls::emplace(&mut slot, init) };
+
+    assert_eq!(counter.base_id, 7);
+    assert_eq!(counter.value(), 3);
+    let node = unsafe { registered()[0].as_ref() };
+    assert_eq!(node.value(), 3);
+    counter.count.set(4);
+    assert_eq!(node.value(), 4);
+
+    unsafe { ptr::drop_in_place(slot.as_mut_ptr()) };
+    assert!(registered().is_empty());
+}
+
+#[test]
+#[should_panic = "the data of the class under construction is not initialized yet"]
+fn own_data_is_uninitialized() {
+    let mut slot = MaybeUninit::<Cls<Counter>>::uninit();
+    let init = Counter::pin_init(Node::register(1), |mut this| {
+        let _ = this.base::<Counter>();
+        unreachable!()
+    });
+    unsafe { Cls::emplace(&mut slot, init) };
+}
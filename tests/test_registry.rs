@@ -0,0 +1,147 @@
+use bridgeless::{registry, *};
+
+#[repr(C)]
+pub struct Shape {
+    pub id: u32,
+}
+
+#[class]
+pub trait Shape {
+    fn area(&self) -> f64 {
+        0.0
+    }
+}
+
+#[repr(C)]
+pub struct Circle {
+    pub radius: f64,
+}
+
+#[class]
+pub trait Circle: Shape_Meta {}
+
+#[class_impl]
+impl Shape_Impl for Impl<Circle> {
+    fn area(&self) -> f64 {
+        3.0 * self.radius * self.radius
+    }
+}
+
+#[repr(C)]
+pub struct Square {
+    pub side: f64,
+}
+
+#[class]
+pub trait Square: Shape_Meta {}
+
+#[class_impl]
+impl Shape_Impl for Impl<Square> {
+    fn area(&self) -> f64 {
+        self.side * self.side
+    }
+}
+
+#[repr(C)]
+pub struct Boxed<T> {
+    pub value: T,
+}
+
+#[class]
+pub trait Boxed<T: Copy> {
+    fn get(&self) -> T {
+        self.value
+    }
+}
+
+fn new_circle(id: u32, radius: f64) -> Cls<Circle> {
+    Cls::new(CircleLayout(
+        ShapeLayout((), Shape { id }),
+        Circle { radius },
+    ))
+}
+
+fn new_square(id: u32, side: f64) -> Cls<Square> {
+    Cls::new(SquareLayout(ShapeLayout((), Shape { id }), Square { side }))
+}
+
+#[test]
+fn concrete_type_checks() {
+    let mut circle = new_circle(1, 2.0);
+    let mut square = new_square(2, 3.0);
+    unsafe { registry::register_vtable::<Circle>(circle.layout().vtable()) }.unwrap();
+
+    assert!(registry::is_registered::<Circle>(circle.layout().vtable()));
+    assert!(!registry::is_registered::<Square>(square.layout().vtable()));
+
+    let shape: &mut DynCls<Shape> = circle.upcast_mut();
+    assert!(shape.is_instance_of::<Circle>());
+    assert!(!shape.is_instance_of::<Square>());
+    assert!(!shape.is_instance_of::<Shape>());
+    assert_eq!(
+        shape.try_as_concrete::<Circle>().map(|c| c.radius),
+        Some(2.0)
+    );
+    assert!(shape.try_as_concrete::<Square>().is_none());
+
+    unsafe { shape.try_as_concrete_mut::<Circle>() }.unwrap().radius = 1.0;
+    assert_eq!(circle.area(), 3.0);
+
+    // Square's vtable is not registered yet
+    let shape: &mut DynCls<Shape> = square.upcast_mut();
+    assert!(unsafe { shape.try_as_concrete_mut::<Square>() }.is_none());
+    assert!(shape.try_as_concrete::<Circle>().is_none());
+
+    unsafe { registry::register_vtable::<Square>(square.layout().vtable()) }.unwrap();
+    let shape: &DynCls<Shape> = square.upcast();
+    assert_eq!(
+        shape.try_as_concrete::<Square>().map(|s| s.area()),
+        Some(9.0)
+    );
+}
+
+#[test]
+fn registration_is_idempotent() {
+    let shape: Cls<Shape> = Cls::new(ShapeLayout((), Shape { id: 3 }));
+    for _ in 0..2 * registry::CAPACITY {
+        unsafe { registry::register_vtable::<Shape>(shape.layout().vtable()) }.unwrap();
+    }
+    assert_eq!(
+        shape.as_dyn().try_as_concrete::<Shape>().map(|s| s.id),
+        Some(3)
+    );
+}
+
+#[test]
+fn generic_classes_are_distinct() {
+    let ints: Cls<Boxed<i32>> = Cls::new(BoxedLayout((), Boxed { value: 1 }));
+    let floats: Cls<Boxed<f32>> = Cls::new(BoxedLayout((), Boxed { value: 1.5 }));
+    unsafe { registry::register_vtable::<Boxed<i32>>(ints.layout().vtable()) }.unwrap();
+
+    assert!(ints.as_dyn().is_instance_of::<Boxed<i32>>());
+    assert!(!floats.as_dyn().is_instance_of::<Boxed<f32>>());
+
+    // The same vtable is not registered for other instances of the generic class
+    let vtable = ints.layout().vtable() as *const _ as *const BoxedCombinedVmt<u32>;
+    assert!(!registry::is_registered::<Boxed<u32>>(unsafe { &*vtable }));
+}
+
+#[test]
+fn rust_vtables_are_unique() {
+    let circle = new_circle(4, 1.0);
+    let other: Cls<Circle> = Cls::new(CircleLayout(
+        ShapeLayout((), Shape { id: 5 }),
+        Circle { radius: 2.0 },
+    ));
+    assert!(core::ptr::eq(
+        circle.layout().vtable(),
+        other.layout().vtable()
+    ));
+    assert!(core::ptr::eq(
+        circle.layout().vtable(),
+        Circle_VMT.as_ref().unwrap()
+    ));
+
+    unsafe { registry::register_vtable::<Circle>(circle.layout().vtable()) }.unwrap();
+    assert!(other.as_dyn().is_instance_of::<Circle>());
+}
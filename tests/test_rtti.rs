@@ -0,0 +1,135 @@
+#![cfg(target_pointer_width = "64")]
+
+use core::ptr::NonNull;
+
+use bridgeless::{
+    class,
+    module::Module,
+    rtti::{self, RttiError},
+};
+
+#[repr(C)]
+pub struct Player {
+    pub health: i32,
+}
+
+#[class]
+pub trait Player {
+    fn health(&self) -> i32;
+    fn max_health(&self) -> i32;
+}
+
+#[repr(C)]
+pub struct Boss {
+    pub phase: i32,
+}
+
+#[class]
+pub trait Boss {
+    fn health(&self) -> i32;
+    fn max_health(&self) -> i32;
+    fn enrage(&mut self);
+}
+
+extern "C" fn slot_0() {}
+extern "C" fn slot_1() {}
+
+const IMAGE_SIZE: usize = 0x1000;
+const TYPE_DESCRIPTOR: usize = 0x200;
+const LOCATOR: usize = 0x300;
+const VTABLE: usize = 0x408;
+
+/// Builds a minimal PE image with the MSVC RTTI data of a class `game::Player` with two virtual
+/// functions.
+fn msvc_image() -> Module {
+    let image: &'static mut [u64] = vec![0u64; IMAGE_SIZE / 8].leak();
+    let base = image.as_mut_ptr() as *mut u8;
+    let bytes = unsafe { core::slice::from_raw_parts_mut(base, IMAGE_SIZE) };
+    let mut write = |offset: usize, data: &[u8]| {
+        bytes[offset..offset + data.len()].copy_from_slice(data);
+    };
+
+    // DOS header, NT headers without optional header and a single readable section
+    write(0, b"MZ");
+    write(0x3c, &0x40u32.to_le_bytes());
+    write(0x40, b"PE\0\0");
+    write(0x40 + 6, &1u16.to_le_bytes());
+    write(0x58 + 8, &(IMAGE_SIZE as u32 - 0x100).to_le_bytes());
+    write(0x58 + 12, &0x100u32.to_le_bytes());
+    write(0x58 + 36, &0x4000_0000u32.to_le_bytes());
+
+    // TypeDescriptor, RTTICompleteObjectLocator and vtable
+    write(TYPE_DESCRIPTOR + 16, b".?AVPlayer@game@@\0");
+    write(LOCATOR, &1u32.to_le_bytes());
+    write(LOCATOR + 12, &(TYPE_DESCRIPTOR as u32).to_le_bytes());
+    write(LOCATOR + 20, &(LOCATOR as u32).to_le_bytes());
+    write(VTABLE - 8, &(base as usize + LOCATOR).to_le_bytes());
+    write(VTABLE, &(slot_0 as *const () as usize).to_le_bytes());
+    write(VTABLE + 8, &(slot_1 as *const () as usize).to_le_bytes());
+
+    unsafe { Module::from_base(NonNull::new(base).unwrap()) }
+}
+
+#[test]
+fn find_msvc_vtables() {
+    let module = msvc_image();
+    let vtable = unsafe { module.base().add(VTABLE) } as *mut u8;
+
+    unsafe {
+        assert_eq!(
+            rtti::find_vtable_mangled(module, b".?AVPlayer@game@@"),
+            NonNull::new(vtable)
+        );
+        assert_eq!(
+            rtti::find_vtable(module, "game::Player"),
+            Ok(NonNull::new(vtable).unwrap())
+        );
+        assert_eq!(
+            rtti::find_vtable(module, "game::Boss"),
+            Err(RttiError::NotFound)
+        );
+        assert_eq!(
+            rtti::find_vtable_mangled(module, b".?AUPlayer@game@@"),
+            None
+        );
+    }
+}
+
+#[test]
+fn validate_msvc_vtables() {
+    let module = msvc_image();
+    let vtable = unsafe { module.base().add(VTABLE) };
+
+    let vmt = unsafe { rtti::find_class_vmt::<Player>(module, "game::Player") }.unwrap();
+    assert_eq!(vmt as *const _ as *const u8, vtable);
+
+    // The third slot of `Boss` is past the vtable
+    assert_eq!(
+        unsafe { rtti::find_class_vmt::<Boss>(module, "game::Player") }.map(|_| ()),
+        Err(RttiError::InvalidSlot(2))
+    );
+}
+
+#[test]
+fn mangle_names() {
+    let module = msvc_image();
+    for name in [
+        "",
+        "::Player",
+        "game::",
+        "game:Player",
+        "Array<int>",
+        "game::*",
+    ] {
+        assert_eq!(
+            unsafe { rtti::find_vtable(module, name) },
+            Err(RttiError::InvalidName),
+            "{name}"
+        );
+    }
+    let long_name = "a".repeat(rtti::MAX_NAME_LEN);
+    assert_eq!(
+        unsafe { rtti::find_vtable(module, &long_name) },
+        Err(RttiError::InvalidName)
+    );
+}
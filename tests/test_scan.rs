@@ -0,0 +1,113 @@
+use core::{hint::black_box, ptr::NonNull};
+
+use bridgeless::{
+    module::Module,
+    scan::{self, Pattern, PatternError, MAX_PATTERN_LEN},
+};
+
+#[test]
+fn parse_patterns() {
+    const PATTERN: Pattern = Pattern::new("48 8b ?? ? 4? ?5");
+    assert_eq!(PATTERN.len(), 6);
+    assert_eq!(format!("{PATTERN:?}"), "Pattern(\"48 8B ?? ?? 4? ?5\")");
+
+    assert_eq!(Pattern::parse("  "), Err(PatternError::Empty));
+    assert_eq!(Pattern::parse("48 8G"), Err(PatternError::InvalidByte(3)));
+    assert_eq!(Pattern::parse("48 123"), Err(PatternError::InvalidByte(3)));
+    assert_eq!(Pattern::parse("48 8"), Err(PatternError::InvalidByte(3)));
+
+    let too_long = "?? ".repeat(MAX_PATTERN_LEN + 1);
+    assert_eq!(Pattern::parse(&too_long), Err(PatternError::TooLong));
+    assert!(Pattern::parse(&too_long[3..]).is_ok());
+}
+
+#[test]
+fn scan_buffers() {
+    let buffer = [
+        0x90, 0x48, 0x8b, 0x05, 0x10, 0x48, 0x8b, 0x0d, 0x20, 0x48, 0x8b,
+    ];
+    let pattern = Pattern::new("48 8B ?5");
+
+    assert_eq!(pattern.find(&buffer), Some(1));
+    assert_eq!(pattern.find_iter(&buffer).collect::<Vec<_>>(), [1]);
+    assert_eq!(
+        Pattern::new("48 8B ??").find_iter(&buffer).collect::<Vec<_>>(),
+        [1, 5]
+    );
+    assert_eq!(Pattern::new("48 8B 0? 2?").find(&buffer), Some(5));
+    assert_eq!(Pattern::new("48 8B 0D 10").find(&buffer), None);
+    assert_eq!(Pattern::from_bytes(&[0x20, 0x48]).find(&buffer), Some(8));
+
+    // Matches can't extend past the end of the buffer
+    assert_eq!(Pattern::new("48 8B ??").find(&buffer[5..]), Some(0));
+    assert_eq!(Pattern::new("48 8B ??").find(&buffer[9..]), None);
+}
+
+#[test]
+fn resolve_relative_operands() {
+    // lea rax, [rip + 0x10]; call -0x20
+    let code = [
+        0x48, 0x8d, 0x05, 0x10, 0, 0, 0, 0xe8, 0xe0, 0xff, 0xff, 0xff,
+    ];
+    let lea = NonNull::from(&code[0]);
+    let call = NonNull::from(&code[7]);
+
+    let lea_target = unsafe { scan::resolve_relative(lea, 3, 7) };
+    assert_eq!(
+        lea_target.as_ptr() as usize,
+        lea.as_ptr() as usize + 7 + 0x10
+    );
+    let call_target = unsafe { scan::resolve_relative(call, 1, 5) };
+    assert_eq!(
+        call_target.as_ptr() as usize,
+        call.as_ptr() as usize + 5 - 0x20
+    );
+}
+
+static MARKER: [u8; 16] = *b"bridgeless\x00scan\x01";
+
+#[inline(never)]
+extern "C" fn scanned_function(value: u32) -> u32 {
+    black_box(value).wrapping_mul(0x9e37_79b9).rotate_left(7) ^ 0x5bd1_e995
+}
+
+#[test]
+fn module_sections() {
+    let module = Module::main();
+    assert_eq!(
+        Module::containing(scanned_function as *const ()),
+        Some(module)
+    );
+    assert_eq!(
+        Module::containing(&MARKER as *const _ as *const ()),
+        Some(module)
+    );
+
+    let code = module.section_containing(scanned_function as *const ()).unwrap();
+    assert!(code.is_readable() && code.is_executable() && !code.is_writable());
+
+    let data = module.section_containing(&MARKER as *const _ as *const ()).unwrap();
+    assert!(data.is_readable() && !data.is_executable());
+    assert!(module.sections().any(|section| section.is_writable()));
+}
+
+#[test]
+fn scan_own_module() {
+    let module = Module::main();
+
+    let marker = unsafe { module.find_pattern(&Pattern::from_bytes(black_box(&MARKER))) };
+    assert_eq!(
+        marker.map(NonNull::as_ptr),
+        Some(MARKER.as_ptr() as *mut u8)
+    );
+
+    let code = unsafe { core::slice::from_raw_parts(scanned_function as *const u8, 24) };
+    let found = unsafe { module.find_code_pattern(&Pattern::from_bytes(code)) }.unwrap();
+    assert_eq!(
+        found.as_ptr() as usize,
+        scanned_function as *const () as usize
+    );
+
+    let function: extern "C" fn(u32) -> u32 = unsafe { scan::function(found) };
+    assert_eq!(function(12345), scanned_function(12345));
+}
@@ -0,0 +1,92 @@
+use bridgeless::*;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Vector3 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq)]
+pub struct Id(pub u64);
+
+#[repr(C)]
+pub struct Body {
+    pub mass: f32,
+}
+
+#[class]
+pub trait Body {
+    #[sret]
+    fn velocity(&self) -> Vector3 {
+        Vector3 {
+            x: 0.0,
+            y: 0.0,
+            z: self.mass,
+        }
+    }
+
+    #[sret(non_trivial)]
+    fn id(&mut self, salt: u64) -> Id {
+        Id(salt)
+    }
+
+    fn mass(&self) -> f32 {
+        self.mass
+    }
+}
+
+#[repr(C)]
+pub struct Projectile {
+    pub speed: f32,
+}
+
+#[class]
+pub trait Projectile: Body_Meta {}
+
+#[class_impl]
+impl Body_Impl for Impl<Projectile> {
+    fn velocity(&self) -> Vector3 {
+        Vector3 {
+            x: self.speed,
+            y: 1.0,
+            z: self.upcast::<Body>().mass,
+        }
+    }
+
+    fn id(&mut self, salt: u64) -> Id {
+        Id(salt * 2)
+    }
+}
+
+#[test]
+fn call_sret_methods() {
+    let mut body: Cls<Body> = Cls::new(BodyLayout((), Body { mass: 2.0 }));
+    assert_eq!(
+        body.velocity(),
+        Vector3 {
+            x: 0.0,
+            y: 0.0,
+            z: 2.0
+        }
+    );
+    assert_eq!(body.id(3), Id(3));
+    assert_eq!(body.mass(), 2.0);
+
+    let mut projectile: Cls<Projectile> = Cls::new(ProjectileLayout(
+        BodyLayout((), Body { mass: 0.5 }),
+        Projectile { speed: 10.0 },
+    ));
+    assert_eq!(
+        projectile.velocity(),
+        Vector3 {
+            x: 10.0,
+            y: 1.0,
+            z: 0.5
+        }
+    );
+    let body: &mut DynCls<Body> = projectile.as_mut();
+    assert_eq!(body.id(3), Id(6));
+}
@@ -0,0 +1,203 @@
+//! Tests for the MSVC STL layouts, using hand-built memory since no MSVC compiler is available.
+//! libstdc++ layouts are tested against g++-built fixtures in `cpp_tests`.
+
+use std::mem::{size_of, size_of_val};
+
+use bridgeless::stl::*;
+
+/// `std::basic_string` layout in MSVC's STL.
+#[repr(C)]
+struct RawMsvcString {
+    bx: [usize; 2],
+    size: usize,
+    res: usize,
+}
+
+impl RawMsvcString {
+    fn small(chars: &[u8]) -> Self {
+        let mut raw = RawMsvcString {
+            bx: [0; 2],
+            size: chars.len(),
+            res: 15,
+        };
+        let buf = raw.bx.as_mut_ptr() as *mut u8;
+        unsafe { buf.copy_from(chars.as_ptr(), chars.len()) };
+        raw
+    }
+
+    fn large(buf: &mut [u8], len: usize) -> Self {
+        RawMsvcString {
+            bx: [buf.as_mut_ptr() as usize, 0],
+            size: len,
+            res: buf.len() - 1,
+        }
+    }
+
+    fn view(&mut self) -> &mut CxxString<MsvcStl> {
+        unsafe { &mut *(self as *mut Self as *mut CxxString<MsvcStl>) }
+    }
+}
+
+#[test]
+fn layout_sizes() {
+    assert_eq!(size_of::<CxxString<MsvcStl>>(), 4 * size_of::<usize>());
+    assert_eq!(size_of::<CxxString<LibStdCxx>>(), 4 * size_of::<usize>());
+    assert_eq!(size_of::<CxxU16String<MsvcStl>>(), 4 * size_of::<usize>());
+    assert_eq!(size_of::<CxxVector<u64>>(), 3 * size_of::<usize>());
+    assert_eq!(size_of::<CxxUniquePtr<u64>>(), size_of::<usize>());
+    assert_eq!(size_of::<CxxSharedPtr<u64>>(), 2 * size_of::<usize>());
+}
+
+#[test]
+fn msvc_small_string() {
+    let mut raw = RawMsvcString::small(b"hello");
+    let string = raw.view();
+
+    assert_eq!(string.len(), 5);
+    assert_eq!(string.capacity(), 15);
+    assert_eq!(string, "hello");
+
+    string.try_assign_str("hello world!").unwrap();
+    assert!(string.try_push(b"1234").is_err());
+    assert_eq!(string.as_c_str(), c"hello world!");
+    assert_eq!(raw.size, 12);
+}
+
+#[test]
+fn msvc_large_string() {
+    let mut buf = *b"a string that does not fit in SSO\0\0\0\0\0\0";
+    let mut raw = RawMsvcString::large(&mut buf, 33);
+    let string = raw.view();
+
+    assert_eq!(string.len(), 33);
+    assert_eq!(string.capacity(), 38);
+    assert_eq!(string.to_str(), Ok("a string that does not fit in SSO"));
+
+    string.try_push(b"!!!!!").unwrap();
+    assert!(string.try_push(b"!").is_err());
+    string.truncate(8);
+    assert_eq!(&buf[..9], b"a string\0");
+}
+
+#[test]
+fn msvc_wide_string() {
+    let mut raw = RawMsvcString {
+        bx: [0; 2],
+        size: 0,
+        res: 7,
+    };
+    let wide: &mut CxxWString<MsvcStl> = unsafe { &mut *(&mut raw as *mut _ as *mut _) };
+
+    let chars: Vec<u16> = "wide".encode_utf16().collect();
+    wide.try_assign(&chars).unwrap();
+    assert_eq!(wide.as_slice(), chars);
+    assert_eq!(wide.capacity(), 7);
+    assert_eq!(size_of_val(&wide.as_slice()[0]), 2);
+}
+
+/// `std::list` node layout in MSVC's STL.
+#[repr(C)]
+struct RawMsvcListNode {
+    next: *const RawMsvcListNode,
+    prev: *const RawMsvcListNode,
+    value: i32,
+}
+
+#[test]
+fn msvc_list() {
+    let mut nodes: Vec<_> = (0..4)
+        .map(|i| RawMsvcListNode {
+            next: std::ptr::null(),
+            prev: std::ptr::null(),
+            value: i * 10,
+        })
+        .collect();
+    // Node 0 is the sentinel head
+    let base = nodes.as_mut_ptr();
+    for i in 0..4 {
+        unsafe {
+            (*base.add(i)).next = base.add((i + 1) % 4);
+            (*base.add(i)).prev = base.add((i + 3) % 4);
+        }
+    }
+    let raw = [base as usize, 3];
+    let list: &CxxList<i32, MsvcStl> = unsafe { &*(&raw as *const _ as *const _) };
+
+    assert_eq!(list.len(), 3);
+    assert_eq!(list.front(), Some(&10));
+    assert_eq!(list.iter().copied().collect::<Vec<_>>(), [10, 20, 30]);
+    assert_eq!(format!("{list:?}"), "[10, 20, 30]");
+}
+
+/// `std::_Tree` node layout in MSVC's STL.
+#[repr(C)]
+struct RawMsvcTreeNode {
+    left: *const RawMsvcTreeNode,
+    parent: *const RawMsvcTreeNode,
+    right: *const RawMsvcTreeNode,
+    color: u8,
+    is_nil: u8,
+    key: i32,
+    value: i32,
+}
+
+#[test]
+fn msvc_map() {
+    let node = |key| RawMsvcTreeNode {
+        left: std::ptr::null(),
+        parent: std::ptr::null(),
+        right: std::ptr::null(),
+        color: 0,
+        is_nil: 0,
+        key,
+        value: key * 100,
+    };
+    // Head, then the tree 2 -> (1, 4 -> (3, _))
+    let mut nodes = [node(0), node(1), node(2), node(3), node(4)];
+    let n = nodes.as_mut_ptr();
+    unsafe {
+        let [head, n1, n2, n3, n4] = [0, 1, 2, 3, 4].map(|i| n.add(i));
+        (*head).is_nil = 1;
+        *head = RawMsvcTreeNode {
+            left: n1,
+            parent: n2,
+            right: n4,
+            ..*head
+        };
+        *n1 = RawMsvcTreeNode {
+            left: head,
+            parent: n2,
+            right: head,
+            ..*n1
+        };
+        *n2 = RawMsvcTreeNode {
+            left: n1,
+            parent: head,
+            right: n4,
+            ..*n2
+        };
+        *n3 = RawMsvcTreeNode {
+            left: head,
+            parent: n4,
+            right: head,
+            ..*n3
+        };
+        *n4 = RawMsvcTreeNode {
+            left: n3,
+            parent: n2,
+            right: head,
+            ..*n4
+        };
+    }
+    let raw = [n as usize, 4];
+    let map: &CxxMap<i32, i32, MsvcStl> = unsafe { &*(&raw as *const _ as *const _) };
+
+    assert_eq!(map.len(), 4);
+    assert_eq!(map.keys().copied().collect::<Vec<_>>(), [1, 2, 3, 4]);
+    assert_eq!(
+        map.values().copied().collect::<Vec<_>>(),
+        [100, 200, 300, 400]
+    );
+    assert_eq!(map.get(&3), Some(&300));
+    assert_eq!(map.get(&5), None);
+}
@@ -0,0 +1,123 @@
+use std::ptr::NonNull;
+
+use bridgeless::{
+    weak::{FnResolver, GenHandle, HandleResolver},
+    *,
+};
+
+#[repr(C)]
+pub struct A {
+    pub a_field: usize,
+}
+
+#[class]
+pub trait A {
+    fn virt_a(&mut self) -> usize {
+        42
+    }
+}
+
+#[repr(C)]
+pub struct B {
+    pub b_field: usize,
+}
+
+#[class]
+pub trait B: A_Meta {}
+
+#[class_impl]
+impl A_Impl for Impl<B> {}
+
+/// Minimal generational handle table, similar to what game engines use to track objects.
+#[derive(Default)]
+struct HandleTable {
+    slots: Vec<(u32, Option<Box<Cls<B>>>)>,
+}
+
+impl HandleTable {
+    fn insert(&mut self, obj: Cls<B>) -> CWeak<B, GenHandle> {
+        let index = self.slots.len() as u32;
+        self.slots.push((0, Some(Box::new(obj))));
+        CWeak::new(GenHandle::new(index, 0))
+    }
+
+    fn remove(&mut self, handle: &GenHandle) {
+        let slot = &mut self.slots[handle.index as usize];
+        slot.0 += 1;
+        slot.1 = None;
+    }
+}
+
+unsafe impl HandleResolver<B, GenHandle> for HandleTable {
+    fn resolve(&self, handle: &GenHandle) -> Option<NonNull<Cls<B>>> {
+        match self.slots.get(handle.index as usize)? {
+            (generation, Some(obj)) if *generation == handle.generation => {
+                Some(NonNull::from(obj.as_ref()))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn make_b(a_field: usize, b_field: usize) -> Cls<B> {
+    Cls::new(BLayout(ALayout((), A { a_field }), B { b_field }))
+}
+
+#[test]
+fn upgrade_live_handle() {
+    let mut table = HandleTable::default();
+    let weak = table.insert(make_b(1, 2));
+
+    let obj = weak.upgrade(&table).unwrap();
+    assert_eq!(obj.b_field, 2);
+    assert_eq!(obj.upcast::<A>().a_field, 1);
+    assert!(weak.is_alive(&table));
+}
+
+#[test]
+fn upgrade_invalidated_handle() {
+    let mut table = HandleTable::default();
+    let weak = table.insert(make_b(1, 2));
+    let other = table.insert(make_b(3, 4));
+
+    table.remove(weak.handle());
+    assert!(weak.upgrade(&table).is_none());
+    assert!(!weak.is_alive(&table));
+    assert_eq!(other.upgrade(&table).unwrap().b_field, 4);
+}
+
+#[test]
+fn upgrade_mut_and_call_virtual() {
+    let mut table = HandleTable::default();
+    let weak = table.insert(make_b(1, 2));
+
+    let mut obj = weak.upgrade_mut(&mut table).unwrap();
+    obj.b_field = 5;
+    assert_eq!(obj.virt_a(), 42);
+    assert_eq!(weak.upgrade(&table).unwrap().b_field, 5);
+}
+
+#[test]
+fn upcast_resolves_to_base() {
+    let mut table = HandleTable::default();
+    let weak: CWeak<A, GenHandle, B> = table.insert(make_b(7, 8)).upcast();
+
+    let mut obj = weak.upgrade_mut(&mut table).unwrap();
+    assert_eq!(obj.a_field, 7);
+    assert_eq!(obj.virt_a(), 42);
+
+    table.remove(weak.handle());
+    assert!(weak.upgrade(&table).is_none());
+}
+
+#[test]
+fn closure_resolver() {
+    let obj = make_b(1, 2);
+    let live = GenHandle::new(0, 3);
+    let resolver =
+        unsafe { FnResolver::new(|h: &GenHandle| (*h == live).then(|| NonNull::from(&obj))) };
+
+    let weak = CWeak::<B, _>::new(live).upcast::<A>();
+    assert_eq!(weak.upgrade(&resolver).unwrap().a_field, 1);
+    assert!(CWeak::<B, _>::new(GenHandle::new(0, 2)).upgrade(&resolver).is_none());
+}